@@ -0,0 +1,23 @@
+//! `cargo testcontainers`: lists, prunes and tails the logs of the resources created by testcontainers.
+
+use std::process::ExitCode;
+
+use clap::Parser;
+use testcontainers::cli::Cli;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // when invoked as `cargo testcontainers`, cargo passes the name of the subcommand first
+    let args = std::env::args_os()
+        .enumerate()
+        .filter(|(index, arg)| !(*index == 1 && arg == "testcontainers"))
+        .map(|(_, arg)| arg);
+
+    match testcontainers::cli::run(Cli::parse_from(args)).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
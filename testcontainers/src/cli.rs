@@ -0,0 +1,274 @@
+//! Implementation of the `cargo testcontainers` companion binary.
+//!
+//! Resources started by `testcontainers` are identified by the automatic labels,
+//! see `TESTCONTAINERS_LABELS_NAMESPACE` for the details.
+
+use clap::{Args, Parser, Subcommand};
+use futures::StreamExt;
+
+use crate::core::{
+    client::Client,
+    env::Command,
+    error::{Result, TestcontainersError},
+    logs::LogSource,
+};
+
+/// Manages the resources created by testcontainers.
+#[derive(Debug, Parser)]
+#[command(
+    name = "cargo-testcontainers",
+    bin_name = "cargo testcontainers",
+    version
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Prints the resolved Docker host and configuration.
+    Config,
+    /// Lists the containers and networks created by testcontainers.
+    List(Filter),
+    /// Removes the containers and networks created by testcontainers.
+    Prune {
+        #[command(flatten)]
+        filter: Filter,
+        /// Only print the resources which would be removed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Prints the logs of the containers created by testcontainers, prefixed with their names.
+    Logs {
+        /// Ids or names of the containers, all the running containers matching the filter if omitted.
+        containers: Vec<String>,
+        #[command(flatten)]
+        filter: Filter,
+        /// Follow the logs until the containers are stopped.
+        #[arg(short, long)]
+        follow: bool,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Filter {
+    /// Only the resources of the session, as labeled for containers reused within the session.
+    #[arg(long)]
+    session: Option<String>,
+    /// Only the resources having the label, in the `key=value` format.
+    #[arg(long = "label", value_name = "KEY=VALUE")]
+    labels: Vec<String>,
+}
+
+/// Runs the command of the companion binary.
+pub async fn run(cli: Cli) -> Result<()> {
+    let client = Client::lazy_client().await?;
+    match cli.command {
+        CliCommand::Config => print_config(&client),
+        CliCommand::List(filter) => list(&client, &filter).await,
+        CliCommand::Prune { filter, dry_run } => prune(&client, &filter, dry_run).await,
+        CliCommand::Logs {
+            containers,
+            filter,
+            follow,
+        } => logs(&client, containers, &filter, follow).await,
+    }
+}
+
+fn print_config(client: &Client) -> Result<()> {
+    let config = &client.config;
+    println!("docker host:        {}", config.docker_host());
+    println!("tls verify:         {}", config.tls_verify());
+    println!(
+        "cert path:          {}",
+        config
+            .cert_path()
+            .map_or_else(|| "-".to_string(), |path| path.display().to_string())
+    );
+    println!(
+        "command:            {}",
+        match config.command() {
+            Command::Keep => "keep",
+            Command::Remove => "remove",
+        }
+    );
+    println!(
+        "labels namespace:   {}",
+        config.labels_namespace().unwrap_or("(disabled)")
+    );
+    println!(
+        "docker auth config: {}",
+        if config.docker_auth_config().is_some() {
+            "configured"
+        } else {
+            "not configured"
+        }
+    );
+    Ok(())
+}
+
+impl Filter {
+    /// Returns the namespace of the automatic labels along with the label filters.
+    fn labels(&self, client: &Client) -> Result<(String, Vec<String>)> {
+        let namespace = client.config.labels_namespace().ok_or_else(|| {
+            TestcontainersError::other(
+                "automatic labels are disabled by TESTCONTAINERS_LABELS_NAMESPACE, \
+                 the resources created by testcontainers can't be identified",
+            )
+        })?;
+
+        let mut labels = vec![format!("{namespace}.managed-by=testcontainers")];
+        if let Some(session) = &self.session {
+            labels.push(format!("{namespace}.session-id={session}"));
+        }
+        labels.extend(self.labels.iter().cloned());
+        Ok((namespace.to_string(), labels))
+    }
+}
+
+async fn list(client: &Client, filter: &Filter) -> Result<()> {
+    let (namespace, labels) = filter.labels(client)?;
+    let session_label = format!("{namespace}.session-id");
+
+    let containers = client.list_labeled_containers(&labels).await?;
+    println!(
+        "{:<12}  {:<32}  {:<32}  {:<10}  SESSION",
+        "CONTAINER", "NAME", "IMAGE", "STATE"
+    );
+    for container in containers {
+        let session = container
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.get(&session_label))
+            .map_or("-", String::as_str);
+        println!(
+            "{:<12}  {:<32}  {:<32}  {:<10}  {session}",
+            short_id(container.id.as_deref().unwrap_or_default()),
+            container_name(container.names.as_deref()),
+            container.image.as_deref().unwrap_or("-"),
+            container.state.as_deref().unwrap_or("-"),
+        );
+    }
+
+    let networks = client.list_labeled_networks(&labels).await?;
+    println!();
+    println!("{:<12}  NAME", "NETWORK");
+    for network in networks {
+        println!(
+            "{:<12}  {}",
+            short_id(network.id.as_deref().unwrap_or_default()),
+            network.name.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
+
+async fn prune(client: &Client, filter: &Filter, dry_run: bool) -> Result<()> {
+    let (_, labels) = filter.labels(client)?;
+
+    // containers go first, as they keep the networks in use
+    for container in client.list_labeled_containers(&labels).await? {
+        let Some(id) = container.id else {
+            continue;
+        };
+        println!(
+            "removing container {} ({})",
+            short_id(&id),
+            container_name(container.names.as_deref())
+        );
+        if !dry_run {
+            client.rm(&id).await?;
+        }
+    }
+    for network in client.list_labeled_networks(&labels).await? {
+        let Some(name) = network.name else {
+            continue;
+        };
+        println!("removing network {name}");
+        if !dry_run {
+            client.remove_network(&name).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn logs(
+    client: &Client,
+    containers: Vec<String>,
+    filter: &Filter,
+    follow: bool,
+) -> Result<()> {
+    let containers = if containers.is_empty() {
+        let (_, labels) = filter.labels(client)?;
+        client
+            .list_labeled_containers(&labels)
+            .await?
+            .into_iter()
+            .filter(|container| container.state.as_deref() == Some("running"))
+            .filter_map(|container| {
+                let name = container_name(container.names.as_deref()).to_string();
+                container.id.map(|id| (id, name))
+            })
+            .collect()
+    } else {
+        containers
+            .into_iter()
+            .map(|container| (container.clone(), container))
+            .collect::<Vec<_>>()
+    };
+
+    let streams = containers.into_iter().map(|(id, name)| {
+        client
+            .logs(&id, follow)
+            .into_lines()
+            .map(move |line| (name.clone(), line))
+    });
+    let mut lines = futures::stream::select_all(streams);
+    while let Some((name, line)) = lines.next().await {
+        let line = line?;
+        match line.source() {
+            LogSource::StdOut => println!("[{name}] {}", line.content_lossy()),
+            LogSource::StdErr => eprintln!("[{name}] {}", line.content_lossy()),
+        }
+    }
+    Ok(())
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(12)]
+}
+
+fn container_name(names: Option<&[String]>) -> &str {
+    names
+        .and_then(|names| names.first())
+        .map_or("-", |name| name.trim_start_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn cli_is_consistent() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::parse_from([
+            "cargo-testcontainers",
+            "prune",
+            "--session",
+            "01J",
+            "--label",
+            "team=db",
+            "--dry-run",
+        ]);
+        let CliCommand::Prune { filter, dry_run } = cli.command else {
+            panic!("unexpected command: {:?}", cli.command);
+        };
+        assert!(dry_run);
+        assert_eq!(filter.session.as_deref(), Some("01J"));
+        assert_eq!(filter.labels, ["team=db"]);
+    }
+}
@@ -2,13 +2,18 @@
 pub use self::image::ReuseDirective;
 pub use self::{
     containers::*,
-    image::{ContainerState, ExecCommand, Image, ImageExt},
+    image::{
+        ContainerState, ExecCommand, ExecFailurePolicy, HostConfigHints, Image, ImageDecision,
+        ImageExt, ImageMetadata,
+    },
     mounts::{AccessMode, Mount, MountType},
+    network::{Network, NetworkBuilder, NetworkEndpoint},
     ports::{ContainerPort, IntoContainerPort},
+    volume::{Volume, VolumeBuilder},
     wait::{cmd_wait::CmdWaitFor, WaitFor},
 };
 
-mod image;
+pub(crate) mod image;
 
 pub(crate) mod async_drop;
 pub mod client;
@@ -16,8 +21,10 @@ pub(crate) mod containers;
 pub(crate) mod copy;
 pub(crate) mod env;
 pub mod error;
+pub(crate) mod lifecycle;
 pub mod logs;
 pub(crate) mod mounts;
 pub(crate) mod network;
 pub mod ports;
+pub(crate) mod volume;
 pub mod wait;
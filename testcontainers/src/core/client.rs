@@ -1,29 +1,50 @@
 use std::{
     collections::HashMap,
     io::{self},
+    path::Path,
+    pin::Pin,
     str::FromStr,
+    sync::RwLock,
+    time::{Duration, SystemTime},
 };
 
+#[cfg(feature = "cli")]
+use bollard::network::ListNetworksOptions;
 use bollard::{
-    auth::DockerCredentials,
     container::{
-        Config, CreateContainerOptions, ListContainersOptions, LogOutput, LogsOptions,
-        RemoveContainerOptions, UploadToContainerOptions,
+        AttachContainerOptions, Config, CreateContainerOptions, DownloadFromContainerOptions,
+        KillContainerOptions, ListContainersOptions, LogOutput, LogsOptions,
+        RemoveContainerOptions, Stats, StatsOptions, StopContainerOptions,
+        UploadToContainerOptions,
     },
     errors::Error as BollardError,
     exec::{CreateExecOptions, StartExecOptions, StartExecResults},
-    image::CreateImageOptions,
-    network::{CreateNetworkOptions, InspectNetworkOptions},
+    image::{
+        CommitContainerOptions, CreateImageOptions, ListImagesOptions, RemoveImageOptions,
+        TagImageOptions,
+    },
+    network::{ConnectNetworkOptions, CreateNetworkOptions, InspectNetworkOptions},
+    system::EventsOptions,
+    volume::{CreateVolumeOptions, ListVolumesOptions, RemoveVolumeOptions},
     Docker,
 };
-use bollard_stubs::models::{ContainerInspectResponse, ExecInspectResponse, Network};
-use futures::{StreamExt, TryStreamExt};
-use tokio::sync::OnceCell;
+#[cfg(any(feature = "cli", feature = "reusable-containers"))]
+use bollard_stubs::models::ContainerSummary;
+use bollard_stubs::models::{
+    ContainerInspectResponse, DistributionInspect, EndpointSettings, EventMessage,
+    ExecInspectResponse, FilesystemChange, ImageInspect, ImageSummary, Network,
+};
+use bytes::Bytes;
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    sync::OnceCell,
+};
 use url::Url;
 
 use crate::core::{
     client::exec::ExecResult,
-    copy::{CopyToContainer, CopyToContainerError},
+    copy::{self, CopyFromContainerError, CopyToContainer, CopyToContainerError},
     env,
     env::ConfigurationError,
     logs::{
@@ -34,18 +55,28 @@ use crate::core::{
 };
 
 mod bollard_client;
+mod capabilities;
+mod credentials;
+mod disk_usage;
 mod exec;
 mod factory;
+mod reconnect;
 
-pub use factory::docker_client_instance;
+pub use capabilities::{client_capabilities, CgroupDriver, CgroupVersion, ClientCapabilities};
+pub use disk_usage::{disk_usage, DiskUsage, DiskUsageSummary};
+pub use factory::{docker_client_instance, invalidate_host_cache};
 
 static IN_A_CONTAINER: OnceCell<bool> = OnceCell::const_new();
 
 // See https://github.com/docker/docker/blob/a9fa38b1edf30b23cae3eade0be48b3d4b1de14b/daemon/initlayer/setup_unix.go#L25
 // and Java impl: https://github.com/testcontainers/testcontainers-java/blob/994b385761dde7d832ab7b6c10bc62747fe4b340/core/src/main/java/org/testcontainers/dockerclient/DockerClientConfigUtils.java#L16C5-L17
+// Podman creates `/run/.containerenv` instead.
 async fn is_in_container() -> bool {
     *IN_A_CONTAINER
-        .get_or_init(|| async { tokio::fs::metadata("/.dockerenv").await.is_ok() })
+        .get_or_init(|| async {
+            tokio::fs::metadata("/.dockerenv").await.is_ok()
+                || tokio::fs::metadata("/run/.containerenv").await.is_ok()
+        })
         .await
 }
 
@@ -64,6 +95,28 @@ pub enum ClientError {
         descriptor: String,
         err: BollardError,
     },
+    #[error("failed to inspect the image '{descriptor}', error: {err}")]
+    InspectImage {
+        descriptor: String,
+        err: BollardError,
+    },
+    #[error("failed to remove the image '{descriptor}', error: {err}")]
+    RemoveImage {
+        descriptor: String,
+        err: BollardError,
+    },
+    #[error("failed to tag the image '{descriptor}', error: {err}")]
+    TagImage {
+        descriptor: String,
+        err: BollardError,
+    },
+    #[error("failed to list images: {0}")]
+    ListImages(BollardError),
+    #[error("failed to inspect the image '{descriptor}' in the registry, error: {err}")]
+    InspectRegistryImage {
+        descriptor: String,
+        err: BollardError,
+    },
     #[error("failed to map ports: {0}")]
     PortMapping(#[from] PortMappingError),
 
@@ -77,8 +130,22 @@ pub enum ClientError {
     StartContainer(BollardError),
     #[error("failed to stop a container: {0}")]
     StopContainer(BollardError),
+    #[error("failed to restart a container: {0}")]
+    RestartContainer(BollardError),
+    #[error("failed to kill a container: {0}")]
+    KillContainer(BollardError),
+    #[error("failed to commit a container: {0}")]
+    CommitContainer(BollardError),
+    #[error("failed to get filesystem changes of a container: {0}")]
+    ContainerChanges(BollardError),
+    #[error("failed to get stats of a container: {0}")]
+    ContainerStats(BollardError),
+    #[error("failed to subscribe to events: {0}")]
+    Events(BollardError),
     #[error("failed to inspect a container: {0}")]
     InspectContainer(BollardError),
+    #[error("failed to attach to a container: {0}")]
+    AttachContainer(BollardError),
 
     #[error("failed to create a network: {0}")]
     CreateNetwork(BollardError),
@@ -88,43 +155,103 @@ pub enum ClientError {
     ListNetworks(BollardError),
     #[error("failed to remove a network: {0}")]
     RemoveNetwork(BollardError),
+    #[error("failed to connect a container to a network: {0}")]
+    ConnectNetwork(BollardError),
+    #[error("failed to create a volume: {0}")]
+    CreateVolume(BollardError),
+    #[error("failed to list volumes: {0}")]
+    ListVolumes(BollardError),
+    #[error("failed to remove a volume: {0}")]
+    RemoveVolume(BollardError),
 
     #[error("failed to initialize exec command: {0}")]
     InitExec(BollardError),
     #[error("failed to inspect exec command: {0}")]
     InspectExec(BollardError),
+    #[error("failed to write to stdin of exec command: {0}")]
+    WriteExecStdin(io::Error),
+
+    #[error("lost connection to the docker daemon (was it restarted?): {0}")]
+    DaemonRestarted(BollardError),
+    #[error("failed to get the daemon version: {0}")]
+    DaemonVersion(BollardError),
+    #[error("failed to get the daemon info: {0}")]
+    DaemonInfo(BollardError),
+    #[error("failed to get the disk usage of the daemon: {0}")]
+    DiskUsage(BollardError),
     #[error("failed to upload data to container: {0}")]
     UploadToContainerError(BollardError),
     #[error("failed to prepare data for copy-to-container: {0}")]
     CopyToContainerError(CopyToContainerError),
+    #[error("failed to download data from container: {0}")]
+    DownloadFromContainerError(BollardError),
+    #[error("failed to extract data copied from container: {0}")]
+    CopyFromContainerError(CopyFromContainerError),
 }
 
 /// The internal client.
 pub(crate) struct Client {
     pub(crate) config: env::Config,
-    bollard: Docker,
+    bollard: RwLock<Docker>,
+    // resolved lazily, see `Client::docker_hostname`
+    docker_hostname: std::sync::Mutex<Option<url::Host>>,
+    credentials: credentials::CredentialsCache,
 }
 
 impl Client {
     async fn new() -> Result<Client, ClientError> {
         let config = env::Config::load::<env::Os>().await?;
+        Self::from_config(config)
+    }
+
+    async fn with_docker_host(docker_host: &str) -> Result<Client, ClientError> {
+        let config = env::Config::load::<env::Os>()
+            .await?
+            .with_docker_host(docker_host);
+        Self::from_config(config)
+    }
+
+    fn from_config(config: env::Config) -> Result<Client, ClientError> {
         let bollard = bollard_client::init(&config).map_err(ClientError::Init)?;
 
-        Ok(Client { config, bollard })
+        Ok(Client {
+            config,
+            bollard: RwLock::new(bollard),
+            docker_hostname: std::sync::Mutex::new(None),
+            credentials: credentials::CredentialsCache::new(),
+        })
     }
 
     pub(crate) fn stdout_logs(&self, id: &str, follow: bool) -> RawLogStream {
-        self.logs_stream(id, Some(LogSource::StdOut), follow)
+        self.logs_stream(id, Some(LogSource::StdOut), follow, false, None)
             .into_stdout()
     }
 
     pub(crate) fn stderr_logs(&self, id: &str, follow: bool) -> RawLogStream {
-        self.logs_stream(id, Some(LogSource::StdErr), follow)
+        self.logs_stream(id, Some(LogSource::StdErr), follow, false, None)
             .into_stderr()
     }
 
     pub(crate) fn logs(&self, id: &str, follow: bool) -> LogStream {
-        self.logs_stream(id, None, follow)
+        self.logs_stream(id, None, follow, false, None)
+    }
+
+    pub(crate) fn timestamped_logs(&self, id: &str, follow: bool) -> LogStream {
+        self.logs_stream(id, None, follow, true, None)
+            .with_timestamps()
+    }
+
+    /// Follows the timestamped logs of the source (or both sources), starting from the second of `since`.
+    ///
+    /// Docker filters the logs by whole seconds, so earlier lines of that second are included.
+    pub(crate) fn timestamped_logs_since(
+        &self,
+        id: &str,
+        source: Option<LogSource>,
+        since: SystemTime,
+    ) -> LogStream {
+        self.logs_stream(id, source, true, true, Some(since))
+            .with_timestamps()
     }
 
     pub(crate) async fn ports(&self, id: &str) -> Result<Ports, ClientError> {
@@ -142,14 +269,13 @@ impl Client {
     }
 
     pub(crate) async fn inspect(&self, id: &str) -> Result<ContainerInspectResponse, ClientError> {
-        self.bollard
-            .inspect_container(id, None)
+        self.with_reconnect(|docker| async move { docker.inspect_container(id, None).await })
             .await
             .map_err(ClientError::InspectContainer)
     }
 
     pub(crate) async fn rm(&self, id: &str) -> Result<(), ClientError> {
-        self.bollard
+        self.bollard()
             .remove_container(
                 id,
                 Some(RemoveContainerOptions {
@@ -159,21 +285,71 @@ impl Client {
                 }),
             )
             .await
-            .map_err(ClientError::RemoveContainer)
+            .map_err(|err| self.stateful_error(err, ClientError::RemoveContainer))
+    }
+
+    /// Stops the container with SIGTERM, killing it once the timeout (rounded up to whole seconds) elapses.
+    /// Without a timeout, the one of the container applies (10 seconds by default).
+    pub(crate) async fn stop(
+        &self,
+        id: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(), ClientError> {
+        let options = timeout.map(|timeout| StopContainerOptions {
+            // a sub-second timeout would kill the container right away
+            t: timeout.as_secs_f64().ceil() as i64,
+        });
+        self.bollard()
+            .stop_container(id, options)
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::StopContainer))
+    }
+
+    pub(crate) async fn restart(&self, id: &str) -> Result<(), ClientError> {
+        self.bollard()
+            .restart_container(id, None)
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::RestartContainer))
+    }
+
+    /// Creates an image `repo:tag` from the current state of the container, which is paused meanwhile.
+    pub(crate) async fn commit(&self, id: &str, repo: &str, tag: &str) -> Result<(), ClientError> {
+        let options = CommitContainerOptions {
+            container: id,
+            repo,
+            tag,
+            pause: true,
+            ..Default::default()
+        };
+        self.bollard()
+            .commit_container(options, Config::<String>::default())
+            .await
+            .map(|_| ())
+            .map_err(|err| self.stateful_error(err, ClientError::CommitContainer))
     }
 
-    pub(crate) async fn stop(&self, id: &str) -> Result<(), ClientError> {
-        self.bollard
-            .stop_container(id, None)
+    pub(crate) async fn changes(&self, id: &str) -> Result<Vec<FilesystemChange>, ClientError> {
+        self.bollard()
+            .container_changes(id)
             .await
-            .map_err(ClientError::StopContainer)
+            .map(Option::unwrap_or_default)
+            .map_err(|err| self.stateful_error(err, ClientError::ContainerChanges))
+    }
+
+    /// Sends the signal to the main process of the container, `SIGKILL` by default.
+    pub(crate) async fn kill(&self, id: &str, signal: Option<&str>) -> Result<(), ClientError> {
+        let options = signal.map(|signal| KillContainerOptions { signal });
+        self.bollard()
+            .kill_container(id, options)
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::KillContainer))
     }
 
     pub(crate) async fn start(&self, id: &str) -> Result<(), ClientError> {
-        self.bollard
+        self.bollard()
             .start_container::<String>(id, None)
             .await
-            .map_err(ClientError::Init)
+            .map_err(|err| self.stateful_error(err, ClientError::Init))
     }
 
     pub(crate) async fn exec(
@@ -181,21 +357,35 @@ impl Client {
         container_id: &str,
         cmd: Vec<String>,
     ) -> Result<ExecResult, ClientError> {
-        let config = CreateExecOptions {
+        let options = CreateExecOptions {
             cmd: Some(cmd),
+            ..Default::default()
+        };
+        self.exec_with_options(container_id, options, None).await
+    }
+
+    /// Executes a command configured with the `options`, writing the `stdin` payload to it if passed.
+    pub(crate) async fn exec_with_options(
+        &self,
+        container_id: &str,
+        options: CreateExecOptions<String>,
+        stdin: Option<Bytes>,
+    ) -> Result<ExecResult, ClientError> {
+        let config = CreateExecOptions {
+            attach_stdin: Some(stdin.is_some()),
             attach_stdout: Some(true),
             attach_stderr: Some(true),
-            ..Default::default()
+            ..options
         };
 
         let exec = self
-            .bollard
+            .bollard()
             .create_exec(container_id, config)
             .await
-            .map_err(ClientError::InitExec)?;
+            .map_err(|err| self.stateful_error(err, ClientError::InitExec))?;
 
         let res = self
-            .bollard
+            .bollard()
             .start_exec(
                 &exec.id,
                 Some(StartExecOptions {
@@ -205,14 +395,23 @@ impl Client {
                 }),
             )
             .await
-            .map_err(ClientError::InitExec)?;
+            .map_err(|err| self.stateful_error(err, ClientError::InitExec))?;
 
         match res {
-            StartExecResults::Attached { output, .. } => {
+            StartExecResults::Attached { output, mut input } => {
                 let (stdout, stderr) = LogStream::from(output).split().await;
                 let stdout = WaitingStreamWrapper::new(stdout).enable_cache();
                 let stderr = WaitingStreamWrapper::new(stderr).enable_cache();
 
+                if let Some(stdin) = stdin {
+                    // shutting down the input closes stdin of the command
+                    input
+                        .write_all(&stdin)
+                        .await
+                        .and(input.shutdown().await)
+                        .map_err(ClientError::WriteExecStdin)?;
+                }
+
                 Ok(ExecResult {
                     id: exec.id,
                     stdout,
@@ -227,8 +426,7 @@ impl Client {
         &self,
         exec_id: &str,
     ) -> Result<ExecInspectResponse, ClientError> {
-        self.bollard
-            .inspect_exec(exec_id)
+        self.with_reconnect(|docker| async move { docker.inspect_exec(exec_id).await })
             .await
             .map_err(ClientError::InspectExec)
     }
@@ -238,58 +436,159 @@ impl Client {
         container_id: &str,
         source_filter: Option<LogSource>,
         follow: bool,
+        timestamps: bool,
+        since: Option<SystemTime>,
     ) -> LogStream {
+        let since = since
+            .and_then(|since| since.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |since| since.as_secs() as i64);
         let options = LogsOptions {
             follow,
+            timestamps,
+            since,
             stdout: source_filter.map(LogSource::is_stdout).unwrap_or(true),
             stderr: source_filter.map(LogSource::is_stderr).unwrap_or(true),
             tail: "all".to_owned(),
             ..Default::default()
         };
 
-        self.bollard.logs(container_id, Some(options)).into()
+        self.bollard().logs(container_id, Some(options)).into()
+    }
+
+    /// Returns the resource usage of the container, streamed about once a second if `stream` is set.
+    pub(crate) fn stats(
+        &self,
+        container_id: &str,
+        stream: bool,
+    ) -> BoxStream<'static, Result<Stats, ClientError>> {
+        let options = StatsOptions {
+            stream,
+            one_shot: false,
+        };
+        self.bollard()
+            .stats(container_id, Some(options))
+            .map_err(ClientError::ContainerStats)
+            .boxed()
+    }
+
+    /// Streams the engine events matching the filters (e.g. `type=container`), starting from now on.
+    ///
+    /// The stream only connects once polled, so the events are requested from the time of the call,
+    /// replaying the ones occurring in between.
+    pub(crate) fn events(
+        &self,
+        filters: HashMap<String, Vec<String>>,
+    ) -> BoxStream<'static, Result<EventMessage, ClientError>> {
+        let since = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let options = EventsOptions {
+            since: Some(format!("{}.{:09}", since.as_secs(), since.subsec_nanos())),
+            filters,
+            ..Default::default()
+        };
+        self.bollard()
+            .events(Some(options))
+            .map_err(ClientError::Events)
+            .boxed()
     }
 
     /// Creates a network with given name and returns an ID
-    pub(crate) async fn create_network(&self, name: &str) -> Result<String, ClientError> {
+    pub(crate) async fn create_network(
+        &self,
+        mut options: CreateNetworkOptions<String>,
+    ) -> Result<String, ClientError> {
+        // same as for containers, the automatic label can't be overridden by the user
+        if let Some(namespace) = self.config.labels_namespace() {
+            options.labels.insert(
+                format!("{namespace}.managed-by"),
+                "testcontainers".to_string(),
+            );
+        }
         let network = self
-            .bollard
+            .bollard()
             .create_network(CreateNetworkOptions {
-                name: name.to_owned(),
                 check_duplicate: true,
-                ..Default::default()
+                ..options
             })
             .await
-            .map_err(ClientError::CreateNetwork)?;
+            .map_err(|err| self.stateful_error(err, ClientError::CreateNetwork))?;
 
         Ok(network.id)
     }
 
+    pub(crate) async fn connect_network(
+        &self,
+        network: &str,
+        container_id: &str,
+        endpoint_config: EndpointSettings,
+    ) -> Result<(), ClientError> {
+        let options = ConnectNetworkOptions {
+            container: container_id,
+            endpoint_config,
+        };
+        self.bollard()
+            .connect_network(network, options)
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::ConnectNetwork))
+    }
+
     /// Inspects a network
     pub(crate) async fn inspect_network(&self, name: &str) -> Result<Network, ClientError> {
-        self.bollard
-            .inspect_network(name, Some(InspectNetworkOptions::<String>::default()))
-            .await
-            .map_err(ClientError::InspectNetwork)
+        self.with_reconnect(|docker| async move {
+            docker
+                .inspect_network(name, Some(InspectNetworkOptions::<String>::default()))
+                .await
+        })
+        .await
+        .map_err(ClientError::InspectNetwork)
     }
 
+    /// Creates a container, returning its ID along with the warnings reported by the daemon.
     pub(crate) async fn create_container(
         &self,
         options: Option<CreateContainerOptions<String>>,
         config: Config<String>,
-    ) -> Result<String, ClientError> {
-        self.bollard
+    ) -> Result<(String, Vec<String>), ClientError> {
+        let created = self
+            .bollard()
             .create_container(options.clone(), config.clone())
             .await
-            .map(|res| res.id)
-            .map_err(ClientError::CreateContainer)
+            .map_err(|err| self.stateful_error(err, ClientError::CreateContainer))?;
+
+        // e.g. ignored resource limits, which often explain failures later on
+        for warning in &created.warnings {
+            log::warn!(
+                "Docker daemon reported a warning on creation of container {}: {warning}",
+                created.id
+            );
+        }
+        Ok((created.id, created.warnings))
     }
 
     pub(crate) async fn start_container(&self, container_id: &str) -> Result<(), ClientError> {
-        self.bollard
+        self.bollard()
             .start_container::<String>(container_id, None)
             .await
-            .map_err(ClientError::StartContainer)
+            .map_err(|err| self.stateful_error(err, ClientError::StartContainer))
+    }
+
+    pub(crate) async fn attach_stdin(
+        &self,
+        container_id: &str,
+    ) -> Result<Pin<Box<dyn AsyncWrite + Send>>, ClientError> {
+        let options = AttachContainerOptions::<String> {
+            stdin: Some(true),
+            stream: Some(true),
+            ..Default::default()
+        };
+
+        let attached = self
+            .bollard()
+            .attach_container(container_id, Some(options))
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::AttachContainer))?;
+        Ok(attached.input)
     }
 
     pub(crate) async fn copy_to_container(
@@ -309,10 +608,63 @@ impl Client {
             .await
             .map_err(ClientError::CopyToContainerError)?;
 
-        self.bollard
+        self.bollard()
             .upload_to_container::<String>(&container_id, Some(options), tar)
             .await
-            .map_err(ClientError::UploadToContainerError)
+            .map_err(|err| self.stateful_error(err, ClientError::UploadToContainerError))
+    }
+
+    pub(crate) async fn copy_dir_from_container(
+        &self,
+        container_id: &str,
+        source_path: &str,
+        target_dir: &Path,
+    ) -> Result<(), ClientError> {
+        let tar = self
+            .download_from_container(container_id, source_path)
+            .await?;
+        copy::untar_dir(tar, source_path, target_dir)
+            .await
+            .map_err(ClientError::CopyFromContainerError)
+    }
+
+    pub(crate) async fn open_file_from_container(
+        &self,
+        container_id: &str,
+        source_path: &str,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>, ClientError> {
+        let tar = self
+            .download_from_container(container_id, source_path)
+            .await?;
+        copy::untar_file(tar, source_path)
+            .await
+            .map_err(ClientError::CopyFromContainerError)
+    }
+
+    /// Returns the archive of `source_path` as a reader, streamed as it's downloaded.
+    async fn download_from_container(
+        &self,
+        container_id: &str,
+        source_path: &str,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>, ClientError> {
+        let options = DownloadFromContainerOptions {
+            path: source_path.to_string(),
+        };
+        let mut tar = self
+            .bollard()
+            .download_from_container(container_id, Some(options));
+
+        // errors of the request itself (e.g. missing path) are reported with the first chunk
+        let first_chunk = tar
+            .next()
+            .await
+            .transpose()
+            .map_err(|err| self.stateful_error(err, ClientError::DownloadFromContainerError))?;
+        let tar = futures::stream::iter(first_chunk.map(Ok))
+            .chain(tar)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err));
+
+        Ok(Box::pin(tokio_util::io::StreamReader::new(tar)))
     }
 
     pub(crate) async fn pull_image(&self, descriptor: &str) -> Result<(), ClientError> {
@@ -321,7 +673,7 @@ impl Client {
             ..Default::default()
         });
         let credentials = self.credentials_for_image(descriptor).await;
-        let mut pulling = self.bollard.create_image(pull_options, None, credentials);
+        let mut pulling = self.bollard().create_image(pull_options, None, credentials);
         while let Some(result) = pulling.next().await {
             result.map_err(|err| ClientError::PullImage {
                 descriptor: descriptor.to_string(),
@@ -331,10 +683,115 @@ impl Client {
         Ok(())
     }
 
+    pub(crate) async fn inspect_image(
+        &self,
+        descriptor: &str,
+    ) -> Result<ImageInspect, ClientError> {
+        self.with_reconnect(|docker| async move { docker.inspect_image(descriptor).await })
+            .await
+            .map_err(|err| ClientError::InspectImage {
+                descriptor: descriptor.to_string(),
+                err,
+            })
+    }
+
+    pub(crate) async fn remove_image(
+        &self,
+        descriptor: &str,
+        force: bool,
+    ) -> Result<(), ClientError> {
+        let options = RemoveImageOptions {
+            force,
+            noprune: false,
+        };
+        self.bollard()
+            .remove_image(descriptor, Some(options), None)
+            .await
+            .map(drop)
+            .map_err(|err| ClientError::RemoveImage {
+                descriptor: descriptor.to_string(),
+                err,
+            })
+    }
+
+    pub(crate) async fn tag_image(
+        &self,
+        descriptor: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<(), ClientError> {
+        self.bollard()
+            .tag_image(descriptor, Some(TagImageOptions { repo, tag }))
+            .await
+            .map_err(|err| ClientError::TagImage {
+                descriptor: descriptor.to_string(),
+                err,
+            })
+    }
+
+    pub(crate) async fn list_images(
+        &self,
+        filters: HashMap<&str, Vec<&str>>,
+    ) -> Result<Vec<ImageSummary>, ClientError> {
+        let options = ListImagesOptions {
+            filters,
+            ..Default::default()
+        };
+        self.with_reconnect(|docker| {
+            let options = options.clone();
+            async move { docker.list_images(Some(options)).await }
+        })
+        .await
+        .map_err(ClientError::ListImages)
+    }
+
+    /// Resolves the manifest of the image in the registry, without pulling it.
+    pub(crate) async fn inspect_registry_image(
+        &self,
+        descriptor: &str,
+    ) -> Result<DistributionInspect, ClientError> {
+        let credentials = self.credentials_for_image(descriptor).await;
+        self.bollard()
+            .inspect_registry_image(descriptor, credentials)
+            .await
+            .map_err(|err| ClientError::InspectRegistryImage {
+                descriptor: descriptor.to_string(),
+                err,
+            })
+    }
+
+    /// Returns the shell (e.g. `["/bin/sh", "-c"]`) to be used for running commands in the image.
+    /// The image is pulled if it's not available locally.
+    pub(crate) async fn image_shell(&self, descriptor: &str) -> Result<Vec<String>, ClientError> {
+        let image = match self.inspect_image(descriptor).await {
+            Err(ClientError::InspectImage {
+                err:
+                    BollardError::DockerResponseServerError {
+                        status_code: 404, ..
+                    },
+                ..
+            }) => {
+                self.pull_image(descriptor).await?;
+                self.inspect_image(descriptor).await?
+            }
+            res => res?,
+        };
+
+        let shell = image
+            .config
+            .and_then(|config| config.shell)
+            .filter(|shell| !shell.is_empty())
+            .unwrap_or_else(|| match image.os.as_deref() {
+                Some("windows") => vec!["cmd".to_string(), "/S".to_string(), "/C".to_string()],
+                _ => vec!["/bin/sh".to_string(), "-c".to_string()],
+            });
+
+        Ok(shell)
+    }
+
     pub(crate) async fn network_exists(&self, network: &str) -> Result<bool, ClientError> {
         let networks = self
-            .bollard
-            .list_networks::<String>(None)
+            .with_reconnect(|docker| async move { docker.list_networks::<String>(None).await })
             .await
             .map_err(ClientError::ListNetworks)?;
 
@@ -343,14 +800,127 @@ impl Client {
             .any(|i| matches!(&i.name, Some(name) if name == network)))
     }
 
+    /// Lists the networks having all the labels, given in the `key=value` format.
+    #[cfg(feature = "cli")]
+    pub(crate) async fn list_labeled_networks(
+        &self,
+        labels: &[String],
+    ) -> Result<Vec<Network>, ClientError> {
+        let options = ListNetworksOptions {
+            filters: HashMap::from([("label".to_string(), labels.to_vec())]),
+        };
+        self.with_reconnect(|docker| {
+            let options = options.clone();
+            async move { docker.list_networks(Some(options)).await }
+        })
+        .await
+        .map_err(ClientError::ListNetworks)
+    }
+
+    /// Lists the containers having all the labels, given in the `key=value` format, including the stopped ones.
+    #[cfg(any(feature = "cli", feature = "reusable-containers"))]
+    pub(crate) async fn list_labeled_containers(
+        &self,
+        labels: &[String],
+    ) -> Result<Vec<ContainerSummary>, ClientError> {
+        let options = ListContainersOptions {
+            all: true,
+            filters: HashMap::from([("label".to_string(), labels.to_vec())]),
+            ..Default::default()
+        };
+        self.with_reconnect(|docker| {
+            let options = options.clone();
+            async move { docker.list_containers(Some(options)).await }
+        })
+        .await
+        .map_err(ClientError::ListContainers)
+    }
+
     pub(crate) async fn remove_network(&self, network: &str) -> Result<(), ClientError> {
-        self.bollard
+        self.bollard()
             .remove_network(network)
             .await
-            .map_err(ClientError::RemoveNetwork)
+            .map_err(|err| self.stateful_error(err, ClientError::RemoveNetwork))
+    }
+
+    /// Creates a volume and returns its name, generated by the daemon unless given in the options.
+    pub(crate) async fn create_volume(
+        &self,
+        mut options: CreateVolumeOptions<String>,
+    ) -> Result<String, ClientError> {
+        // same as for containers, the automatic label can't be overridden by the user
+        if let Some(namespace) = self.config.labels_namespace() {
+            options.labels.insert(
+                format!("{namespace}.managed-by"),
+                "testcontainers".to_string(),
+            );
+        }
+        let volume = self
+            .bollard()
+            .create_volume(options)
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::CreateVolume))?;
+
+        Ok(volume.name)
+    }
+
+    /// Lists the names of the volumes matching the filters.
+    pub(crate) async fn list_volumes(
+        &self,
+        filters: HashMap<String, Vec<String>>,
+    ) -> Result<Vec<String>, ClientError> {
+        let options = ListVolumesOptions { filters };
+        let response = self
+            .with_reconnect(|docker| {
+                let options = options.clone();
+                async move { docker.list_volumes(Some(options)).await }
+            })
+            .await
+            .map_err(ClientError::ListVolumes)?;
+
+        Ok(response
+            .volumes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|volume| volume.name)
+            .collect())
+    }
+
+    pub(crate) async fn remove_volume(&self, name: &str, force: bool) -> Result<(), ClientError> {
+        self.bollard()
+            .remove_volume(name, Some(RemoveVolumeOptions { force }))
+            .await
+            .map_err(|err| self.stateful_error(err, ClientError::RemoveVolume))
     }
 
+    /// Returns the host containers are reached on, resolving it once per client.
     pub(crate) async fn docker_hostname(&self) -> Result<url::Host, ClientError> {
+        if let Some(host) = self.cached_docker_hostname().clone() {
+            return Ok(host);
+        }
+
+        let host = self.resolve_docker_hostname().await?;
+        *self.cached_docker_hostname() = Some(host.clone());
+        Ok(host)
+    }
+
+    /// Drops the resolved host, so it's resolved again on the next use.
+    pub(crate) fn invalidate_docker_hostname(&self) {
+        self.cached_docker_hostname().take();
+    }
+
+    fn cached_docker_hostname(&self) -> std::sync::MutexGuard<'_, Option<url::Host>> {
+        self.docker_hostname
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    async fn resolve_docker_hostname(&self) -> Result<url::Host, ClientError> {
+        if let Some(host) = self.config.host_override() {
+            return url::Host::parse(host)
+                .map_err(|_| ConfigurationError::InvalidHostOverride(host.to_string()).into());
+        }
+
         let docker_host = &self.config.docker_host();
         let docker_host_url = Url::from_str(docker_host)
             .map_err(|e| ConfigurationError::InvalidDockerHost(e.to_string()))?;
@@ -365,8 +935,8 @@ impl Client {
             "unix" | "npipe" => {
                 if is_in_container().await {
                     let host = self
-                        .bollard
-                        .inspect_network::<String>("bridge", None)
+                        .bollard()
+                        .inspect_network::<String>(self.config.gateway_network(), None)
                         .await
                         .ok()
                         .and_then(|net| net.ipam)
@@ -388,39 +958,6 @@ impl Client {
         }
     }
 
-    async fn credentials_for_image(&self, descriptor: &str) -> Option<DockerCredentials> {
-        let auth_config = self.config.docker_auth_config()?.to_string();
-        let (server, _) = descriptor.split_once('/')?;
-
-        // `docker_credential` uses blocking API, thus we spawn blocking task to prevent executor from being blocked
-        let cloned_server = server.to_string();
-        let credentials = tokio::task::spawn_blocking(move || {
-            docker_credential::get_credential_from_reader(auth_config.as_bytes(), &cloned_server)
-                .ok()
-        })
-        .await
-        .ok()
-        .flatten()?;
-
-        let bollard_credentials = match credentials {
-            docker_credential::DockerCredential::IdentityToken(token) => DockerCredentials {
-                identitytoken: Some(token),
-                serveraddress: Some(server.to_string()),
-                ..DockerCredentials::default()
-            },
-            docker_credential::DockerCredential::UsernamePassword(username, password) => {
-                DockerCredentials {
-                    username: Some(username),
-                    password: Some(password),
-                    serveraddress: Some(server.to_string()),
-                    ..DockerCredentials::default()
-                }
-            }
-        };
-
-        Some(bollard_credentials)
-    }
-
     /// Get the `id` of the first running container whose `name`, `network`,
     /// and `labels` match the supplied values
     #[cfg_attr(not(feature = "reusable-containers"), allow(dead_code))]
@@ -454,8 +991,10 @@ impl Client {
         });
 
         let containers = self
-            .bollard
-            .list_containers(options)
+            .with_reconnect(|docker| {
+                let options = options.clone();
+                async move { docker.list_containers(options).await }
+            })
             .await
             .map_err(ClientError::ListContainers)?;
 
@@ -482,12 +1021,20 @@ where
 {
     fn from(stream: BS) -> Self {
         let stream = stream
-            .map_ok(|chunk| match chunk {
-                LogOutput::StdErr { message } => LogFrame::StdErr(message),
-                LogOutput::StdOut { message } => LogFrame::StdOut(message),
-                LogOutput::StdIn { .. } | LogOutput::Console { .. } => {
-                    unreachable!("only stdout and stderr are supported")
-                }
+            .try_filter_map(|chunk| {
+                let frame = match chunk {
+                    LogOutput::StdErr { message } => Some(LogFrame::StdErr(message)),
+                    // the output of a container with a TTY isn't multiplexed, it's reported as console output
+                    LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                        Some(LogFrame::StdOut(message))
+                    }
+                    // the input written to the container isn't part of its output
+                    LogOutput::StdIn { message } => {
+                        log::trace!("Skipping stdin frame of {} bytes", message.len());
+                        None
+                    }
+                };
+                futures::future::ready(Ok(frame))
             })
             .map_err(|err| match err {
                 BollardError::DockerResponseServerError {
@@ -504,3 +1051,110 @@ where
         LogStream::new(stream)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct FakeEnvTcpHost;
+
+    impl env::GetEnvValue for FakeEnvTcpHost {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://docker-a:2375".to_owned()),
+                _ => env::without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvHostOverride;
+
+    impl env::GetEnvValue for FakeEnvHostOverride {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://docker-a:2375".to_owned()),
+                "TESTCONTAINERS_HOST_OVERRIDE" => Some("10.0.0.7".to_owned()),
+                _ => env::without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvInvalidHostOverride;
+
+    impl env::GetEnvValue for FakeEnvInvalidHostOverride {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://docker-a:2375".to_owned()),
+                "TESTCONTAINERS_HOST_OVERRIDE" => Some("not a host".to_owned()),
+                _ => env::without_docker_config(key),
+            }
+        }
+    }
+
+    async fn client_for<E: env::GetEnvValue>() -> Client {
+        Client::from_config(env::Config::load::<E>().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn host_override_takes_precedence_over_docker_host() {
+        let client = client_for::<FakeEnvHostOverride>().await;
+        assert_eq!(
+            client.docker_hostname().await.unwrap(),
+            url::Host::<String>::Ipv4([10, 0, 0, 7].into())
+        );
+
+        let client = client_for::<FakeEnvInvalidHostOverride>().await;
+        let err = client.docker_hostname().await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ClientError::Configuration(ConfigurationError::InvalidHostOverride(_))
+            ),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolved_host_is_cached_until_invalidated() {
+        let client = client_for::<FakeEnvTcpHost>().await;
+        let resolved = url::Host::Domain("docker-a".to_string());
+        assert_eq!(client.docker_hostname().await.unwrap(), resolved);
+
+        // a cached host is returned without being resolved again
+        let stale = url::Host::Domain("stale".to_string());
+        *client.cached_docker_hostname() = Some(stale.clone());
+        assert_eq!(client.docker_hostname().await.unwrap(), stale);
+
+        client.invalidate_docker_hostname();
+        assert_eq!(client.docker_hostname().await.unwrap(), resolved);
+    }
+
+    #[tokio::test]
+    async fn poisoned_host_cache_is_still_used() {
+        let client = client_for::<FakeEnvTcpHost>().await;
+        std::thread::scope(|scope| {
+            let poisoning = scope.spawn(|| {
+                let _guard = client.cached_docker_hostname();
+                panic!("poisoning the lock");
+            });
+            assert!(poisoning.join().is_err());
+        });
+
+        assert_eq!(
+            client.docker_hostname().await.unwrap(),
+            url::Host::Domain("docker-a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn host_cache_of_shared_clients_is_invalidated() {
+        let client = Client::lazy_client().await.unwrap();
+        *client.cached_docker_hostname() = Some(url::Host::Domain("stale".to_string()));
+
+        invalidate_host_cache().await;
+        assert_eq!(*client.cached_docker_hostname(), None);
+    }
+}
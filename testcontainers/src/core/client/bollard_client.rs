@@ -33,14 +33,59 @@ pub(super) fn init(config: &env::Config) -> Result<Docker, bollard::errors::Erro
 }
 
 fn connect_with_ssl(config: &env::Config) -> Result<Docker, bollard::errors::Error> {
-    let cert_path = config.cert_path().expect("cert path not found");
+    let cert_path = config
+        .cert_path()
+        .ok_or(bollard::errors::Error::NoHomePathError)?;
+    let [key, cert, ca] = ["key.pem", "cert.pem", "ca.pem"].map(|file| cert_path.join(file));
+
+    // the client key and certificate are loaded only on the first request, fail early instead
+    if let Some(missing) = [&key, &cert, &ca].into_iter().find(|path| !path.is_file()) {
+        return Err(bollard::errors::Error::CertPathError {
+            path: missing.clone(),
+        });
+    }
 
     Docker::connect_with_ssl(
         &config.docker_host(),
-        &cert_path.join("key.pem"),
-        &cert_path.join("cert.pem"),
-        &cert_path.join("ca.pem"),
+        &key,
+        &cert,
+        &ca,
         DEFAULT_TIMEOUT.as_secs(),
         API_DEFAULT_VERSION,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::env::GetEnvValue;
+
+    #[derive(Debug)]
+    struct FakeEnvTlsWithoutCerts;
+
+    impl GetEnvValue for FakeEnvTlsWithoutCerts {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_HOST" => Some("tcp://remote-daemon:2376".to_owned()),
+                "DOCKER_TLS_VERIFY" => Some("1".to_owned()),
+                "DOCKER_CERT_PATH" => Some("/nonexistent/docker/certs".to_owned()),
+                _ => env::without_docker_config(key),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_certificates_are_reported() {
+        let config = env::Config::load::<FakeEnvTlsWithoutCerts>().await.unwrap();
+
+        let err = init(&config).unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                bollard::errors::Error::CertPathError { path }
+                    if path.ends_with("/nonexistent/docker/certs/key.pem")
+            ),
+            "unexpected error: {err}"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use bollard::system::Version;
+use bollard_stubs::models::{SystemInfo, SystemInfoCgroupDriverEnum, SystemInfoCgroupVersionEnum};
+
+use crate::core::client::{Client, ClientError};
+
+/// The minimal API version of the daemon supporting BuildKit (Docker 18.09).
+const BUILDKIT_MIN_API_VERSION: (u32, u32) = (1, 39);
+
+/// Capabilities of the Docker daemon the client is connected to.
+///
+/// Allows to detect the features supported by the daemon at runtime, e.g. in order to skip
+/// tests instead of failing obscurely on older or restricted daemons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCapabilities {
+    daemon_version: Option<String>,
+    api_version: Option<String>,
+    os_type: Option<String>,
+    buildkit: bool,
+    cgroup_version: Option<CgroupVersion>,
+    cgroup_driver: Option<CgroupDriver>,
+    rootless: bool,
+    podman: bool,
+    runtimes: Vec<String>,
+    default_runtime: Option<String>,
+}
+
+/// The version of the cgroup subsystem used by the daemon.
+#[derive(parse_display::Display, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CgroupVersion {
+    #[display("v1")]
+    V1,
+    #[display("v2")]
+    V2,
+}
+
+/// The driver managing the cgroups of the containers.
+#[derive(parse_display::Display, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CgroupDriver {
+    #[display("cgroupfs")]
+    Cgroupfs,
+    #[display("systemd")]
+    Systemd,
+}
+
+impl ClientCapabilities {
+    pub(crate) fn new(version: Version, info: SystemInfo) -> Self {
+        let podman = version
+            .components
+            .iter()
+            .flatten()
+            .any(|component| component.name.to_ascii_lowercase().contains("podman"));
+        let api_version = version.api_version;
+        let os_type = info.os_type.or(version.os);
+
+        let is_windows = os_type
+            .as_deref()
+            .is_some_and(|os| os.eq_ignore_ascii_case("windows"));
+        let buildkit = !is_windows
+            && api_version
+                .as_deref()
+                .and_then(parse_api_version)
+                .is_some_and(|version| version >= BUILDKIT_MIN_API_VERSION);
+
+        let cgroup_version = match info.cgroup_version {
+            Some(SystemInfoCgroupVersionEnum::_1) => Some(CgroupVersion::V1),
+            Some(SystemInfoCgroupVersionEnum::_2) => Some(CgroupVersion::V2),
+            Some(SystemInfoCgroupVersionEnum::EMPTY) | None => None,
+        };
+        let cgroup_driver = match info.cgroup_driver {
+            Some(SystemInfoCgroupDriverEnum::CGROUPFS) => Some(CgroupDriver::Cgroupfs),
+            Some(SystemInfoCgroupDriverEnum::SYSTEMD) => Some(CgroupDriver::Systemd),
+            Some(SystemInfoCgroupDriverEnum::EMPTY | SystemInfoCgroupDriverEnum::NONE) | None => {
+                None
+            }
+        };
+
+        let rootless = info
+            .security_options
+            .unwrap_or_default()
+            .iter()
+            .any(|option| option.split(',').any(|part| part == "name=rootless"));
+
+        let mut runtimes: Vec<_> = info.runtimes.unwrap_or_default().into_keys().collect();
+        runtimes.sort();
+
+        Self {
+            daemon_version: version.version.or(info.server_version),
+            api_version,
+            os_type,
+            buildkit,
+            cgroup_version,
+            cgroup_driver,
+            rootless,
+            podman,
+            runtimes,
+            default_runtime: info.default_runtime,
+        }
+    }
+
+    /// Version of the Docker daemon, e.g. `27.3.1`.
+    pub fn daemon_version(&self) -> Option<&str> {
+        self.daemon_version.as_deref()
+    }
+
+    /// The default (and highest) API version supported by the daemon, e.g. `1.47`.
+    pub fn api_version(&self) -> Option<&str> {
+        self.api_version.as_deref()
+    }
+
+    /// Operating system the daemon is running on, e.g. `linux` or `windows`.
+    pub fn os_type(&self) -> Option<&str> {
+        self.os_type.as_deref()
+    }
+
+    /// Whether the daemon is able to build images with BuildKit.
+    pub fn buildkit(&self) -> bool {
+        self.buildkit
+    }
+
+    /// The version of the cgroup subsystem, if reported by the daemon.
+    pub fn cgroup_version(&self) -> Option<CgroupVersion> {
+        self.cgroup_version
+    }
+
+    /// The driver managing the cgroups of the containers, if reported by the daemon.
+    pub fn cgroup_driver(&self) -> Option<CgroupDriver> {
+        self.cgroup_driver
+    }
+
+    /// Whether the daemon is running in rootless mode.
+    pub fn rootless(&self) -> bool {
+        self.rootless
+    }
+
+    /// Whether the daemon is actually Podman, serving the Docker-compatible API.
+    pub fn podman(&self) -> bool {
+        self.podman
+    }
+
+    /// Names of the container runtimes available on the daemon (e.g. `runc`, `runsc`), sorted alphabetically.
+    pub fn runtimes(&self) -> &[String] {
+        &self.runtimes
+    }
+
+    /// Returns `true` if the runtime with the given name is available on the daemon.
+    pub fn has_runtime(&self, name: &str) -> bool {
+        self.runtimes.iter().any(|runtime| runtime == name)
+    }
+
+    /// The runtime used by default for new containers.
+    pub fn default_runtime(&self) -> Option<&str> {
+        self.default_runtime.as_deref()
+    }
+}
+
+impl Client {
+    /// Detects capabilities of the daemon.
+    pub(crate) async fn capabilities(&self) -> Result<ClientCapabilities, ClientError> {
+        let version = self
+            .with_reconnect(|docker| async move { docker.version().await })
+            .await
+            .map_err(ClientError::DaemonVersion)?;
+        let info = self
+            .with_reconnect(|docker| async move { docker.info().await })
+            .await
+            .map_err(ClientError::DaemonInfo)?;
+
+        Ok(ClientCapabilities::new(version, info))
+    }
+}
+
+/// Returns the capabilities of the Docker daemon used by testcontainers.
+///
+/// Useful to skip tests depending on features which are not supported by the current environment:
+///
+/// ```rust,no_run
+/// use testcontainers::core::client::client_capabilities;
+///
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let caps = client_capabilities().await?;
+/// if !caps.buildkit() {
+///     eprintln!("BuildKit is not supported by the daemon, skipping");
+///     return Ok(());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn client_capabilities() -> Result<ClientCapabilities, ClientError> {
+    Client::lazy_client().await?.capabilities().await
+}
+
+fn parse_api_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::system::VersionComponents;
+    use bollard_stubs::models::Runtime;
+
+    use super::*;
+
+    #[test]
+    fn capabilities_are_detected_from_daemon_info() {
+        let version = Version {
+            version: Some("27.3.1".to_string()),
+            api_version: Some("1.47".to_string()),
+            ..Default::default()
+        };
+        let info = SystemInfo {
+            os_type: Some("linux".to_string()),
+            cgroup_version: Some(SystemInfoCgroupVersionEnum::_2),
+            cgroup_driver: Some(SystemInfoCgroupDriverEnum::SYSTEMD),
+            security_options: Some(vec![
+                "name=seccomp,profile=builtin".to_string(),
+                "name=rootless".to_string(),
+            ]),
+            runtimes: Some(HashMap::from([
+                ("runc".to_string(), Runtime::default()),
+                ("io.containerd.runc.v2".to_string(), Runtime::default()),
+            ])),
+            default_runtime: Some("runc".to_string()),
+            ..Default::default()
+        };
+
+        let caps = ClientCapabilities::new(version, info);
+
+        assert_eq!(caps.daemon_version(), Some("27.3.1"));
+        assert_eq!(caps.api_version(), Some("1.47"));
+        assert!(caps.buildkit());
+        assert_eq!(caps.cgroup_version(), Some(CgroupVersion::V2));
+        assert_eq!(caps.cgroup_driver(), Some(CgroupDriver::Systemd));
+        assert!(caps.rootless());
+        assert!(!caps.podman());
+        assert_eq!(caps.runtimes(), ["io.containerd.runc.v2", "runc"]);
+        assert!(caps.has_runtime("runc"));
+        assert_eq!(caps.default_runtime(), Some("runc"));
+    }
+
+    #[test]
+    fn buildkit_is_not_available_on_old_or_windows_daemons() {
+        let old_daemon = ClientCapabilities::new(
+            Version {
+                api_version: Some("1.38".to_string()),
+                ..Default::default()
+            },
+            SystemInfo::default(),
+        );
+        assert!(!old_daemon.buildkit());
+        assert!(!old_daemon.rootless());
+        assert_eq!(old_daemon.cgroup_version(), None);
+
+        let windows_daemon = ClientCapabilities::new(
+            Version {
+                api_version: Some("1.47".to_string()),
+                ..Default::default()
+            },
+            SystemInfo {
+                os_type: Some("windows".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(!windows_daemon.buildkit());
+    }
+
+    #[test]
+    fn podman_is_detected_from_version_components() {
+        let caps = ClientCapabilities::new(
+            Version {
+                components: Some(vec![VersionComponents {
+                    name: "Podman Engine".to_string(),
+                    version: "5.2.2".to_string(),
+                    details: None,
+                }]),
+                ..Default::default()
+            },
+            SystemInfo::default(),
+        );
+        assert!(caps.podman());
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use bollard::auth::DockerCredentials;
+use tokio::sync::Mutex;
+
+use crate::core::client::Client;
+
+/// How long the credentials resolved for a registry are reused, before asking the credential helper again.
+const CREDENTIALS_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Credentials resolved per registry, shared by all the containers started with the client.
+///
+/// Resolving the credentials may spawn a credential helper process (e.g. `docker-credential-ecr-login`),
+/// which is expensive to do for every container of a suite.
+#[derive(Debug)]
+pub(super) struct CredentialsCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedCredentials>>,
+}
+
+#[derive(Debug)]
+struct CachedCredentials {
+    resolved_at: Instant,
+    credentials: Option<DockerCredentials>,
+}
+
+impl CredentialsCache {
+    pub(super) fn new() -> Self {
+        Self::with_ttl(CREDENTIALS_TTL)
+    }
+
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// Returns the cached credentials of the registry, resolving them if they are missing or expired.
+    ///
+    /// The lock is held while resolving, so the concurrent starts wait for a single resolution.
+    /// The absence of credentials is cached as well.
+    async fn get_or_resolve<F, Fut>(&self, server: &str, resolve: F) -> Option<DockerCredentials>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Option<DockerCredentials>>,
+    {
+        let mut entries = self.entries.lock().await;
+        if let Some(cached) = entries.get(server) {
+            if cached.resolved_at.elapsed() < self.ttl {
+                return cached.credentials.clone();
+            }
+        }
+
+        let credentials = resolve().await;
+        entries.insert(
+            server.to_string(),
+            CachedCredentials {
+                resolved_at: Instant::now(),
+                credentials: credentials.clone(),
+            },
+        );
+        credentials
+    }
+}
+
+impl Client {
+    pub(super) async fn credentials_for_image(
+        &self,
+        descriptor: &str,
+    ) -> Option<DockerCredentials> {
+        let auth_config = self.config.docker_auth_config()?.to_string();
+        let (server, _) = descriptor.split_once('/')?;
+
+        self.credentials
+            .get_or_resolve(server, || resolve_credentials(auth_config, server))
+            .await
+    }
+}
+
+async fn resolve_credentials(auth_config: String, server: &str) -> Option<DockerCredentials> {
+    // `docker_credential` uses blocking API, thus we spawn blocking task to prevent executor from being blocked
+    let cloned_server = server.to_string();
+    let credentials = tokio::task::spawn_blocking(move || {
+        docker_credential::get_credential_from_reader(auth_config.as_bytes(), &cloned_server).ok()
+    })
+    .await
+    .ok()
+    .flatten()?;
+
+    let bollard_credentials = match credentials {
+        docker_credential::DockerCredential::IdentityToken(token) => DockerCredentials {
+            identitytoken: Some(token),
+            serveraddress: Some(server.to_string()),
+            ..DockerCredentials::default()
+        },
+        docker_credential::DockerCredential::UsernamePassword(username, password) => {
+            DockerCredentials {
+                username: Some(username),
+                password: Some(password),
+                serveraddress: Some(server.to_string()),
+                ..DockerCredentials::default()
+            }
+        }
+    };
+
+    Some(bollard_credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn credentials(username: &str) -> Option<DockerCredentials> {
+        Some(DockerCredentials {
+            username: Some(username.to_string()),
+            ..DockerCredentials::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn credentials_are_resolved_once_per_registry() {
+        let cache = CredentialsCache::new();
+        let resolutions = AtomicUsize::new(0);
+        let resolve = |username: &'static str| {
+            let resolutions = &resolutions;
+            move || async move {
+                resolutions.fetch_add(1, Ordering::SeqCst);
+                credentials(username)
+            }
+        };
+
+        let first = cache
+            .get_or_resolve("registry.example.com", resolve("a"))
+            .await;
+        let second = cache
+            .get_or_resolve("registry.example.com", resolve("b"))
+            .await;
+        let other = cache
+            .get_or_resolve("other.example.com", resolve("c"))
+            .await;
+
+        assert_eq!(first, credentials("a"));
+        assert_eq!(second, credentials("a"));
+        assert_eq!(other, credentials("c"));
+        assert_eq!(resolutions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_credentials_are_resolved_again() {
+        let cache = CredentialsCache::with_ttl(Duration::ZERO);
+
+        cache
+            .get_or_resolve("registry.example.com", || async { credentials("a") })
+            .await;
+        let resolved = cache
+            .get_or_resolve("registry.example.com", || async { credentials("b") })
+            .await;
+
+        assert_eq!(resolved, credentials("b"));
+    }
+}
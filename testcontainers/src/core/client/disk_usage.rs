@@ -0,0 +1,262 @@
+use bollard_stubs::models::SystemDataUsageResponse;
+
+use crate::core::client::{Client, ClientError};
+
+/// Disk usage of the Docker daemon, the same as reported by `docker system df`.
+///
+/// Allows long-running CI agents to log the usage or fail early when the daemon is nearly full,
+/// instead of failing on obscure `no space left on device` errors while pulling images.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    images: DiskUsageSummary,
+    containers: DiskUsageSummary,
+    volumes: DiskUsageSummary,
+    build_cache: DiskUsageSummary,
+}
+
+/// Disk usage of a kind of resources, e.g. images or volumes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsageSummary {
+    count: usize,
+    active: usize,
+    size: u64,
+    reclaimable: u64,
+}
+
+impl DiskUsage {
+    pub(crate) fn new(usage: SystemDataUsageResponse) -> Self {
+        let images = usage.images.unwrap_or_default();
+        let images_size = usage
+            .layers_size
+            .map(to_bytes)
+            .unwrap_or_else(|| images.iter().map(|image| to_bytes(image.size)).sum());
+        // the layers shared with other images are still in use, even if the image is removed
+        let images_in_use: u64 = images
+            .iter()
+            .filter(|image| image.containers > 0)
+            .map(|image| to_bytes(image.size).saturating_sub(to_bytes(image.shared_size)))
+            .sum();
+        let images = DiskUsageSummary {
+            count: images.len(),
+            active: images.iter().filter(|image| image.containers > 0).count(),
+            size: images_size,
+            reclaimable: images_size.saturating_sub(images_in_use),
+        };
+
+        let containers = DiskUsageSummary::collect(
+            usage.containers.unwrap_or_default(),
+            |container| container.state.as_deref() == Some("running"),
+            |container| container.size_rw.map_or(0, to_bytes),
+        );
+        let volumes = DiskUsageSummary::collect(
+            usage.volumes.unwrap_or_default(),
+            |volume| {
+                volume
+                    .usage_data
+                    .as_ref()
+                    .is_some_and(|usage| usage.ref_count > 0)
+            },
+            |volume| {
+                volume
+                    .usage_data
+                    .as_ref()
+                    .map_or(0, |usage| to_bytes(usage.size))
+            },
+        );
+        let build_cache = DiskUsageSummary::collect(
+            usage.build_cache.unwrap_or_default(),
+            |cache| cache.in_use.unwrap_or_default() || cache.shared.unwrap_or_default(),
+            |cache| cache.size.map_or(0, to_bytes),
+        );
+
+        Self {
+            images,
+            containers,
+            volumes,
+            build_cache,
+        }
+    }
+
+    /// Usage of the images, the size counts the shared layers only once.
+    pub fn images(&self) -> DiskUsageSummary {
+        self.images
+    }
+
+    /// Usage of the writable layers of the containers, the active ones are running.
+    pub fn containers(&self) -> DiskUsageSummary {
+        self.containers
+    }
+
+    /// Usage of the local volumes, the active ones are used by at least one container.
+    pub fn volumes(&self) -> DiskUsageSummary {
+        self.volumes
+    }
+
+    /// Usage of the build cache.
+    pub fn build_cache(&self) -> DiskUsageSummary {
+        self.build_cache
+    }
+
+    /// Total size of all the resources in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.summaries().map(|summary| summary.size).sum()
+    }
+
+    /// Total size in bytes which may be reclaimed by pruning the unused resources.
+    pub fn total_reclaimable(&self) -> u64 {
+        self.summaries().map(|summary| summary.reclaimable).sum()
+    }
+
+    fn summaries(&self) -> impl Iterator<Item = DiskUsageSummary> {
+        [self.images, self.containers, self.volumes, self.build_cache].into_iter()
+    }
+}
+
+impl DiskUsageSummary {
+    fn collect<T>(items: Vec<T>, is_active: impl Fn(&T) -> bool, size: impl Fn(&T) -> u64) -> Self {
+        items.iter().fold(Self::default(), |summary, item| {
+            let active = is_active(item);
+            let size = size(item);
+            Self {
+                count: summary.count + 1,
+                active: summary.active + usize::from(active),
+                size: summary.size + size,
+                reclaimable: summary.reclaimable + if active { 0 } else { size },
+            }
+        })
+    }
+
+    /// Number of the resources.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Number of the resources in use.
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Size of the resources in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Size in bytes of the resources which aren't in use, and may be pruned.
+    pub fn reclaimable(&self) -> u64 {
+        self.reclaimable
+    }
+}
+
+/// The daemon reports `-1` if the size is unknown (e.g. not calculated yet).
+fn to_bytes(size: i64) -> u64 {
+    u64::try_from(size).unwrap_or_default()
+}
+
+impl Client {
+    /// Returns the disk usage of the daemon.
+    pub(crate) async fn disk_usage(&self) -> Result<DiskUsage, ClientError> {
+        let usage = self
+            .with_reconnect(|docker| async move { docker.df().await })
+            .await
+            .map_err(ClientError::DiskUsage)?;
+
+        Ok(DiskUsage::new(usage))
+    }
+}
+
+/// Returns the disk usage of the Docker daemon used by testcontainers.
+///
+/// Note that calculating the usage may take a while on daemons with many resources.
+///
+/// ```rust,no_run
+/// use testcontainers::core::client::disk_usage;
+///
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let usage = disk_usage().await?;
+/// println!(
+///     "docker uses {} bytes, {} of them reclaimable",
+///     usage.total_size(),
+///     usage.total_reclaimable()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub async fn disk_usage() -> Result<DiskUsage, ClientError> {
+    Client::lazy_client().await?.disk_usage().await
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard_stubs::models::{ContainerSummary, ImageSummary, Volume, VolumeUsageData};
+
+    use super::*;
+
+    fn image(size: i64, shared_size: i64, containers: i64) -> ImageSummary {
+        ImageSummary {
+            size,
+            shared_size,
+            containers,
+            ..Default::default()
+        }
+    }
+
+    fn volume(size: i64, ref_count: i64) -> Volume {
+        Volume {
+            usage_data: Some(VolumeUsageData { size, ref_count }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disk_usage_is_summarized_like_docker_system_df() {
+        let usage = DiskUsage::new(SystemDataUsageResponse {
+            layers_size: Some(1000),
+            images: Some(vec![image(600, 200, 1), image(500, 200, 0)]),
+            containers: Some(vec![
+                ContainerSummary {
+                    state: Some("running".to_string()),
+                    size_rw: Some(10),
+                    ..Default::default()
+                },
+                ContainerSummary {
+                    state: Some("exited".to_string()),
+                    size_rw: Some(20),
+                    ..Default::default()
+                },
+            ]),
+            volumes: Some(vec![volume(100, 1), volume(50, 0), volume(-1, 0)]),
+            build_cache: None,
+        });
+
+        assert_eq!(
+            usage.images(),
+            DiskUsageSummary {
+                count: 2,
+                active: 1,
+                size: 1000,
+                reclaimable: 600,
+            }
+        );
+        assert_eq!(
+            usage.containers(),
+            DiskUsageSummary {
+                count: 2,
+                active: 1,
+                size: 30,
+                reclaimable: 20,
+            }
+        );
+        assert_eq!(
+            usage.volumes(),
+            DiskUsageSummary {
+                count: 3,
+                active: 1,
+                size: 150,
+                reclaimable: 50,
+            }
+        );
+        assert_eq!(usage.build_cache(), DiskUsageSummary::default());
+        assert_eq!(usage.total_size(), 1180);
+        assert_eq!(usage.total_reclaimable(), 670);
+    }
+}
@@ -1,4 +1,7 @@
-use std::sync::{Arc, OnceLock, Weak};
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, Weak},
+};
 
 use tokio::sync::Mutex;
 
@@ -8,6 +11,8 @@ use crate::core::client::{Client, ClientError};
 // Instead, we re-create the client if it was dropped and asked one more time.
 // This way we provide on `Drop` guarantees and avoid unnecessary instantiation at the same time.
 static DOCKER_CLIENT: OnceLock<Mutex<Weak<Client>>> = OnceLock::new();
+// Clients for Docker hosts overridden per request, keyed by the host.
+static HOST_CLIENTS: OnceLock<Mutex<HashMap<String, Weak<Client>>>> = OnceLock::new();
 
 impl Client {
     /// Returns a client instance, reusing already created or initializing a new one.
@@ -27,6 +32,53 @@ impl Client {
             Ok(client)
         }
     }
+
+    /// Returns a client instance for the given Docker host, or the default one if no host is passed.
+    pub(crate) async fn lazy_client_for(
+        docker_host: Option<&str>,
+    ) -> Result<Arc<Client>, ClientError> {
+        let Some(docker_host) = docker_host else {
+            return Self::lazy_client().await;
+        };
+
+        let mut guard = HOST_CLIENTS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .await;
+        // drop the entries of clients which are no longer in use
+        guard.retain(|_, client| client.strong_count() > 0);
+
+        if let Some(client) = guard.get(docker_host).and_then(Weak::upgrade) {
+            Ok(client)
+        } else {
+            let client = Arc::new(Client::with_docker_host(docker_host).await?);
+            guard.insert(docker_host.to_string(), Arc::downgrade(&client));
+
+            Ok(client)
+        }
+    }
+}
+
+/// Drops the cached host that containers are reached on (see [`ContainerAsync::get_host`]),
+/// so it's resolved again on the next use.
+///
+/// The host is resolved once per Docker host, which involves inspecting the gateway network
+/// when running inside a container. Invalidation is only required if the network setup changes
+/// during the run, e.g. when the gateway network is recreated.
+///
+/// [`ContainerAsync::get_host`]: crate::ContainerAsync::get_host
+pub async fn invalidate_host_cache() {
+    let mut clients = Vec::new();
+    if let Some(client) = DOCKER_CLIENT.get() {
+        clients.extend(client.lock().await.upgrade());
+    }
+    if let Some(host_clients) = HOST_CLIENTS.get() {
+        clients.extend(host_clients.lock().await.values().filter_map(Weak::upgrade));
+    }
+
+    for client in clients {
+        client.invalidate_docker_hostname();
+    }
 }
 
 /// Returns a configured Docker client instance.
@@ -37,5 +89,5 @@ impl Client {
 ///
 /// This method returns a lazily-created client, reusing an existing one if available.
 pub async fn docker_client_instance() -> Result<bollard::Docker, ClientError> {
-    Client::lazy_client().await.map(|c| c.bollard.clone())
+    Client::lazy_client().await.map(|c| c.bollard())
 }
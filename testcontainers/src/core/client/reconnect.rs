@@ -0,0 +1,105 @@
+use std::{future::Future, sync::PoisonError, time::Duration};
+
+use bollard::{errors::Error as BollardError, Docker};
+
+use crate::core::client::{bollard_client, Client, ClientError};
+
+/// How many times an idempotent operation is retried after the connection to the daemon was lost.
+const MAX_RECONNECT_ATTEMPTS: u32 = 4;
+/// Delay before the first reconnection attempt, doubled on every subsequent one.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+impl Client {
+    /// Returns the current bollard client.
+    pub(super) fn bollard(&self) -> Docker {
+        self.bollard
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Runs an idempotent (read-only) operation against the daemon.
+    ///
+    /// If the connection to the daemon is lost (e.g. the daemon has been restarted), the client is
+    /// re-created and the operation is retried with exponential backoff.
+    pub(super) async fn with_reconnect<T, F, Fut>(&self, op: F) -> Result<T, BollardError>
+    where
+        F: Fn(Docker) -> Fut,
+        Fut: Future<Output = Result<T, BollardError>>,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match op(self.bollard()).await {
+                Err(err) if is_connection_error(&err) && attempt < MAX_RECONNECT_ATTEMPTS => {
+                    log::warn!(
+                        "Lost connection to the docker daemon: {err}, reconnecting in {backoff:?}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    self.reconnect();
+                    attempt += 1;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Converts an error of a non-idempotent operation.
+    ///
+    /// Such operations can't be retried safely, so a lost connection is reported as
+    /// [`ClientError::DaemonRestarted`] and the client is re-created for subsequent calls.
+    pub(super) fn stateful_error(
+        &self,
+        err: BollardError,
+        wrap: impl FnOnce(BollardError) -> ClientError,
+    ) -> ClientError {
+        if is_connection_error(&err) {
+            self.reconnect();
+            ClientError::DaemonRestarted(err)
+        } else {
+            wrap(err)
+        }
+    }
+
+    fn reconnect(&self) {
+        match bollard_client::init(&self.config) {
+            Ok(bollard) => {
+                *self.bollard.write().unwrap_or_else(PoisonError::into_inner) = bollard;
+            }
+            Err(err) => log::warn!("Failed to re-create the docker client: {err}"),
+        }
+    }
+}
+
+fn is_connection_error(err: &BollardError) -> bool {
+    matches!(
+        err,
+        BollardError::IOError { .. }
+            | BollardError::HyperResponseError { .. }
+            | BollardError::HyperLegacyError { .. }
+            | BollardError::SocketNotFoundError(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_transport_errors_are_treated_as_connection_errors() {
+        assert!(is_connection_error(&BollardError::IOError {
+            err: std::io::Error::from(std::io::ErrorKind::ConnectionRefused),
+        }));
+        assert!(is_connection_error(&BollardError::SocketNotFoundError(
+            "/var/run/docker.sock".to_string()
+        )));
+        assert!(!is_connection_error(
+            &BollardError::DockerResponseServerError {
+                status_code: 404,
+                message: "no such container".to_string(),
+            }
+        ));
+        assert!(!is_connection_error(&BollardError::RequestTimeoutError));
+    }
+}
@@ -1,23 +1,48 @@
-use std::{fmt, net::IpAddr, pin::Pin, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
-use tokio::io::{AsyncBufRead, AsyncReadExt};
+use bollard::errors::Error as BollardError;
+use futures::stream::BoxStream;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWrite};
 use tokio_stream::StreamExt;
 
 use crate::{
     core::{
         async_drop,
-        client::Client,
+        client::{CgroupDriver, CgroupVersion, Client, ClientError},
+        containers::capture::PacketCapture,
         env,
-        error::{ContainerMissingInfo, ExecError, Result, TestcontainersError},
-        network::Network,
+        error::{
+            AfterStartExecError, ContainerMissingInfo, ExecError, Result, TestcontainersError,
+        },
+        lifecycle::lifecycle_event,
+        logs::stream::LogStream,
+        mounts::AccessMode,
+        network::{Network, NetworkEndpoint},
         ports::Ports,
-        wait::WaitStrategy,
-        CmdWaitFor, ContainerPort, ContainerState, ExecCommand, WaitFor,
+        wait::{mapped_host_port, LogWaitStrategy, WaitStrategy},
+        BudgetViolation, CmdWaitFor, ContainerEvent, ContainerPort, ContainerState,
+        ContainerStateSnapshot, ContainerStats, ExecCommand, ExecFailurePolicy, FilesystemChange,
+        ImageMetadata, StartupReport, WaitFor, WaitStepReport,
     },
-    ContainerRequest, Image,
+    ContainerRequest, GenericImage, Image,
 };
 
 pub(super) mod exec;
+pub(super) mod handle;
+
+/// The deadline of [`ContainerAsync::tcp_stream`] to connect to a port.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The first and the maximal delays between connection attempts.
+const CONNECT_BACKOFF: (Duration, Duration) = (Duration::from_millis(50), Duration::from_secs(1));
 
 /// Represents a running docker container that has been started using an async client.
 ///
@@ -39,8 +64,13 @@ pub struct ContainerAsync<I: Image> {
     id: String,
     image: ContainerRequest<I>,
     pub(super) docker_client: Arc<Client>,
-    #[allow(dead_code)]
-    network: Option<Arc<Network>>,
+    // networks created by testcontainers, kept alive while the container is in use
+    networks: std::sync::Mutex<Vec<Arc<Network>>>,
+    creation_warnings: Vec<String>,
+    startup_report: StartupReport,
+    // first violation of the resource budget, recorded by the background monitor
+    budget_violation: Arc<std::sync::OnceLock<BudgetViolation>>,
+    packet_capture: Option<PacketCapture>,
     dropped: bool,
     #[cfg(feature = "reusable-containers")]
     reuse: crate::ReuseDirective,
@@ -56,11 +86,14 @@ where
         id: String,
         docker_client: Arc<Client>,
         container_req: ContainerRequest<I>,
-        network: Option<Arc<Network>>,
+        networks: Vec<Arc<Network>>,
     ) -> Result<ContainerAsync<I>> {
-        let container = Self::construct(id, docker_client, container_req, network);
-        let ready_conditions = container.image().ready_conditions();
-        container.block_until_ready(ready_conditions).await?;
+        let mut container = Self::construct(id, docker_client, container_req, networks);
+        if let Some((uid, gid)) = container.image.mount_owner() {
+            container.chown_mounts(uid, gid).await?;
+        }
+        let ready_conditions = container.ready_conditions();
+        container.startup_report.wait_steps = container.wait_until_ready(ready_conditions).await?;
         Ok(container)
     }
 
@@ -68,7 +101,7 @@ where
         id: String,
         docker_client: Arc<Client>,
         mut container_req: ContainerRequest<I>,
-        network: Option<Arc<Network>>,
+        networks: Vec<Arc<Network>>,
     ) -> ContainerAsync<I> {
         #[cfg(feature = "reusable-containers")]
         let reuse = container_req.reuse();
@@ -78,7 +111,11 @@ where
             id,
             image: container_req,
             docker_client,
-            network,
+            networks: std::sync::Mutex::new(networks),
+            creation_warnings: Vec::new(),
+            startup_report: StartupReport::default(),
+            budget_violation: Arc::default(),
+            packet_capture: None,
             dropped: false,
             #[cfg(feature = "reusable-containers")]
             reuse,
@@ -106,6 +143,41 @@ where
             });
         }
 
+        if let Some(budget) = container.image.resource_budget() {
+            let client = container.docker_client.clone();
+            let violation = container.budget_violation.clone();
+            let container_id = container.id.clone();
+            tokio::spawn(async move {
+                // requested before the stats, so that a restart in between isn't missed
+                let mut starts = client.events(HashMap::from([
+                    ("type".to_string(), vec!["container".to_string()]),
+                    ("container".to_string(), vec![container_id.clone()]),
+                    (
+                        "event".to_string(),
+                        vec!["start".to_string(), "destroy".to_string()],
+                    ),
+                ]));
+                loop {
+                    // the stream ends once the container stops
+                    let mut stats = client.stats(&container_id, true);
+                    while let Some(Ok(stats)) = stats.next().await {
+                        if let Some(exceeded) = budget.check(&ContainerStats::from_stats(&stats)) {
+                            log::error!(
+                                "Container {container_id} exceeded its resource budget: {exceeded}"
+                            );
+                            let _ = violation.set(exceeded);
+                            return;
+                        }
+                    }
+                    // the monitoring resumes once the container is started again, until it's removed
+                    match starts.next().await {
+                        Some(Ok(event)) if event.action.as_deref() == Some("start") => {}
+                        _ => return,
+                    }
+                }
+            });
+        }
+
         container
     }
 
@@ -121,6 +193,42 @@ where
         self.image.image()
     }
 
+    /// Returns the warnings reported by the Docker daemon on creation of the container,
+    /// e.g. about resource limits that are not supported by the host and were ignored.
+    ///
+    /// The warnings are logged as well. Empty for reused containers, as they were created earlier.
+    pub fn creation_warnings(&self) -> &[String] {
+        &self.creation_warnings
+    }
+
+    pub(crate) fn with_creation_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.creation_warnings = warnings;
+        self
+    }
+
+    pub(crate) fn with_packet_capture(mut self, capture: Option<PacketCapture>) -> Self {
+        self.packet_capture = capture;
+        self
+    }
+
+    /// Returns the timings of the startup of the container: pull of the image, creation, start
+    /// and each ready condition, e.g. to report them to a dashboard tracking flaky startups.
+    pub fn startup_report(&self) -> &StartupReport {
+        &self.startup_report
+    }
+
+    pub(crate) fn startup_report_mut(&mut self) -> &mut StartupReport {
+        &mut self.startup_report
+    }
+
+    /// Returns a cheap, cloneable [`ContainerHandle`](handle::ContainerHandle) to this container.
+    ///
+    /// The handle doesn't own the container, so it can be shared with helper structs and spawned tasks
+    /// (e.g. to query ports or follow logs), while this instance keeps controlling the container lifetime.
+    pub fn handle(&self) -> handle::ContainerHandle {
+        handle::ContainerHandle::new(&self.id, self.docker_client.clone())
+    }
+
     pub async fn ports(&self) -> Result<Ports> {
         self.docker_client.ports(&self.id).await.map_err(Into::into)
     }
@@ -163,6 +271,57 @@ where
             })
     }
 
+    /// Returns a snapshot of the current state of the container (status, start and finish times, exit code).
+    pub async fn state(&self) -> Result<ContainerStateSnapshot> {
+        let state = self
+            .docker_client
+            .inspect(&self.id)
+            .await?
+            .state
+            .and_then(ContainerStateSnapshot::from_inspect)
+            .ok_or_else(|| ContainerMissingInfo::new(&self.id, "State.Status"))?;
+        Ok(state)
+    }
+
+    /// Resolves the information passed to [`Image::exec_after_start`].
+    pub(crate) async fn container_state(&self) -> Result<ContainerState> {
+        let inspect = self.docker_client.inspect(&self.id).await?;
+        let network_settings = inspect.network_settings.unwrap_or_default();
+        let ports = network_settings
+            .ports
+            .map(Ports::try_from)
+            .transpose()
+            .map_err(ClientError::from)?
+            .unwrap_or_default();
+
+        let mut state = ContainerState::new(&self.id, ports).with_host(self.get_host().await?);
+        for (network, settings) in network_settings.networks.unwrap_or_default() {
+            let ip = settings
+                .ip_address
+                .as_deref()
+                .and_then(|ip| IpAddr::from_str(ip).ok());
+            if let Some(ip) = ip {
+                state = state.with_ip_address(network, ip);
+            }
+        }
+        Ok(state)
+    }
+
+    /// Returns metadata (digest, creation date, size) of the image the container has been created from.
+    ///
+    /// The image is resolved by the id the container is running, so the metadata is accurate even if
+    /// the tag has been moved since the container was created.
+    pub async fn image_metadata(&self) -> Result<ImageMetadata> {
+        let image_id = self
+            .docker_client
+            .inspect(&self.id)
+            .await?
+            .image
+            .ok_or_else(|| ContainerMissingInfo::new(&self.id, "Image"))?;
+        let inspect = self.docker_client.inspect_image(&image_id).await?;
+        Ok(ImageMetadata::new(image_id, inspect))
+    }
+
     /// Returns the bridge ip address of docker container as specified in NetworkSettings.Networks.IPAddress
     pub async fn get_bridge_ip_address(&self) -> Result<IpAddr> {
         let container_id = &self.id;
@@ -209,87 +368,595 @@ where
             .map_err(Into::into)
     }
 
+    /// Waits until the mapped port accepts TCP connections, retrying with an exponential backoff.
+    ///
+    /// Returns the last connection error if the port doesn't accept connections within the timeout.
+    pub async fn wait_for_port_connectable(
+        &self,
+        port: impl Into<ContainerPort>,
+        timeout: Duration,
+    ) -> Result<()> {
+        self.connect(port.into(), timeout).await.map(drop)
+    }
+
+    /// Returns a TCP stream connected to the mapped port, retrying with an exponential backoff
+    /// for up to 10 seconds.
+    ///
+    /// Useful to talk to the container without a dedicated client:
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{core::IntoContainerPort, runners::AsyncRunner, GenericImage, ImageExt};
+    /// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    ///
+    /// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let redis = GenericImage::new("redis", "7.2.4")
+    ///     .with_exposed_port(6379.tcp())
+    ///     .start()
+    ///     .await?;
+    ///
+    /// let mut stream = redis.tcp_stream(6379).await?;
+    /// stream.write_all(b"PING\r\n").await?;
+    /// let mut reply = [0; 7];
+    /// stream.read_exact(&mut reply).await?;
+    /// assert_eq!(&reply, b"+PONG\r\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tcp_stream(
+        &self,
+        port: impl Into<ContainerPort>,
+    ) -> Result<tokio::net::TcpStream> {
+        self.connect(port.into(), DEFAULT_CONNECT_TIMEOUT).await
+    }
+
+    async fn connect(
+        &self,
+        port: ContainerPort,
+        timeout: Duration,
+    ) -> Result<tokio::net::TcpStream> {
+        let (host, host_port) = mapped_host_port(self, port).await?;
+        let address = format!("{host}:{host_port}");
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        let (mut delay, max_delay) = CONNECT_BACKOFF;
+        loop {
+            let attempt =
+                tokio::time::timeout_at(deadline, tokio::net::TcpStream::connect(&address));
+            let err = match attempt.await {
+                Ok(Ok(stream)) => return Ok(stream),
+                Ok(Err(err)) => err,
+                Err(_) => std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("connection to {address} timed out"),
+                ),
+            };
+
+            if tokio::time::Instant::now() + delay >= deadline {
+                log::debug!(
+                    "Port {port} is not connectable on {address} within {timeout:?}: {err}"
+                );
+                return Err(err.into());
+            }
+            log::debug!("Port {port} is not connectable on {address} yet: {err}");
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(max_delay);
+        }
+    }
+
     /// Executes a command in the container.
     pub async fn exec(&self, cmd: ExecCommand) -> Result<exec::ExecResult> {
+        let (exec, ready) = self.exec_with_outcome(cmd).await?;
+        ready?;
+        Ok(exec)
+    }
+
+    /// Executes a command in the container as the `user`, regardless of the default user of the container.
+    ///
+    /// Useful to adjust permissions or install debugging tools as `root` in images dropping privileges.
+    /// Shortcut for [`ExecCommand::with_user`], the user is in the `user`, `user:group`, `uid` or `uid:gid` format.
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{core::ExecCommand, runners::AsyncRunner, GenericImage};
+    ///
+    /// # async fn example() -> testcontainers::core::error::Result<()> {
+    /// let container = GenericImage::new("postgres", "16-alpine").start().await?;
+    /// container
+    ///     .exec_as("root", ExecCommand::new(["apk", "add", "strace"]))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exec_as(
+        &self,
+        user: impl Into<String>,
+        cmd: ExecCommand,
+    ) -> Result<exec::ExecResult> {
+        self.exec(cmd.with_user(user)).await
+    }
+
+    /// Executes the command and waits for its ready conditions.
+    ///
+    /// The outcome of the wait is returned along with the result, so the output of a failed command is still available.
+    async fn exec_with_outcome(&self, cmd: ExecCommand) -> Result<(exec::ExecResult, Result<()>)> {
+        lifecycle_event!("exec", id = self.id, cmd = cmd.cmd);
+        let started = tokio::time::Instant::now();
+
+        let options = cmd.create_exec_options();
         let ExecCommand {
-            cmd,
             container_ready_conditions,
             cmd_ready_condition,
+            stdin,
+            ..
         } = cmd;
-
-        log::debug!("Executing command {:?}", cmd);
-
-        let mut exec = self.docker_client.exec(&self.id, cmd).await?;
-        self.block_until_ready(container_ready_conditions).await?;
-
-        match cmd_ready_condition {
-            CmdWaitFor::StdOutMessage { message } => {
-                exec.stdout()
-                    .wait_for_message(&message, 1)
-                    .await
-                    .map_err(ExecError::from)?;
-            }
-            CmdWaitFor::StdErrMessage { message } => {
-                exec.stderr()
-                    .wait_for_message(&message, 1)
-                    .await
-                    .map_err(ExecError::from)?;
-            }
-            CmdWaitFor::ExitCode { code } => {
-                let exec_id = exec.id().to_string();
-                loop {
-                    let inspect = self.docker_client.inspect_exec(&exec_id).await?;
-
-                    if let Some(actual) = inspect.exit_code {
-                        if actual != code {
-                            Err(ExecError::ExitCodeMismatch {
-                                expected: code,
-                                actual,
-                            })?;
+        let mut exec = self
+            .docker_client
+            .exec_with_options(&self.id, options, stdin)
+            .await?;
+
+        let ready = async {
+            self.block_until_ready(container_ready_conditions).await?;
+
+            match cmd_ready_condition {
+                CmdWaitFor::StdOutMessage { message } => {
+                    exec.stdout()
+                        .wait_for_message(&message, 1)
+                        .await
+                        .map_err(ExecError::from)?;
+                }
+                CmdWaitFor::StdErrMessage { message } => {
+                    exec.stderr()
+                        .wait_for_message(&message, 1)
+                        .await
+                        .map_err(ExecError::from)?;
+                }
+                CmdWaitFor::ExitCode { code } => {
+                    let exec_id = exec.id().to_string();
+                    loop {
+                        let inspect = self.docker_client.inspect_exec(&exec_id).await?;
+
+                        if let Some(actual) = inspect.exit_code {
+                            if actual != code {
+                                Err(ExecError::ExitCodeMismatch {
+                                    expected: code,
+                                    actual,
+                                })?;
+                            }
+                            break;
+                        } else {
+                            tokio::time::sleep(Duration::from_millis(500)).await;
                         }
-                        break;
-                    } else {
-                        tokio::time::sleep(Duration::from_millis(500)).await;
                     }
                 }
+                CmdWaitFor::Duration { length } => {
+                    tokio::time::sleep(length).await;
+                }
+                _ => {}
             }
-            CmdWaitFor::Duration { length } => {
-                tokio::time::sleep(length).await;
+            Ok(())
+        }
+        .await;
+        lifecycle_event!(
+            "exec ready",
+            id = self.id,
+            exec_id = exec.id,
+            ready = ready.is_ok(),
+            elapsed = started.elapsed()
+        );
+
+        let exec = exec::ExecResult {
+            client: self.docker_client.clone(),
+            id: exec.id,
+            stdout: exec.stdout.into_inner(),
+            stderr: exec.stderr.into_inner(),
+        };
+        Ok((exec, ready))
+    }
+
+    /// Executes a command returned by [`Image::exec_after_start`], following its failure policy.
+    pub(crate) async fn exec_after_start(&self, cmd: ExecCommand) -> Result<()> {
+        let (attempts, backoff) = match cmd.failure_policy {
+            ExecFailurePolicy::Retry { attempts, backoff } => (attempts.max(1), backoff),
+            _ => (1, Duration::ZERO),
+        };
+
+        let mut attempt = 1;
+        let err = loop {
+            let err = match self.exec_with_outcome(cmd.clone()).await {
+                Ok((_, Ok(()))) => return Ok(()),
+                Ok((exec, Err(source))) => after_start_error(&cmd, Some(exec), source).await,
+                Err(source) => after_start_error(&cmd, None, source).await,
+            };
+            if attempt >= attempts {
+                break err;
             }
-            _ => {}
+            log::warn!(
+                "Command {:?} failed (attempt {attempt} of {attempts}), retrying in {backoff:?}: {err}",
+                cmd.cmd
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        };
+
+        if cmd.failure_policy == ExecFailurePolicy::Warn {
+            log::warn!("Ignoring the failure of a command executed after start: {err}");
+            return Ok(());
         }
+        Err(err)
+    }
 
-        Ok(exec::ExecResult {
+    /// Executes the command, ignoring its ready conditions, and collects its output once it exits.
+    pub(crate) async fn exec_output(&self, cmd: &ExecCommand) -> Result<exec::ExecOutput> {
+        let exec = self
+            .docker_client
+            .exec_with_options(&self.id, cmd.create_exec_options(), cmd.stdin.clone())
+            .await?;
+        let exec = exec::ExecResult {
             client: self.docker_client.clone(),
             id: exec.id,
             stdout: exec.stdout.into_inner(),
             stderr: exec.stderr.into_inner(),
-        })
+        };
+        exec.into_output(cmd.cmd.clone()).await
+    }
+
+    /// Executes a command in the container and collects its output once it exits,
+    /// returning [`ExecError::Failed`] with the output if it exits with a non-zero code.
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{core::ExecCommand, runners::AsyncRunner, GenericImage};
+    ///
+    /// # async fn example() -> testcontainers::core::error::Result<()> {
+    /// let container = GenericImage::new("postgres", "16-alpine").start().await?;
+    /// let output = container
+    ///     .exec_expecting_success(ExecCommand::new(["pg_isready"]))
+    ///     .await?;
+    /// println!("{}", String::from_utf8_lossy(output.stdout()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exec_expecting_success(&self, cmd: ExecCommand) -> Result<exec::ExecOutput> {
+        let args = cmd.cmd.clone();
+        let output = self.exec(cmd).await?.into_output(args).await?;
+        if !output.is_success() {
+            return Err(ExecError::Failed(Box::new(output)).into());
+        }
+        Ok(output)
+    }
+
+    /// Executes the commands in the container sequentially, stopping at the first one exiting with a non-zero code.
+    ///
+    /// Every command is awaited until it exits and its whole output is collected in the returned
+    /// [`ExecTranscript`](exec::ExecTranscript), check [`ExecTranscript::failure`](exec::ExecTranscript::failure)
+    /// to find out whether the batch has been interrupted.
+    ///
+    /// Errors are returned only if a command can't be executed at all (or its ready conditions are not met).
+    pub async fn exec_all(
+        &self,
+        cmds: impl IntoIterator<Item = ExecCommand>,
+    ) -> Result<exec::ExecTranscript> {
+        let mut transcript = exec::ExecTranscript::default();
+        for cmd in cmds {
+            let args = cmd.cmd.clone();
+            let output = self.exec(cmd).await?.into_output(args).await?;
+            let failed = !output.is_success();
+            transcript.push(output);
+            if failed {
+                break;
+            }
+        }
+        Ok(transcript)
+    }
+
+    /// Lists the commands executed in the container, including the ones which are still running.
+    ///
+    /// Docker keeps the exec instances only for a while after they exit, so this list may not be exhaustive.
+    /// Running commands are terminated together with the container, once it's stopped or removed.
+    pub async fn execs(&self) -> Result<Vec<exec::ExecInfo>> {
+        let exec_ids = self
+            .docker_client
+            .inspect(&self.id)
+            .await?
+            .exec_ids
+            .unwrap_or_default();
+
+        let mut execs = Vec::with_capacity(exec_ids.len());
+        for exec_id in exec_ids {
+            match self.docker_client.inspect_exec(&exec_id).await {
+                Ok(inspect) => execs.push(inspect.into()),
+                // the exec instance may be cleaned up in the meantime
+                Err(ClientError::InspectExec(BollardError::DockerResponseServerError {
+                    status_code: 404,
+                    ..
+                })) => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(execs)
+    }
+
+    /// Connects the container to the network, in addition to the networks it's already connected to.
+    ///
+    /// The container is resolvable by the `aliases` within the network.
+    /// Like with [`ImageExt::with_network`](crate::ImageExt::with_network), the network is created if it doesn't exist.
+    pub async fn connect_to_network(
+        &self,
+        network: impl Into<String>,
+        aliases: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<()> {
+        let network = network.into();
+        let created = Network::new(&network, self.docker_client.clone()).await?;
+        let endpoint = NetworkEndpoint::new().with_aliases(aliases);
+        self.docker_client
+            .connect_network(&network, &self.id, endpoint.into())
+            .await?;
+
+        if let Some(created) = created {
+            self.networks
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(created);
+        }
+        Ok(())
     }
 
     /// Starts the container.
     pub async fn start(&self) -> Result<()> {
         self.docker_client.start(&self.id).await?;
-        let state = ContainerState::new(self.id(), self.ports().await?);
+        let state = self.container_state().await?;
         for cmd in self.image.exec_after_start(state)? {
-            self.exec(cmd).await?;
+            self.exec_after_start(cmd).await?;
         }
         Ok(())
     }
 
+    /// Waits for the condition to be met by the running container, e.g. for the healthcheck to
+    /// pass again after a restart, or for the container to exit after a shutdown signal.
+    ///
+    /// The default wait timeout of the request applies to conditions without their own timeout,
+    /// see [`ImageExt::with_wait_timeout`](crate::ImageExt::with_wait_timeout).
+    /// Once the condition is met, a violation of the resource budget is reported as by
+    /// [`ContainerAsync::check_resource_budget`], e.g. for a batch job waited for until its exit.
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{
+    ///     core::{wait::ExitWaitStrategy, WaitFor},
+    ///     runners::AsyncRunner,
+    ///     GenericImage,
+    /// };
+    ///
+    /// # async fn example() -> testcontainers::core::error::Result<()> {
+    /// let container = GenericImage::new("nginx", "1.27-alpine").start().await?;
+    /// container.kill(Some("SIGQUIT")).await?;
+    /// container
+    ///     .wait(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait(&self, condition: WaitFor) -> Result<()> {
+        let condition = match self.image.wait_timeout() {
+            Some(timeout) => condition.with_default_timeout(timeout),
+            None => condition,
+        };
+        let started = tokio::time::Instant::now();
+        condition
+            .wait_until_ready(&self.docker_client, self)
+            .await?;
+        lifecycle_event!(
+            "condition met",
+            id = self.id,
+            condition = condition,
+            elapsed = started.elapsed()
+        );
+        self.check_resource_budget()
+    }
+
+    /// Waits for the message to be logged (to stdout or stderr) by the running container,
+    /// e.g. `request processed` after sending a request to it.
+    ///
+    /// Only the lines logged from the call are considered, see [`LogWaitStrategy::from_now`];
+    /// the container is followed across its restarts. Fails with [`WaitContainerError::Timeout`]
+    /// if the message isn't logged in time.
+    ///
+    /// If the message may be logged before the call, create the strategy with
+    /// [`LogWaitStrategy::from_now`] beforehand and pass it to [`ContainerAsync::wait`].
+    ///
+    /// [`WaitContainerError::Timeout`]: crate::core::error::WaitContainerError::Timeout
+    pub async fn wait_for_log(&self, message: impl AsRef<[u8]>, timeout: Duration) -> Result<()> {
+        let strategy = LogWaitStrategy::stdout(message)
+            .with_any_source()
+            .with_timeout(timeout)
+            .from_now();
+        self.wait(WaitFor::log(strategy)).await
+    }
+
     /// Stops the container (not the same with `pause`).
+    ///
+    /// Commands still running in the container (see [`ContainerAsync::execs`]) are killed along with it.
     pub async fn stop(&self) -> Result<()> {
         log::debug!("Stopping docker container {}", self.id);
 
-        self.docker_client.stop(&self.id).await?;
+        self.docker_client
+            .stop(&self.id, self.image.stop_timeout())
+            .await?;
+        Ok(())
+    }
+
+    /// Creates an image `repository:tag` from the current state of the container, e.g. to snapshot
+    /// a seeded database once and start the following tests from it.
+    ///
+    /// The container is paused while committing. Note that the data of volumes isn't included in the image.
+    /// The returned image has no ready conditions, add them with [`GenericImage::with_wait_for`].
+    ///
+    /// ```rust,no_run
+    /// use testcontainers::{runners::AsyncRunner, GenericImage};
+    ///
+    /// # async fn test() -> testcontainers::core::error::Result<()> {
+    /// let container = GenericImage::new("postgres", "16-alpine").start().await?;
+    /// // seed the database
+    /// let snapshot = container.commit("postgres-seeded", "test").await?;
+    /// let seeded = snapshot.start().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn commit(&self, repository: &str, tag: &str) -> Result<GenericImage> {
+        log::debug!(
+            "Committing docker container {} as {repository}:{tag}",
+            self.id
+        );
+
+        self.docker_client.commit(&self.id, repository, tag).await?;
+        Ok(GenericImage::new(repository, tag))
+    }
+
+    /// Returns the paths of the container filesystem added, modified or deleted since its creation,
+    /// e.g. to assert that a tool under test only writes to the expected directories.
+    ///
+    /// The changes of mounted volumes, bind mounts and tmpfs mounts aren't reported.
+    pub async fn changes(&self) -> Result<Vec<FilesystemChange>> {
+        let changes = self.docker_client.changes(&self.id).await?;
+        Ok(changes
+            .into_iter()
+            .map(FilesystemChange::from_response)
+            .collect())
+    }
+
+    /// Returns the path of the cgroup of the container on the host, e.g.
+    /// `/sys/fs/cgroup/system.slice/docker-<id>.scope`, resolved from the cgroup driver of the daemon.
+    ///
+    /// Allows performance harnesses to read the cgroup files directly, e.g. the pressure stall
+    /// information (`cpu.pressure`, `memory.pressure`) or `memory.events`, which aren't exposed by
+    /// [`ContainerAsync::stats`]. The path is only accessible if the daemon runs on the same Linux host
+    /// (e.g. not with Docker Desktop). Only cgroup v2 is supported, as well as rootful Docker.
+    pub async fn cgroup_path(&self) -> Result<PathBuf> {
+        let capabilities = self.docker_client.capabilities().await?;
+        if capabilities.rootless() || capabilities.podman() {
+            return Err(TestcontainersError::other(
+                "cgroup path is only resolved for rootful Docker daemons",
+            ));
+        }
+        let cgroup_parent = self
+            .docker_client
+            .inspect(&self.id)
+            .await?
+            .host_config
+            .and_then(|config| config.cgroup_parent)
+            .filter(|parent| !parent.is_empty());
+
+        cgroup_path(
+            &self.id,
+            capabilities.cgroup_version(),
+            capabilities.cgroup_driver(),
+            cgroup_parent.as_deref(),
+        )
+        .map_err(TestcontainersError::other)
+    }
+
+    /// Returns a sample of the resource usage of the container: CPU, memory, network and block IO.
+    ///
+    /// The daemon collects two samples to compute the CPU usage, so it takes about a second.
+    pub async fn stats(&self) -> Result<ContainerStats> {
+        let stats = self
+            .docker_client
+            .stats(&self.id, false)
+            .next()
+            .await
+            .transpose()?
+            .ok_or_else(|| {
+                TestcontainersError::other(format!("no stats reported for container {}", self.id))
+            })?;
+        Ok(ContainerStats::from_stats(&stats))
+    }
+
+    /// Returns a stream of resource usage samples, reported by the daemon about once a second
+    /// until the container stops.
+    ///
+    /// Useful to watch the container while a load is applied, e.g. to check it stays under a memory ceiling.
+    pub fn stats_stream(&self) -> BoxStream<'static, Result<ContainerStats>> {
+        let stats = self.docker_client.stats(&self.id, true).map(|stats| {
+            stats
+                .map(|stats| ContainerStats::from_stats(&stats))
+                .map_err(Into::into)
+        });
+        Box::pin(stats)
+    }
+
+    /// Returns an error if the container exceeded its [resource budget](crate::ImageExt::with_resource_budget).
+    ///
+    /// The usage is sampled about once a second while the container runs, across its restarts,
+    /// so a spike right before the call may not be reported yet.
+    pub fn check_resource_budget(&self) -> Result<()> {
+        match self.budget_violation.get() {
+            Some(violation) => Err(TestcontainersError::ResourceBudgetExceeded {
+                id: self.id.clone(),
+                violation: *violation,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a stream of the events of the container reported by the Docker engine, e.g. when it dies,
+    /// runs out of memory or its health status changes.
+    ///
+    /// Only the events occurring after the call are streamed, so subscribe before triggering them.
+    /// The events occurring before the stream is first polled are included, as they're compared by time.
+    /// Note that the time of the events is assigned by the Docker daemon, whose clock must be in sync with this host.
+    /// The stream never ends on its own, use it with a timeout or take a limited number of events.
+    pub fn events(&self) -> BoxStream<'static, Result<ContainerEvent>> {
+        let filters = HashMap::from([
+            ("type".to_string(), vec!["container".to_string()]),
+            ("container".to_string(), vec![self.id.clone()]),
+        ]);
+        let events = self
+            .docker_client
+            .events(filters)
+            .map(|event| event.map(ContainerEvent::from_message).map_err(Into::into));
+        Box::pin(events)
+    }
+
+    /// Restarts the container and waits for it to be ready again, according to the ready conditions of the image.
+    ///
+    /// The log conditions only count the lines logged since the restart, as with
+    /// [`LogWaitStrategy::from_now`](crate::core::wait::LogWaitStrategy::from_now).
+    /// Keep in mind that the mapped host ports may change, so they should be resolved again.
+    pub async fn restart(&self) -> Result<()> {
+        log::debug!("Restarting docker container {}", self.id);
+
+        let since = SystemTime::now();
+        self.docker_client.restart(&self.id).await?;
+        let ready_conditions = self
+            .ready_conditions()
+            .into_iter()
+            .map(|condition| condition.with_logs_since(since))
+            .collect();
+        self.block_until_ready(ready_conditions).await
+    }
+
+    /// Sends the signal (e.g. `SIGTERM` or `SIGHUP`) to the main process of the container, `SIGKILL` if none passed.
+    ///
+    /// Unlike [`ContainerAsync::stop`], there is no grace period, and the container keeps running
+    /// if the process handles the signal. A killed container can be started again with [`ContainerAsync::start`].
+    pub async fn kill(&self, signal: Option<&str>) -> Result<()> {
+        log::debug!("Killing docker container {} with {signal:?}", self.id);
+
+        self.docker_client.kill(&self.id, signal).await?;
         Ok(())
     }
 
     /// Removes the container.
+    ///
+    /// The container is removed forcibly, killing all the commands still running in it.
     pub async fn rm(mut self) -> Result<()> {
-        log::debug!("Deleting docker container {}", self.id);
+        let started = tokio::time::Instant::now();
 
+        if let Some(capture) = self.packet_capture.take() {
+            capture.finish().await;
+        }
+        if let Some(timeout) = self.image.stop_timeout() {
+            self.docker_client.stop(&self.id, Some(timeout)).await?;
+        }
         self.docker_client.rm(&self.id).await?;
+        lifecycle_event!("removed", id = self.id, elapsed = started.elapsed());
 
         #[cfg(feature = "watchdog")]
         crate::watchdog::unregister(&self.id);
@@ -298,6 +965,41 @@ where
         Ok(())
     }
 
+    /// Copies the directory `container_path` out of the container into `host_dir`,
+    /// preserving the directory structure and file modes.
+    ///
+    /// The host directory is created if it doesn't exist, existing files are overwritten.
+    pub async fn copy_dir_from(
+        &self,
+        container_path: impl Into<String>,
+        host_dir: impl AsRef<Path>,
+    ) -> Result<()> {
+        let container_path = container_path.into();
+        self.docker_client
+            .copy_dir_from_container(&self.id, &container_path, host_dir.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns an asynchronous reader for the contents of the file `container_path`.
+    ///
+    /// The file is streamed out of the container as it's read, so even large files
+    /// (e.g. database dumps) don't have to fit in memory or be written to the host first.
+    /// Fails with [`CopyFromContainerError::NotAFile`] if the path isn't a regular file.
+    ///
+    /// [`CopyFromContainerError::NotAFile`]: crate::CopyFromContainerError::NotAFile
+    pub async fn open_file(
+        &self,
+        container_path: impl Into<String>,
+    ) -> Result<Pin<Box<dyn AsyncRead + Send>>> {
+        let container_path = container_path.into();
+        let reader = self
+            .docker_client
+            .open_file_from_container(&self.id, &container_path)
+            .await?;
+        Ok(reader)
+    }
+
     /// Returns an asynchronous reader for stdout.
     ///
     /// Accepts a boolean parameter to follow the logs:
@@ -318,6 +1020,30 @@ where
         Box::pin(tokio_util::io::StreamReader::new(stderr))
     }
 
+    /// Returns a stream of both stdout and stderr of the container, in the order they are received.
+    ///
+    /// Accepts a boolean parameter to follow the logs, see [`ContainerAsync::stdout`].
+    pub fn log_stream(&self, follow: bool) -> LogStream {
+        self.docker_client.logs(&self.id, follow)
+    }
+
+    /// Returns a stream of both stdout and stderr of the container, with each line prefixed
+    /// with its timestamp, which is parsed by [`LogStream::into_lines`].
+    ///
+    /// Accepts a boolean parameter to follow the logs, see [`ContainerAsync::stdout`].
+    pub fn timestamped_log_stream(&self, follow: bool) -> LogStream {
+        self.docker_client.timestamped_logs(&self.id, follow)
+    }
+
+    /// Attaches to the stdin of the container and returns an asynchronous writer for it.
+    ///
+    /// The container must be started with an open stdin, see [`ImageExt::with_open_stdin`](crate::ImageExt::with_open_stdin).
+    /// Shutting down the writer closes the connection (not the stdin of the container itself).
+    pub async fn attach_stdin(&self) -> Result<Pin<Box<dyn AsyncWrite + Send>>> {
+        let stdin = self.docker_client.attach_stdin(&self.id).await?;
+        Ok(stdin)
+    }
+
     /// Returns stdout as a vector of bytes available at the moment of call (from container startup to present).
     ///
     /// If you want to read stdout in asynchronous manner, use [`ContainerAsync::stdout`] instead.
@@ -336,21 +1062,113 @@ where
         Ok(stderr)
     }
 
+    /// Returns the ready conditions of the image, or the healthcheck of the container if there are none
+    /// and `TESTCONTAINERS_IMPLICIT_HEALTHCHECK_WAIT` is enabled.
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        let ready_conditions = self.image.ready_conditions();
+        if !ready_conditions.is_empty() || !self.image.implicit_healthcheck {
+            return ready_conditions;
+        }
+
+        log::debug!(
+            "Container {} has no ready conditions, waiting for its healthcheck",
+            self.id
+        );
+        vec![WaitFor::healthcheck()]
+    }
+
     pub(crate) async fn block_until_ready(&self, ready_conditions: Vec<WaitFor>) -> Result<()> {
+        self.wait_until_ready(ready_conditions).await.map(|_| ())
+    }
+
+    /// Waits for the conditions one after another, timing each of them.
+    async fn wait_until_ready(
+        &self,
+        ready_conditions: Vec<WaitFor>,
+    ) -> Result<Vec<WaitStepReport>> {
         log::debug!("Waiting for container {} to be ready", self.id);
         let id = self.id();
+        let wait_timeout = self.image.wait_timeout();
 
+        let mut steps = Vec::with_capacity(ready_conditions.len());
         for condition in ready_conditions {
+            let condition = match wait_timeout {
+                Some(timeout) => condition.with_default_timeout(timeout),
+                None => condition,
+            };
+            let started = tokio::time::Instant::now();
             condition
                 .wait_until_ready(&self.docker_client, self)
                 .await?;
+            lifecycle_event!(
+                "condition met",
+                id = id,
+                condition = condition,
+                elapsed = started.elapsed()
+            );
+            steps.push(WaitStepReport::new(condition, started.elapsed()));
         }
 
         log::debug!("Container {id} is now ready!");
+        Ok(steps)
+    }
+
+    async fn chown_mounts(&self, uid: u32, gid: u32) -> Result<()> {
+        let targets: Vec<String> = self
+            .image
+            .mounts()
+            .filter(|mount| matches!(mount.access_mode(), AccessMode::ReadWrite))
+            .filter_map(|mount| mount.target().map(String::from))
+            .collect();
+        if targets.is_empty() {
+            return Ok(());
+        }
+
+        log::debug!("Changing owner of {targets:?} to {uid}:{gid}");
+        let cmd = [
+            "chown".to_string(),
+            "-R".to_string(),
+            format!("{uid}:{gid}"),
+        ]
+        .into_iter()
+        .chain(targets);
+        self.exec(
+            ExecCommand::new(cmd)
+                .with_user("root")
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
         Ok(())
     }
 }
 
+/// How long the output of a failed command executed after start is awaited, it may still be running.
+const AFTER_START_OUTPUT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attaches the command and its output (if it has exited) to the failure of a command executed after start.
+async fn after_start_error(
+    cmd: &ExecCommand,
+    exec: Option<exec::ExecResult>,
+    source: TestcontainersError,
+) -> TestcontainersError {
+    let output = match exec {
+        Some(exec) => tokio::time::timeout(
+            AFTER_START_OUTPUT_TIMEOUT,
+            exec.into_output(cmd.cmd.clone()),
+        )
+        .await
+        .ok()
+        .and_then(|output| output.ok()),
+        None => None,
+    };
+    ExecError::AfterStart(Box::new(AfterStartExecError::new(
+        cmd.cmd.clone(),
+        output.as_ref(),
+        source,
+    )))
+    .into()
+}
+
 impl<I> fmt::Debug for ContainerAsync<I>
 where
     I: fmt::Debug + Image,
@@ -361,7 +1179,7 @@ where
         repr.field("id", &self.id)
             .field("image", &self.image)
             .field("command", &self.docker_client.config.command())
-            .field("network", &self.network)
+            .field("networks", &self.networks)
             .field("dropped", &self.dropped);
 
         #[cfg(feature = "reusable-containers")]
@@ -371,18 +1189,63 @@ where
     }
 }
 
+/// Resolves the cgroup of the container the same way as the daemon (see `libcontainer/cgroups`).
+fn cgroup_path(
+    id: &str,
+    version: Option<CgroupVersion>,
+    driver: Option<CgroupDriver>,
+    parent: Option<&str>,
+) -> std::result::Result<PathBuf, String> {
+    if version != Some(CgroupVersion::V2) {
+        return Err(format!(
+            "cgroup path is only resolved for cgroup v2, the daemon uses {}",
+            version.map_or("an unknown version".to_string(), |v| v.to_string())
+        ));
+    }
+
+    let mut path = PathBuf::from("/sys/fs/cgroup");
+    match driver {
+        Some(CgroupDriver::Systemd) => {
+            // a slice is nested into the slices of its prefixes, e.g. `a-b.slice` into `a.slice`
+            let slice = parent.unwrap_or("system.slice");
+            let name = slice.strip_suffix(".slice").unwrap_or(slice);
+            if name != "-" {
+                let mut prefix = String::new();
+                for part in name.split('-') {
+                    if !prefix.is_empty() {
+                        prefix.push('-');
+                    }
+                    prefix.push_str(part);
+                    path.push(format!("{prefix}.slice"));
+                }
+            }
+            path.push(format!("docker-{id}.scope"));
+        }
+        Some(CgroupDriver::Cgroupfs) => {
+            path.push(parent.unwrap_or("/docker").trim_start_matches('/'));
+            path.push(id);
+        }
+        None => return Err("cgroup driver isn't reported by the daemon".to_string()),
+    }
+    Ok(path)
+}
+
 impl<I> Drop for ContainerAsync<I>
 where
     I: Image,
 {
     fn drop(&mut self) {
+        let packet_capture = self.packet_capture.take();
+
         #[cfg(feature = "reusable-containers")]
         {
-            use crate::ReuseDirective::{Always, CurrentSession};
+            use crate::ReuseDirective::{Always, CurrentInvocation, CurrentSession};
 
-            if !self.dropped && matches!(self.reuse, Always | CurrentSession) {
+            if !self.dropped && matches!(self.reuse, Always | CurrentSession | CurrentInvocation) {
                 log::debug!("Declining to reap container marked for reuse: {}", &self.id);
-
+                if let Some(capture) = packet_capture {
+                    async_drop::async_drop(capture.finish());
+                }
                 return;
             }
         }
@@ -391,13 +1254,26 @@ where
             let id = self.id.clone();
             let client = self.docker_client.clone();
             let command = self.docker_client.config.command();
+            let stop_timeout = self.image.stop_timeout();
 
             let drop_task = async move {
                 log::trace!("Drop was called for container {id}, cleaning up");
+                let started = tokio::time::Instant::now();
+                if let Some(capture) = packet_capture {
+                    capture.finish().await;
+                }
                 match command {
                     env::Command::Remove => {
-                        if let Err(e) = client.rm(&id).await {
-                            log::error!("Failed to remove container on drop: {}", e);
+                        if let Some(timeout) = stop_timeout {
+                            if let Err(e) = client.stop(&id, Some(timeout)).await {
+                                log::warn!("Failed to stop container {id} gracefully on drop: {e}");
+                            }
+                        }
+                        match client.rm(&id).await {
+                            Ok(()) => {
+                                lifecycle_event!("removed", id = id, elapsed = started.elapsed());
+                            }
+                            Err(e) => log::error!("Failed to remove container on drop: {}", e),
                         }
                     }
                     env::Command::Keep => {}
@@ -420,6 +1296,42 @@ mod tests {
 
     use crate::{images::generic::GenericImage, runners::AsyncRunner};
 
+    #[test]
+    fn cgroup_path_depends_on_driver_and_parent() {
+        use std::path::Path;
+
+        use super::cgroup_path;
+        use crate::core::client::{CgroupDriver, CgroupVersion};
+
+        let v2 = Some(CgroupVersion::V2);
+        let resolve = |driver, parent| cgroup_path("abc", v2, Some(driver), parent).unwrap();
+
+        assert_eq!(
+            resolve(CgroupDriver::Systemd, None),
+            Path::new("/sys/fs/cgroup/system.slice/docker-abc.scope")
+        );
+        assert_eq!(
+            resolve(CgroupDriver::Systemd, Some("ci-tests.slice")),
+            Path::new("/sys/fs/cgroup/ci.slice/ci-tests.slice/docker-abc.scope")
+        );
+        assert_eq!(
+            resolve(CgroupDriver::Cgroupfs, None),
+            Path::new("/sys/fs/cgroup/docker/abc")
+        );
+        assert_eq!(
+            resolve(CgroupDriver::Cgroupfs, Some("/ci/tests")),
+            Path::new("/sys/fs/cgroup/ci/tests/abc")
+        );
+        assert!(cgroup_path(
+            "abc",
+            Some(CgroupVersion::V1),
+            Some(CgroupDriver::Cgroupfs),
+            None
+        )
+        .is_err());
+        assert!(cgroup_path("abc", v2, None, None).is_err());
+    }
+
     #[tokio::test]
     async fn async_logs_are_accessible() -> anyhow::Result<()> {
         let image = GenericImage::new("testcontainers/helloworld", "1.1.0");
@@ -625,6 +1537,33 @@ mod tests {
         similar_container.rm().await.map_err(anyhow::Error::from)
     }
 
+    #[cfg(feature = "reusable-containers")]
+    #[tokio::test]
+    async fn async_reused_containers_match_configuration_hash() -> anyhow::Result<()> {
+        use crate::{ImageExt, ReuseDirective};
+
+        let image = |value: &str| {
+            GenericImage::new("testcontainers/helloworld", "1.1.0")
+                .with_env_var("VALUE", value)
+                .with_label(
+                    "test-name",
+                    "async_reused_containers_match_configuration_hash",
+                )
+                .with_reuse(ReuseDirective::Always)
+                .with_reuse_hash(true)
+        };
+
+        let initial_container = image("initial").start().await?;
+        let reused_container = image("initial").start().await?;
+        let changed_container = image("changed").start().await?;
+
+        assert_eq!(initial_container.id(), reused_container.id());
+        assert_ne!(initial_container.id(), changed_container.id());
+
+        changed_container.rm().await?;
+        reused_container.rm().await.map_err(anyhow::Error::from)
+    }
+
     #[cfg(feature = "reusable-containers")]
     #[tokio::test]
     async fn async_reusable_containers_are_not_dropped() -> anyhow::Result<()> {
@@ -1,10 +1,17 @@
-use std::{fmt, io, pin::Pin, sync::Arc};
+use std::{fmt, io, pin::Pin, sync::Arc, time::Duration};
 
+use bollard_stubs::models::ExecInspectResponse;
 use bytes::Bytes;
 use futures::stream::BoxStream;
 use tokio::io::{AsyncBufRead, AsyncReadExt};
 
-use crate::core::{client::Client, error::Result};
+use crate::{
+    core::{client::Client, error::Result},
+    TestcontainersError,
+};
+
+/// How long the exit code of a command is awaited once its output streams are closed.
+const EXIT_CODE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Represents the result of an executed command in a container.
 pub struct ExecResult {
@@ -23,12 +30,12 @@ impl ExecResult {
     }
 
     /// Returns an asynchronous reader for stdout. It follows log stream until the command exits.
-    pub fn stdout<'b>(&'b mut self) -> Pin<Box<dyn AsyncBufRead + 'b>> {
+    pub fn stdout<'b>(&'b mut self) -> Pin<Box<dyn AsyncBufRead + Send + 'b>> {
         Box::pin(tokio_util::io::StreamReader::new(&mut self.stdout))
     }
 
     /// Returns an asynchronous reader for stderr. It follows log stream until the command exits.
-    pub fn stderr<'b>(&'b mut self) -> Pin<Box<dyn AsyncBufRead + 'b>> {
+    pub fn stderr<'b>(&'b mut self) -> Pin<Box<dyn AsyncBufRead + Send + 'b>> {
         Box::pin(tokio_util::io::StreamReader::new(&mut self.stderr))
     }
 
@@ -51,6 +58,163 @@ impl ExecResult {
         self.stderr().read_to_end(&mut stderr).await?;
         Ok(stderr)
     }
+
+    /// Reads the whole output and waits for the command to exit.
+    pub(crate) async fn into_output(mut self, cmd: Vec<String>) -> Result<ExecOutput> {
+        let stdout = self.stdout_to_vec().await?;
+        let stderr = self.stderr_to_vec().await?;
+
+        // output streams are closed once the process exits, but the exit code may be reported slightly later;
+        // the client is used directly, as the future of `exit_code` borrows the streams, which aren't `Sync`
+        let deadline = tokio::time::Instant::now() + EXIT_CODE_TIMEOUT;
+        let mut backoff = Duration::from_millis(10);
+        let exit_code = loop {
+            if let Some(code) = self.client.inspect_exec(&self.id).await?.exit_code {
+                break code;
+            }
+            // e.g. the process detached from its output streams and keeps running
+            if tokio::time::Instant::now() >= deadline {
+                return Err(TestcontainersError::other(format!(
+                    "exit code of command {cmd:?} is not reported within {EXIT_CODE_TIMEOUT:?} after the end of its output"
+                )));
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Duration::from_millis(500));
+        };
+
+        Ok(ExecOutput {
+            cmd,
+            exit_code,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+/// Output of a command executed as a part of [`ContainerAsync::exec_all`](crate::ContainerAsync::exec_all).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecOutput {
+    cmd: Vec<String>,
+    exit_code: i64,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+impl ExecOutput {
+    /// The executed command.
+    pub fn cmd(&self) -> &[String] {
+        &self.cmd
+    }
+
+    /// Exit code of the command.
+    pub fn exit_code(&self) -> i64 {
+        self.exit_code
+    }
+
+    /// Returns `true` if the command exited with code `0`.
+    pub fn is_success(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    /// Standard output of the command.
+    pub fn stdout(&self) -> &[u8] {
+        &self.stdout
+    }
+
+    /// Standard error of the command.
+    pub fn stderr(&self) -> &[u8] {
+        &self.stderr
+    }
+}
+
+/// Transcript of commands executed by [`ContainerAsync::exec_all`](crate::ContainerAsync::exec_all).
+///
+/// Contains the outputs of all executed commands in order. If a command failed,
+/// it's the last one of the transcript and the remaining commands were not executed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecTranscript {
+    outputs: Vec<ExecOutput>,
+}
+
+impl ExecTranscript {
+    pub(crate) fn push(&mut self, output: ExecOutput) {
+        self.outputs.push(output);
+    }
+
+    /// Outputs of the executed commands.
+    pub fn outputs(&self) -> &[ExecOutput] {
+        &self.outputs
+    }
+
+    /// Returns `true` if all the commands exited successfully.
+    pub fn is_success(&self) -> bool {
+        self.failure().is_none()
+    }
+
+    /// Returns the output of the command which failed and stopped the execution, if any.
+    pub fn failure(&self) -> Option<&ExecOutput> {
+        self.outputs.iter().find(|output| !output.is_success())
+    }
+}
+
+/// Status of a command executed in a container, as listed by [`ContainerAsync::execs`](crate::ContainerAsync::execs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecInfo {
+    id: String,
+    cmd: Vec<String>,
+    running: bool,
+    exit_code: Option<i64>,
+    pid: Option<i64>,
+}
+
+impl ExecInfo {
+    /// Id of the exec instance.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The executed command.
+    pub fn cmd(&self) -> &[String] {
+        &self.cmd
+    }
+
+    /// Returns `true` if the command is still running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Exit code of the command, `None` if it has not exited yet.
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    /// Process id of the command on the Docker host, `None` if it has not been started.
+    pub fn pid(&self) -> Option<i64> {
+        self.pid.filter(|pid| *pid > 0)
+    }
+}
+
+impl From<ExecInspectResponse> for ExecInfo {
+    fn from(inspect: ExecInspectResponse) -> Self {
+        let cmd = inspect
+            .process_config
+            .map(|config| {
+                config
+                    .entrypoint
+                    .into_iter()
+                    .chain(config.arguments.unwrap_or_default())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            id: inspect.id.unwrap_or_default(),
+            cmd,
+            running: inspect.running.unwrap_or_default(),
+            exit_code: inspect.exit_code,
+            pid: inspect.pid,
+        }
+    }
 }
 
 impl fmt::Debug for ExecResult {
@@ -0,0 +1,136 @@
+use std::{fmt, pin::Pin, sync::Arc};
+
+use tokio::io::{AsyncBufRead, AsyncReadExt};
+
+use super::exec::{ExecOutput, ExecResult};
+use crate::core::{
+    client::Client,
+    error::{Result, TestcontainersError},
+    ports::Ports,
+    ContainerPort,
+};
+
+/// A cheap, cloneable handle to a running container.
+///
+/// Unlike [`ContainerAsync`], a handle has no `Drop` semantics: it does not own the container
+/// and never removes it. It's useful for sharing access to the container between helper structs and
+/// spawned tasks, while the owning [`ContainerAsync`] keeps controlling the container lifetime.
+///
+/// Once the owning [`ContainerAsync`] is dropped, operations of the handle will return errors.
+///
+/// [`ContainerAsync`]: crate::ContainerAsync
+#[derive(Clone)]
+pub struct ContainerHandle {
+    id: Arc<str>,
+    docker_client: Arc<Client>,
+}
+
+impl ContainerHandle {
+    pub(crate) fn new(id: &str, docker_client: Arc<Client>) -> Self {
+        Self {
+            id: Arc::from(id),
+            docker_client,
+        }
+    }
+
+    /// Returns the id of the container.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the exposed ports of the container.
+    pub async fn ports(&self) -> Result<Ports> {
+        self.docker_client.ports(&self.id).await.map_err(Into::into)
+    }
+
+    /// Returns the mapped host port for an internal port of the container, on the host's IPv4 interfaces.
+    ///
+    /// See [`ContainerAsync::get_host_port_ipv4`](crate::ContainerAsync::get_host_port_ipv4) for more details.
+    pub async fn get_host_port_ipv4(&self, internal_port: impl Into<ContainerPort>) -> Result<u16> {
+        let internal_port = internal_port.into();
+        self.ports()
+            .await?
+            .map_to_host_port_ipv4(internal_port)
+            .ok_or_else(|| TestcontainersError::PortNotExposed {
+                id: self.id.to_string(),
+                port: internal_port,
+            })
+    }
+
+    /// Returns the mapped host port for an internal port of the container, on the host's IPv6 interfaces.
+    ///
+    /// See [`ContainerAsync::get_host_port_ipv6`](crate::ContainerAsync::get_host_port_ipv6) for more details.
+    pub async fn get_host_port_ipv6(&self, internal_port: impl Into<ContainerPort>) -> Result<u16> {
+        let internal_port = internal_port.into();
+        self.ports()
+            .await?
+            .map_to_host_port_ipv6(internal_port)
+            .ok_or_else(|| TestcontainersError::PortNotExposed {
+                id: self.id.to_string(),
+                port: internal_port,
+            })
+    }
+
+    /// Returns the host that the container may be reached on (may not be the local machine)
+    /// Suitable for use in URL
+    pub async fn get_host(&self) -> Result<url::Host> {
+        self.docker_client
+            .docker_hostname()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Executes the command in the container, waiting for it to exit and collecting its output.
+    pub async fn exec(
+        &self,
+        cmd: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<ExecOutput> {
+        let cmd: Vec<String> = cmd.into_iter().map(Into::into).collect();
+        let exec = self.docker_client.exec(&self.id, cmd.clone()).await?;
+        let exec = ExecResult {
+            client: self.docker_client.clone(),
+            id: exec.id,
+            stdout: exec.stdout.into_inner(),
+            stderr: exec.stderr.into_inner(),
+        };
+        exec.into_output(cmd).await
+    }
+
+    /// Returns an asynchronous reader for stdout.
+    ///
+    /// See [`ContainerAsync::stdout`](crate::ContainerAsync::stdout) for more details.
+    pub fn stdout(&self, follow: bool) -> Pin<Box<dyn AsyncBufRead + Send>> {
+        let stdout = self.docker_client.stdout_logs(&self.id, follow);
+        Box::pin(tokio_util::io::StreamReader::new(stdout))
+    }
+
+    /// Returns an asynchronous reader for stderr.
+    ///
+    /// See [`ContainerAsync::stderr`](crate::ContainerAsync::stderr) for more details.
+    pub fn stderr(&self, follow: bool) -> Pin<Box<dyn AsyncBufRead + Send>> {
+        let stderr = self.docker_client.stderr_logs(&self.id, follow);
+        Box::pin(tokio_util::io::StreamReader::new(stderr))
+    }
+
+    /// Returns stdout as a vector of bytes available at the moment of call (from container startup to present).
+    pub async fn stdout_to_vec(&self) -> Result<Vec<u8>> {
+        let mut stdout = Vec::new();
+        self.stdout(false).read_to_end(&mut stdout).await?;
+        Ok(stdout)
+    }
+
+    /// Returns stderr as a vector of bytes available at the moment of call (from container startup to present).
+    pub async fn stderr_to_vec(&self) -> Result<Vec<u8>> {
+        let mut stderr = Vec::new();
+        self.stderr(false).read_to_end(&mut stderr).await?;
+        Ok(stderr)
+    }
+}
+
+impl fmt::Debug for ContainerHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContainerHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DurationMilliSeconds};
+
+use crate::core::ContainerStats;
+
+/// Limits of the resource usage of a container, checked against the stats sampled in the background.
+///
+/// Unlike the limits enforced by the daemon (e.g. a memory limit killing the container), exceeding the budget
+/// doesn't affect the container: the violation is reported by [`ContainerAsync::check_resource_budget`],
+/// so performance-regression tests can flag runaway containers.
+///
+/// [`ContainerAsync::check_resource_budget`]: crate::ContainerAsync::check_resource_budget
+#[serde_as]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceBudget {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_memory: Option<u64>,
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_cpu_time: Option<Duration>,
+}
+
+/// Resource usage of a container exceeding its [`ResourceBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum BudgetViolation {
+    #[error("memory usage of {usage} bytes exceeds the budget of {limit} bytes")]
+    Memory { usage: u64, limit: u64 },
+    #[error("CPU time of {used:?} exceeds the budget of {limit:?}")]
+    CpuTime { used: Duration, limit: Duration },
+}
+
+impl ResourceBudget {
+    /// Creates a budget without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the memory usage in bytes, excluding the page cache (see [`ContainerStats::memory_usage`]).
+    pub fn with_max_memory(mut self, bytes: u64) -> Self {
+        self.max_memory = Some(bytes);
+        self
+    }
+
+    /// Limits the total CPU time consumed by the container (see [`ContainerStats::cpu_time`]).
+    pub fn with_max_cpu_time(mut self, cpu_time: Duration) -> Self {
+        self.max_cpu_time = Some(cpu_time);
+        self
+    }
+
+    /// Returns the first limit exceeded by the sample, if any.
+    pub(crate) fn check(&self, stats: &ContainerStats) -> Option<BudgetViolation> {
+        if let (Some(limit), Some(usage)) = (self.max_memory, stats.memory_usage()) {
+            if usage > limit {
+                return Some(BudgetViolation::Memory { usage, limit });
+            }
+        }
+        match self.max_cpu_time {
+            Some(limit) if stats.cpu_time() > limit => Some(BudgetViolation::CpuTime {
+                used: stats.cpu_time(),
+                limit,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_exceeded_limit_is_reported() {
+        let budget = ResourceBudget::new()
+            .with_max_memory(1024)
+            .with_max_cpu_time(Duration::from_secs(1));
+
+        let within = ContainerStats::with_usage(Some(1024), Duration::from_secs(1));
+        assert_eq!(budget.check(&within), None);
+
+        let memory = ContainerStats::with_usage(Some(2048), Duration::from_secs(2));
+        assert_eq!(
+            budget.check(&memory),
+            Some(BudgetViolation::Memory {
+                usage: 2048,
+                limit: 1024
+            })
+        );
+
+        // the memory usage isn't reported on some platforms
+        let cpu = ContainerStats::with_usage(None, Duration::from_secs(2));
+        assert_eq!(
+            budget.check(&cpu),
+            Some(BudgetViolation::CpuTime {
+                used: Duration::from_secs(2),
+                limit: Duration::from_secs(1)
+            })
+        );
+
+        assert_eq!(ResourceBudget::new().check(&memory), None);
+    }
+}
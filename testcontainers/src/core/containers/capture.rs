@@ -0,0 +1,82 @@
+use std::{path::PathBuf, time::Duration};
+
+use futures::{future::BoxFuture, FutureExt};
+
+use crate::{
+    core::{error::Result, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, GenericImage, ImageExt, TestcontainersError,
+};
+
+const CAPTURE_IMAGE: (&str, &str) = ("nicolaka/netshoot", "v0.13");
+const CAPTURE_DIR: &str = "/capture";
+
+/// A `tcpdump` sidecar sharing the network namespace of a container,
+/// started with [`ImageExt::with_packet_capture`](crate::ImageExt::with_packet_capture).
+pub(crate) struct PacketCapture {
+    sidecar: Box<ContainerAsync<GenericImage>>,
+    output: PathBuf,
+}
+
+impl PacketCapture {
+    /// Starts capturing the traffic of the container `id` on all of its interfaces,
+    /// once `tcpdump` is listening.
+    pub(crate) async fn start(id: &str, output: PathBuf) -> Result<Self> {
+        let file_name = output
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                TestcontainersError::other(format!(
+                    "packet capture path {output:?} doesn't name a file"
+                ))
+            })?;
+
+        let (name, tag) = CAPTURE_IMAGE;
+        let sidecar = GenericImage::new(name, tag)
+            .with_entrypoint("sh")
+            .with_wait_for(WaitFor::message_on_stderr("listening on"))
+            .with_cmd([
+                "-c".to_string(),
+                // `exec` so that tcpdump receives SIGTERM and flushes the capture on stop
+                format!("mkdir -p {CAPTURE_DIR} && exec tcpdump -i any -U -w '{CAPTURE_DIR}/{file_name}'"),
+            ])
+            .with_network(format!("container:{id}"))
+            .with_cap_add("NET_ADMIN")
+            .with_cap_add("NET_RAW")
+            .with_stop_timeout(Duration::from_secs(5))
+            .start()
+            .await?;
+        log::debug!(
+            "Capturing packets of container {id} with sidecar {}",
+            sidecar.id()
+        );
+
+        Ok(Self {
+            sidecar: Box::new(sidecar),
+            output,
+        })
+    }
+
+    /// Stops the capture and writes it to the host. Failures are logged, as it runs on teardown.
+    ///
+    /// Boxed, as removing the sidecar finishes its own (absent) capture.
+    pub(crate) fn finish(self) -> BoxFuture<'static, ()> {
+        let Self { sidecar, output } = self;
+        let sidecar = *sidecar;
+        let host_dir = output.parent().map(PathBuf::from).unwrap_or_default();
+
+        async move {
+            let result = async {
+                sidecar.stop().await?;
+                sidecar.copy_dir_from(CAPTURE_DIR, &host_dir).await?;
+                sidecar.rm().await
+            }
+            .await;
+            match result {
+                Ok(()) => log::debug!("Packet capture written to {output:?}"),
+                Err(err) => log::warn!("Failed to write packet capture to {output:?}: {err}"),
+            }
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,71 @@
+use bollard_stubs::models::{ChangeType, FilesystemChange as ChangeResponse};
+
+/// Kind of a change of the filesystem of a container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilesystemChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+/// A path of the container filesystem changed since the container was created, compared to its image.
+///
+/// Obtained with [`ContainerAsync::changes`](crate::ContainerAsync::changes).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FilesystemChange {
+    path: String,
+    kind: FilesystemChangeKind,
+}
+
+impl FilesystemChange {
+    pub(crate) fn from_response(change: ChangeResponse) -> Self {
+        let kind = match change.kind {
+            ChangeType::_0 => FilesystemChangeKind::Modified,
+            ChangeType::_1 => FilesystemChangeKind::Added,
+            ChangeType::_2 => FilesystemChangeKind::Deleted,
+        };
+        Self {
+            path: change.path,
+            kind,
+        }
+    }
+
+    /// Absolute path of the changed file or directory, e.g. `/etc/hosts`.
+    ///
+    /// Note that the parent directories of an added file are reported as modified.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Kind of the change.
+    pub fn kind(&self) -> FilesystemChangeKind {
+        self.kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_kinds_are_mapped() {
+        let change = |path: &str, kind| {
+            FilesystemChange::from_response(ChangeResponse {
+                path: path.to_string(),
+                kind,
+            })
+        };
+
+        assert_eq!(
+            change("/tmp", ChangeType::_0).kind(),
+            FilesystemChangeKind::Modified
+        );
+        assert_eq!(
+            change("/tmp/out.txt", ChangeType::_1).kind(),
+            FilesystemChangeKind::Added
+        );
+        let deleted = change("/etc/motd", ChangeType::_2);
+        assert_eq!(deleted.kind(), FilesystemChangeKind::Deleted);
+        assert_eq!(deleted.path(), "/etc/motd");
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use bollard_stubs::models::EventMessage;
+
+/// Kind of a container event, the most common ones are recognized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerEventKind {
+    Create,
+    Start,
+    Restart,
+    Stop,
+    Pause,
+    Unpause,
+    /// A signal has been sent to the container.
+    Kill {
+        signal: Option<String>,
+    },
+    /// The main process of the container has exited.
+    Die {
+        exit_code: Option<i64>,
+    },
+    /// The container has run out of memory.
+    Oom,
+    /// The health status of the container has changed, e.g. to `healthy` or `unhealthy`.
+    HealthStatus(String),
+    Destroy,
+    /// Any other action, e.g. `exec_start` or `attach`.
+    Other(String),
+}
+
+/// An event of a container, reported by the Docker engine.
+///
+/// Obtained with [`ContainerAsync::events`](crate::ContainerAsync::events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerEvent {
+    kind: ContainerEventKind,
+    action: String,
+    time: Option<SystemTime>,
+    attributes: HashMap<String, String>,
+}
+
+impl ContainerEvent {
+    pub(crate) fn from_message(message: EventMessage) -> Self {
+        let action = message.action.unwrap_or_default();
+        let attributes = message
+            .actor
+            .and_then(|actor| actor.attributes)
+            .unwrap_or_default();
+
+        // some actions carry a detail, e.g. `health_status: healthy` or `exec_start: sh -c ...`
+        let (name, detail) = action
+            .split_once(": ")
+            .map_or((action.as_str(), None), |(name, detail)| {
+                (name, Some(detail))
+            });
+        let kind = match name {
+            "create" => ContainerEventKind::Create,
+            "start" => ContainerEventKind::Start,
+            "restart" => ContainerEventKind::Restart,
+            "stop" => ContainerEventKind::Stop,
+            "pause" => ContainerEventKind::Pause,
+            "unpause" => ContainerEventKind::Unpause,
+            "kill" => ContainerEventKind::Kill {
+                signal: attributes.get("signal").cloned(),
+            },
+            "die" => ContainerEventKind::Die {
+                exit_code: attributes
+                    .get("exitCode")
+                    .and_then(|code| code.parse().ok()),
+            },
+            "oom" => ContainerEventKind::Oom,
+            "health_status" => ContainerEventKind::HealthStatus(detail.unwrap_or_default().into()),
+            "destroy" => ContainerEventKind::Destroy,
+            _ => ContainerEventKind::Other(action.clone()),
+        };
+
+        let time = message
+            .time_nano
+            .and_then(|nanos| u64::try_from(nanos).ok())
+            .map(Duration::from_nanos)
+            .or_else(|| {
+                let secs = u64::try_from(message.time?).ok()?;
+                Some(Duration::from_secs(secs))
+            })
+            .map(|since_epoch| SystemTime::UNIX_EPOCH + since_epoch);
+
+        Self {
+            kind,
+            action,
+            time,
+            attributes,
+        }
+    }
+
+    /// Kind of the event.
+    pub fn kind(&self) -> &ContainerEventKind {
+        &self.kind
+    }
+
+    /// The action as reported by the engine, e.g. `die` or `health_status: healthy`.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// The time the event occurred.
+    pub fn time(&self) -> Option<SystemTime> {
+        self.time
+    }
+
+    /// Attributes of the event, including the labels of the container, its name and image.
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard_stubs::models::EventActor;
+
+    use super::*;
+
+    fn message(action: &str, attributes: &[(&str, &str)]) -> EventMessage {
+        EventMessage {
+            action: Some(action.to_string()),
+            actor: Some(EventActor {
+                id: Some("0123456789ab".to_string()),
+                attributes: Some(
+                    attributes
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+            }),
+            time: Some(1_718_096_913),
+            time_nano: Some(1_718_096_913_123_456_789),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn event_kind_is_parsed_from_action() {
+        let event = ContainerEvent::from_message(message("die", &[("exitCode", "137")]));
+        assert_eq!(
+            event.kind(),
+            &ContainerEventKind::Die {
+                exit_code: Some(137)
+            }
+        );
+        assert_eq!(
+            event.time(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_nanos(1_718_096_913_123_456_789))
+        );
+
+        let event = ContainerEvent::from_message(message("health_status: unhealthy", &[]));
+        assert_eq!(
+            event.kind(),
+            &ContainerEventKind::HealthStatus("unhealthy".to_string())
+        );
+
+        let event = ContainerEvent::from_message(message("exec_start: sh -c true", &[]));
+        assert_eq!(
+            event.kind(),
+            &ContainerEventKind::Other("exec_start: sh -c true".to_string())
+        );
+        assert_eq!(event.action(), "exec_start: sh -c true");
+    }
+}
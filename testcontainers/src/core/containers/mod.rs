@@ -1,10 +1,27 @@
 pub(crate) mod async_container;
+pub(crate) mod budget;
+pub(crate) mod capture;
+pub(crate) mod changes;
+pub(crate) mod events;
+pub(crate) mod report;
 pub(crate) mod request;
+pub(crate) mod state;
+pub(crate) mod stats;
 #[cfg(feature = "blocking")]
 pub(crate) mod sync_container;
 
-pub use async_container::{exec::ExecResult, ContainerAsync};
-pub use request::{CgroupnsMode, ContainerRequest, Host, PortMapping};
+pub use async_container::{
+    exec::{ExecInfo, ExecOutput, ExecResult, ExecTranscript},
+    handle::ContainerHandle,
+    ContainerAsync,
+};
+pub use budget::{BudgetViolation, ResourceBudget};
+pub use changes::{FilesystemChange, FilesystemChangeKind};
+pub use events::{ContainerEvent, ContainerEventKind};
+pub use report::{StartupReport, WaitStepReport};
+pub use request::{CgroupnsMode, ContainerCreateBody, ContainerRequest, Host, PortMapping};
+pub use state::{ContainerStateSnapshot, ContainerStatus};
+pub use stats::ContainerStats;
 #[cfg(feature = "blocking")]
 #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
 pub use sync_container::{exec::SyncExecResult, Container};
@@ -0,0 +1,156 @@
+use std::{fmt, time::Duration};
+
+use crate::core::WaitFor;
+
+/// Timings of the startup of a container, to track slow or flaky startups over time.
+///
+/// Obtained with [`ContainerAsync::startup_report`](crate::ContainerAsync::startup_report).
+/// The report of a reused container is empty, as it was started earlier.
+///
+/// Its `Display` representation is a one-line summary, e.g.
+/// `pull: 1.2s, create: 35.0ms, start: 210.0ms, wait: 2.1s (log: 2.1s), total: 3.6s`,
+/// which is logged once the container is ready with [`ImageExt::with_startup_report_logging`].
+///
+/// [`ImageExt::with_startup_report_logging`]: crate::ImageExt::with_startup_report_logging
+#[derive(Debug, Clone, Default)]
+pub struct StartupReport {
+    pub(crate) pull: Option<Duration>,
+    pub(crate) create: Duration,
+    pub(crate) start: Duration,
+    pub(crate) wait_steps: Vec<WaitStepReport>,
+    pub(crate) total: Duration,
+}
+
+/// Time spent waiting for a single ready condition.
+#[derive(Debug, Clone)]
+pub struct WaitStepReport {
+    condition: WaitFor,
+    duration: Duration,
+}
+
+impl StartupReport {
+    /// Time spent pulling the image, if it was missing.
+    pub fn pull(&self) -> Option<Duration> {
+        self.pull
+    }
+
+    /// Time spent creating the container, excluding the pull of the image.
+    pub fn create(&self) -> Duration {
+        self.create
+    }
+
+    /// Time spent starting the container, until the daemon reported it as started.
+    pub fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Time spent waiting for each ready condition, in the order they were awaited.
+    pub fn wait_steps(&self) -> &[WaitStepReport] {
+        &self.wait_steps
+    }
+
+    /// Time spent waiting for all the ready conditions.
+    pub fn wait(&self) -> Duration {
+        self.wait_steps.iter().map(WaitStepReport::duration).sum()
+    }
+
+    /// Overall time of the startup, from the request until the container was ready,
+    /// including the commands executed after start.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+impl fmt::Display for StartupReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(pull) = self.pull {
+            write!(f, "pull: {pull:.1?}, ")?;
+        }
+        write!(
+            f,
+            "create: {:.1?}, start: {:.1?}, wait: {:.1?}",
+            self.create,
+            self.start,
+            self.wait()
+        )?;
+        if !self.wait_steps.is_empty() {
+            let steps = self
+                .wait_steps
+                .iter()
+                .map(|step| format!("{}: {:.1?}", condition_kind(&step.condition), step.duration))
+                .collect::<Vec<_>>();
+            write!(f, " ({})", steps.join(", "))?;
+        }
+        write!(f, ", total: {:.1?}", self.total)
+    }
+}
+
+fn condition_kind(condition: &WaitFor) -> &'static str {
+    match condition {
+        WaitFor::Nothing => "nothing",
+        WaitFor::Log(_) => "log",
+        WaitFor::LogQuiet(_) => "quiet",
+        WaitFor::Duration { .. } => "duration",
+        WaitFor::Healthcheck(_) => "healthcheck",
+        #[cfg(feature = "http_wait")]
+        WaitFor::Http(_) => "http",
+        #[cfg(feature = "grpc_wait")]
+        WaitFor::Grpc(_) => "grpc",
+        WaitFor::Exit(_) => "exit",
+        WaitFor::Port(_) => "port",
+        WaitFor::Command(_) => "command",
+        WaitFor::Composite(_) => "composite",
+        WaitFor::Custom(_) => "custom",
+    }
+}
+
+impl WaitStepReport {
+    pub(crate) fn new(condition: WaitFor, duration: Duration) -> Self {
+        Self {
+            condition,
+            duration,
+        }
+    }
+
+    /// The awaited condition.
+    pub fn condition(&self) -> &WaitFor {
+        &self.condition
+    }
+
+    /// Time until the condition was met.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_lists_phases_and_wait_steps() {
+        let report = StartupReport {
+            pull: Some(Duration::from_millis(1200)),
+            create: Duration::from_millis(35),
+            start: Duration::from_millis(210),
+            wait_steps: vec![
+                WaitStepReport::new(
+                    WaitFor::message_on_stdout("ready"),
+                    Duration::from_millis(1500),
+                ),
+                WaitStepReport::new(WaitFor::seconds(1), Duration::from_millis(1000)),
+            ],
+            total: Duration::from_millis(3000),
+        };
+        assert_eq!(
+            report.to_string(),
+            "pull: 1.2s, create: 35.0ms, start: 210.0ms, wait: 2.5s (log: 1.5s, duration: 1.0s), total: 3.0s"
+        );
+
+        let report = StartupReport::default();
+        assert_eq!(
+            report.to_string(),
+            "create: 0.0ns, start: 0.0ns, wait: 0.0ns, total: 0.0ns"
+        );
+    }
+}
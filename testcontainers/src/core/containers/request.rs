@@ -3,48 +3,91 @@ use std::{
     collections::BTreeMap,
     fmt::{Debug, Formatter},
     net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
 use bollard_stubs::models::ResourcesUlimits;
+use futures::future::BoxFuture;
 
 use crate::{
     core::{
-        copy::CopyToContainer, logs::consumer::LogConsumer, mounts::Mount, ports::ContainerPort,
-        ContainerState, ExecCommand, WaitFor,
+        copy::CopyToContainer, image::policy::ImagePolicy, logs::consumer::LogConsumer,
+        mounts::Mount, ports::ContainerPort, ContainerHandle, ContainerState, ExecCommand,
+        ResourceBudget, WaitFor,
     },
     Image, TestcontainersError,
 };
 
+mod spec;
+
 /// Represents a request to start a container, allowing customization of the container.
+///
+/// The request can be serialized (e.g. to hash it or to keep it as a golden file), which includes
+/// only its declarative configuration: ready conditions, log consumers, the create body modifier,
+/// the image policy and commands executed after start are omitted. Maps are ordered by key, so the output is stable.
+/// A serialized request of any image can be deserialized as a request of [`GenericImage`](crate::GenericImage).
 #[must_use]
 pub struct ContainerRequest<I: Image> {
     pub(crate) image: I,
     pub(crate) overridden_cmd: Vec<String>,
+    pub(crate) shell_cmd: Option<String>,
     pub(crate) image_name: Option<String>,
     pub(crate) image_tag: Option<String>,
     pub(crate) container_name: Option<String>,
     pub(crate) network: Option<String>,
+    pub(crate) network_aliases: BTreeMap<String, Vec<String>>,
     pub(crate) labels: BTreeMap<String, String>,
     pub(crate) env_vars: BTreeMap<String, String>,
     pub(crate) hosts: BTreeMap<String, Host>,
     pub(crate) mounts: Vec<Mount>,
+    pub(crate) mount_owner: Option<(u32, u32)>,
     pub(crate) copy_to_sources: Vec<CopyToContainer>,
     pub(crate) ports: Option<Vec<PortMapping>>,
     pub(crate) ulimits: Option<Vec<ResourcesUlimits>>,
     pub(crate) privileged: bool,
+    pub(crate) open_stdin: bool,
     pub(crate) cap_add: Option<Vec<String>>,
     pub(crate) cap_drop: Option<Vec<String>>,
     pub(crate) shm_size: Option<u64>,
     pub(crate) cgroupns_mode: Option<CgroupnsMode>,
     pub(crate) userns_mode: Option<String>,
     pub(crate) startup_timeout: Option<Duration>,
+    pub(crate) stop_timeout: Option<Duration>,
+    pub(crate) log_startup_report: bool,
+    pub(crate) packet_capture: Option<PathBuf>,
+    pub(crate) wait_timeout: Option<Duration>,
     pub(crate) working_dir: Option<String>,
+    pub(crate) hostname: Option<String>,
+    pub(crate) mac_address: Option<String>,
+    pub(crate) resource_budget: Option<ResourceBudget>,
+    pub(crate) docker_host: Option<String>,
+    pub(crate) timeout_escalation: Option<TimeoutEscalation>,
+    pub(crate) image_policy: Option<ImagePolicy>,
+    pub(crate) create_body_modifier: Option<CreateBodyModifier>,
+    /// Set by the runner once the image is known, see `TESTCONTAINERS_IMPLICIT_HEALTHCHECK_WAIT`.
+    pub(crate) implicit_healthcheck: bool,
     pub(crate) log_consumers: Vec<Box<dyn LogConsumer + 'static>>,
     #[cfg(feature = "reusable-containers")]
     pub(crate) reuse: crate::ReuseDirective,
+    #[cfg(feature = "reusable-containers")]
+    pub(crate) reuse_hash: bool,
 }
 
+/// Callback collecting diagnostics of a container which is about to exceed its startup timeout.
+pub(crate) type TimeoutEscalation =
+    Arc<dyn Fn(ContainerHandle) -> BoxFuture<'static, String> + Send + Sync>;
+
+/// Body of the request creating a container, as submitted to the Docker daemon.
+///
+/// **NOTE:** this is the type of the underlying Docker client, and it may change with its upgrades
+/// (even in minor releases of `testcontainers`).
+pub type ContainerCreateBody = bollard::container::Config<String>;
+
+/// Callback adjusting the body of the request creating a container right before its submission.
+pub(crate) type CreateBodyModifier = Arc<dyn Fn(&mut ContainerCreateBody) + Send + Sync>;
+
 /// Represents a port mapping between a host's external port and the internal port of a container.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PortMapping {
@@ -52,7 +95,7 @@ pub struct PortMapping {
     pub(crate) container_port: ContainerPort,
 }
 
-#[derive(parse_display::Display, Debug, Clone)]
+#[derive(parse_display::Display, parse_display::FromStr, Debug, Clone)]
 pub enum Host {
     #[display("{0}")]
     Addr(IpAddr),
@@ -60,7 +103,8 @@ pub enum Host {
     HostGateway,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CgroupnsMode {
     /// Use the host system's cgroup namespace
     Host,
@@ -77,6 +121,11 @@ impl<I: Image> ContainerRequest<I> {
         &self.network
     }
 
+    /// Returns the DNS aliases of the container, per network.
+    pub fn network_aliases(&self) -> &BTreeMap<String, Vec<String>> {
+        &self.network_aliases
+    }
+
     pub fn labels(&self) -> &BTreeMap<String, String> {
         &self.labels
     }
@@ -105,6 +154,11 @@ impl<I: Image> ContainerRequest<I> {
         self.image.mounts().into_iter().chain(self.mounts.iter())
     }
 
+    /// Returns the `uid` and `gid` the writable mounts are chowned to after start, if any.
+    pub fn mount_owner(&self) -> Option<(u32, u32)> {
+        self.mount_owner
+    }
+
     pub fn copy_to_sources(&self) -> impl Iterator<Item = &CopyToContainer> {
         self.image
             .copy_to_sources()
@@ -120,6 +174,11 @@ impl<I: Image> ContainerRequest<I> {
         self.privileged
     }
 
+    /// Indicates whether the stdin of the container is kept open.
+    pub fn open_stdin(&self) -> bool {
+        self.open_stdin
+    }
+
     pub fn cap_add(&self) -> Option<&Vec<String>> {
         self.cap_add.as_ref()
     }
@@ -153,6 +212,11 @@ impl<I: Image> ContainerRequest<I> {
         }
     }
 
+    /// Returns the command to be executed using the shell of the image, if any.
+    pub fn shell_cmd(&self) -> Option<&str> {
+        self.shell_cmd.as_deref()
+    }
+
     pub fn descriptor(&self) -> String {
         let original_name = self.image.name();
         let original_tag = self.image.tag();
@@ -183,15 +247,73 @@ impl<I: Image> ContainerRequest<I> {
         self.startup_timeout
     }
 
+    /// Returns the grace period of the container to shut down before it's removed, if set.
+    pub fn stop_timeout(&self) -> Option<Duration> {
+        self.stop_timeout
+    }
+
+    /// Returns `true` if the startup report is logged once the container is ready.
+    pub fn log_startup_report(&self) -> bool {
+        self.log_startup_report
+    }
+
+    /// Returns the file on the host the captured packets of the container are written to, if set.
+    pub fn packet_capture(&self) -> Option<&Path> {
+        self.packet_capture.as_deref()
+    }
+
+    /// Returns the deadline of each ready condition which doesn't have its own.
+    pub fn wait_timeout(&self) -> Option<Duration> {
+        self.wait_timeout
+    }
+
     pub fn working_dir(&self) -> Option<&str> {
         self.working_dir.as_deref()
     }
 
+    /// Returns the hostname of the container, if set.
+    pub fn hostname(&self) -> Option<&str> {
+        self.hostname.as_deref()
+    }
+
+    /// Returns the MAC address of the container, if set.
+    pub fn mac_address(&self) -> Option<&str> {
+        self.mac_address.as_deref()
+    }
+
+    /// Returns the resource budget the container is checked against, if set.
+    pub fn resource_budget(&self) -> Option<ResourceBudget> {
+        self.resource_budget
+    }
+
+    pub(crate) fn timeout_escalation(&self) -> Option<&TimeoutEscalation> {
+        self.timeout_escalation.as_ref()
+    }
+
+    pub(crate) fn image_policy(&self) -> Option<&ImagePolicy> {
+        self.image_policy.as_ref()
+    }
+
+    pub(crate) fn create_body_modifier(&self) -> Option<&CreateBodyModifier> {
+        self.create_body_modifier.as_ref()
+    }
+
+    /// Returns the Docker host overridden for this request, if any.
+    pub fn docker_host(&self) -> Option<&str> {
+        self.docker_host.as_deref()
+    }
+
     /// Indicates that the container will not be stopped when it is dropped
     #[cfg(feature = "reusable-containers")]
     pub fn reuse(&self) -> crate::ReuseDirective {
         self.reuse
     }
+
+    /// Indicates that a container is reused only if its configuration hash matches
+    #[cfg(feature = "reusable-containers")]
+    pub fn reuse_hash(&self) -> bool {
+        self.reuse_hash
+    }
 }
 
 impl<I: Image> From<I> for ContainerRequest<I> {
@@ -199,32 +321,63 @@ impl<I: Image> From<I> for ContainerRequest<I> {
         Self {
             image,
             overridden_cmd: Vec::new(),
+            shell_cmd: None,
             image_name: None,
             image_tag: None,
             container_name: None,
             network: None,
+            network_aliases: BTreeMap::default(),
             labels: BTreeMap::default(),
             env_vars: BTreeMap::default(),
             hosts: BTreeMap::default(),
             mounts: Vec::new(),
+            mount_owner: None,
             copy_to_sources: Vec::new(),
             ports: None,
             ulimits: None,
             privileged: false,
+            open_stdin: false,
             cap_add: None,
             cap_drop: None,
             shm_size: None,
             cgroupns_mode: None,
             userns_mode: None,
             startup_timeout: None,
+            stop_timeout: None,
+            log_startup_report: false,
+            packet_capture: None,
+            wait_timeout: default_wait_timeout(),
             working_dir: None,
+            hostname: None,
+            mac_address: None,
+            resource_budget: None,
+            docker_host: None,
+            timeout_escalation: None,
+            image_policy: None,
+            create_body_modifier: None,
+            implicit_healthcheck: false,
             log_consumers: vec![],
             #[cfg(feature = "reusable-containers")]
             reuse: crate::ReuseDirective::Never,
+            #[cfg(feature = "reusable-containers")]
+            reuse_hash: false,
         }
     }
 }
 
+/// Reads the default wait timeout from the `TESTCONTAINERS_WAIT_TIMEOUT` env variable (e.g. `30s`).
+fn default_wait_timeout() -> Option<Duration> {
+    let value = std::env::var("TESTCONTAINERS_WAIT_TIMEOUT").ok()?;
+    if value.trim().is_empty() {
+        return None;
+    }
+    humantime::parse_duration(value.trim())
+        .inspect_err(|err| {
+            log::warn!("Ignoring invalid TESTCONTAINERS_WAIT_TIMEOUT '{value}': {err}");
+        })
+        .ok()
+}
+
 impl PortMapping {
     pub(crate) fn new(local: u16, internal: ContainerPort) -> Self {
         Self {
@@ -248,27 +401,44 @@ impl<I: Image + Debug> Debug for ContainerRequest<I> {
 
         repr.field("image", &self.image)
             .field("overridden_cmd", &self.overridden_cmd)
+            .field("shell_cmd", &self.shell_cmd)
             .field("image_name", &self.image_name)
             .field("image_tag", &self.image_tag)
             .field("container_name", &self.container_name)
             .field("network", &self.network)
+            .field("network_aliases", &self.network_aliases)
             .field("labels", &self.labels)
             .field("env_vars", &self.env_vars)
             .field("hosts", &self.hosts)
             .field("mounts", &self.mounts)
+            .field("mount_owner", &self.mount_owner)
             .field("ports", &self.ports)
             .field("ulimits", &self.ulimits)
             .field("privileged", &self.privileged)
+            .field("open_stdin", &self.open_stdin)
             .field("cap_add", &self.cap_add)
             .field("cap_drop", &self.cap_drop)
             .field("shm_size", &self.shm_size)
             .field("cgroupns_mode", &self.cgroupns_mode)
             .field("userns_mode", &self.userns_mode)
             .field("startup_timeout", &self.startup_timeout)
-            .field("working_dir", &self.working_dir);
+            .field("stop_timeout", &self.stop_timeout)
+            .field("log_startup_report", &self.log_startup_report)
+            .field("packet_capture", &self.packet_capture)
+            .field("wait_timeout", &self.wait_timeout)
+            .field("working_dir", &self.working_dir)
+            .field("hostname", &self.hostname)
+            .field("mac_address", &self.mac_address)
+            .field("resource_budget", &self.resource_budget)
+            .field("docker_host", &self.docker_host)
+            .field("timeout_escalation", &self.timeout_escalation.is_some())
+            .field("image_policy", &self.image_policy.is_some())
+            .field("create_body_modifier", &self.create_body_modifier.is_some());
 
         #[cfg(feature = "reusable-containers")]
         repr.field("reusable", &self.reuse);
+        #[cfg(feature = "reusable-containers")]
+        repr.field("reuse_hash", &self.reuse_hash);
 
         repr.finish()
     }
@@ -0,0 +1,289 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use bollard_stubs::models::ResourcesUlimits;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{serde_as, DisplayFromStr, DurationMilliSeconds};
+
+use crate::{
+    core::{
+        containers::{
+            request::{CgroupnsMode, Host, PortMapping},
+            ResourceBudget,
+        },
+        copy::CopyToContainer,
+        mounts::Mount,
+        ports::ContainerPort,
+    },
+    ContainerRequest, GenericImage, Image,
+};
+
+/// Serializable view of a [`ContainerRequest`], omitting everything that can't be represented as data.
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+struct ContainerRequestSpec {
+    image_name: String,
+    image_tag: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entrypoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cmd: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shell_cmd: Option<String>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exposed_ports: Vec<ContainerPort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    container_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    network: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    network_aliases: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    env_vars: BTreeMap<String, String>,
+    #[serde_as(as = "BTreeMap<_, DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    hosts: BTreeMap<String, Host>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mounts: Vec<Mount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mount_owner: Option<(u32, u32)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    copy_to: Vec<CopyToContainer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ports: Option<Vec<PortMappingSpec>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ulimits: Option<Vec<ResourcesUlimits>>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    privileged: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    open_stdin: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cap_add: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cap_drop: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shm_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cgroupns_mode: Option<CgroupnsMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    userns_mode: Option<String>,
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    startup_timeout_ms: Option<Duration>,
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stop_timeout_ms: Option<Duration>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    log_startup_report: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    packet_capture: Option<PathBuf>,
+    #[serde_as(as = "Option<DurationMilliSeconds<u64>>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait_timeout_ms: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    working_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mac_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resource_budget: Option<ResourceBudget>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    docker_host: Option<String>,
+    #[cfg(feature = "reusable-containers")]
+    #[serde(default)]
+    reuse: crate::ReuseDirective,
+    #[cfg(feature = "reusable-containers")]
+    #[serde(default, skip_serializing_if = "is_false")]
+    reuse_hash: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+struct PortMappingSpec {
+    host_port: u16,
+    #[serde_as(as = "DisplayFromStr")]
+    container_port: ContainerPort,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl<I: Image> From<&ContainerRequest<I>> for ContainerRequestSpec {
+    fn from(request: &ContainerRequest<I>) -> Self {
+        let image = request.image();
+        Self {
+            image_name: request
+                .image_name
+                .clone()
+                .unwrap_or_else(|| image.name().to_string()),
+            image_tag: request
+                .image_tag
+                .clone()
+                .unwrap_or_else(|| image.tag().to_string()),
+            entrypoint: request.entrypoint().map(String::from),
+            cmd: request.cmd().map(String::from).collect(),
+            shell_cmd: request.shell_cmd.clone(),
+            exposed_ports: request.expose_ports().to_vec(),
+            container_name: request.container_name.clone(),
+            network: request.network.clone(),
+            network_aliases: request.network_aliases.clone(),
+            labels: request.labels.clone(),
+            env_vars: request
+                .env_vars()
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect(),
+            hosts: request.hosts.clone(),
+            mounts: request.mounts().cloned().collect(),
+            mount_owner: request.mount_owner,
+            copy_to: request.copy_to_sources().cloned().collect(),
+            ports: request.ports.as_ref().map(|ports| {
+                ports
+                    .iter()
+                    .map(|mapping| PortMappingSpec {
+                        host_port: mapping.host_port,
+                        container_port: mapping.container_port,
+                    })
+                    .collect()
+            }),
+            ulimits: request.ulimits.clone(),
+            privileged: request.privileged,
+            open_stdin: request.open_stdin,
+            cap_add: request.cap_add.clone(),
+            cap_drop: request.cap_drop.clone(),
+            shm_size: request.shm_size,
+            cgroupns_mode: request.cgroupns_mode,
+            userns_mode: request.userns_mode.clone(),
+            startup_timeout_ms: request.startup_timeout,
+            stop_timeout_ms: request.stop_timeout,
+            log_startup_report: request.log_startup_report,
+            packet_capture: request.packet_capture.clone(),
+            wait_timeout_ms: request.wait_timeout,
+            working_dir: request.working_dir.clone(),
+            hostname: request.hostname.clone(),
+            mac_address: request.mac_address.clone(),
+            resource_budget: request.resource_budget,
+            docker_host: request.docker_host.clone(),
+            #[cfg(feature = "reusable-containers")]
+            reuse: request.reuse,
+            #[cfg(feature = "reusable-containers")]
+            reuse_hash: request.reuse_hash,
+        }
+    }
+}
+
+impl From<ContainerRequestSpec> for ContainerRequest<GenericImage> {
+    fn from(spec: ContainerRequestSpec) -> Self {
+        let mut image = GenericImage::new(spec.image_name, spec.image_tag);
+        if let Some(entrypoint) = &spec.entrypoint {
+            image = image.with_entrypoint(entrypoint);
+        }
+        for port in spec.exposed_ports {
+            image = image.with_exposed_port(port);
+        }
+
+        Self {
+            overridden_cmd: spec.cmd,
+            shell_cmd: spec.shell_cmd,
+            container_name: spec.container_name,
+            network: spec.network,
+            network_aliases: spec.network_aliases,
+            labels: spec.labels,
+            env_vars: spec.env_vars,
+            hosts: spec.hosts,
+            mounts: spec.mounts,
+            mount_owner: spec.mount_owner,
+            copy_to_sources: spec.copy_to,
+            ports: spec.ports.map(|ports| {
+                ports
+                    .into_iter()
+                    .map(|mapping| PortMapping::new(mapping.host_port, mapping.container_port))
+                    .collect()
+            }),
+            ulimits: spec.ulimits,
+            privileged: spec.privileged,
+            open_stdin: spec.open_stdin,
+            cap_add: spec.cap_add,
+            cap_drop: spec.cap_drop,
+            shm_size: spec.shm_size,
+            cgroupns_mode: spec.cgroupns_mode,
+            userns_mode: spec.userns_mode,
+            startup_timeout: spec.startup_timeout_ms,
+            stop_timeout: spec.stop_timeout_ms,
+            log_startup_report: spec.log_startup_report,
+            packet_capture: spec.packet_capture,
+            wait_timeout: spec.wait_timeout_ms,
+            working_dir: spec.working_dir,
+            hostname: spec.hostname,
+            mac_address: spec.mac_address,
+            resource_budget: spec.resource_budget,
+            docker_host: spec.docker_host,
+            #[cfg(feature = "reusable-containers")]
+            reuse: spec.reuse,
+            #[cfg(feature = "reusable-containers")]
+            reuse_hash: spec.reuse_hash,
+            ..ContainerRequest::from(image)
+        }
+    }
+}
+
+impl<I: Image> Serialize for ContainerRequest<I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ContainerRequestSpec::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ContainerRequest<GenericImage> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ContainerRequestSpec::deserialize(deserializer).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{IntoContainerPort, WaitFor},
+        ImageExt,
+    };
+
+    #[test]
+    fn serialization_is_stable_across_round_trips() {
+        let request = GenericImage::new("redis", "7.2.4")
+            .with_exposed_port(6379.tcp())
+            .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+            .with_env_var("B", "2")
+            .with_env_var("A", "1")
+            .with_mapped_port(16379, 6379.tcp())
+            .with_host("gateway", Host::HostGateway)
+            .with_mount(Mount::tmpfs_mount("/data"))
+            .with_cmd(["redis-server", "--appendonly", "yes"])
+            .with_startup_timeout(Duration::from_secs(30))
+            .with_hostname("cache")
+            .with_resource_budget(ResourceBudget::new().with_max_memory(64 * 1024 * 1024));
+
+        let serialized = serde_json::to_value(&request).unwrap();
+        assert_eq!(serialized["image_name"], "redis");
+        assert_eq!(serialized["exposed_ports"][0], "6379/tcp");
+        assert_eq!(serialized["hosts"]["gateway"], "host-gateway");
+        assert_eq!(serialized["startup_timeout_ms"], 30000);
+        assert_eq!(serialized["hostname"], "cache");
+        assert_eq!(
+            serialized["resource_budget"]["max_memory"],
+            64 * 1024 * 1024
+        );
+        assert_eq!(
+            serde_json::to_string(&serialized["env_vars"]).unwrap(),
+            r#"{"A":"1","B":"2"}"#
+        );
+
+        let deserialized: ContainerRequest<GenericImage> =
+            serde_json::from_value(serialized.clone()).unwrap();
+        assert_eq!(deserialized.descriptor(), "redis:7.2.4");
+        assert!(deserialized.ready_conditions().is_empty());
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), serialized);
+    }
+}
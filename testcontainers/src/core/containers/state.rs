@@ -0,0 +1,161 @@
+use std::time::{Duration, SystemTime};
+
+use bollard_stubs::models::{ContainerState as InspectState, ContainerStateStatusEnum};
+
+/// Status of a container, as reported by the Docker daemon.
+#[derive(parse_display::Display, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[display(style = "lowercase")]
+pub enum ContainerStatus {
+    Created,
+    Running,
+    Paused,
+    Restarting,
+    Removing,
+    Exited,
+    Dead,
+}
+
+/// A snapshot of the state of a container at the moment of inspection.
+///
+/// Obtained with [`ContainerAsync::state`](crate::ContainerAsync::state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerStateSnapshot {
+    status: ContainerStatus,
+    started_at: Option<SystemTime>,
+    finished_at: Option<SystemTime>,
+    exit_code: Option<i64>,
+    oom_killed: bool,
+}
+
+impl ContainerStateSnapshot {
+    /// Returns `None` if the daemon didn't report the status of the container.
+    pub(crate) fn from_inspect(state: InspectState) -> Option<Self> {
+        let status = match state.status? {
+            ContainerStateStatusEnum::CREATED => ContainerStatus::Created,
+            ContainerStateStatusEnum::RUNNING => ContainerStatus::Running,
+            ContainerStateStatusEnum::PAUSED => ContainerStatus::Paused,
+            ContainerStateStatusEnum::RESTARTING => ContainerStatus::Restarting,
+            ContainerStateStatusEnum::REMOVING => ContainerStatus::Removing,
+            ContainerStateStatusEnum::EXITED => ContainerStatus::Exited,
+            ContainerStateStatusEnum::DEAD => ContainerStatus::Dead,
+            ContainerStateStatusEnum::EMPTY => return None,
+        };
+
+        // the exit code is meaningless until the container has actually finished
+        let exit_code = matches!(status, ContainerStatus::Exited | ContainerStatus::Dead)
+            .then_some(state.exit_code)
+            .flatten();
+
+        Some(Self {
+            status,
+            started_at: state.started_at.as_deref().and_then(parse_timestamp),
+            finished_at: state.finished_at.as_deref().and_then(parse_timestamp),
+            exit_code,
+            oom_killed: state.oom_killed.unwrap_or(false),
+        })
+    }
+
+    /// Status of the container.
+    pub fn status(&self) -> ContainerStatus {
+        self.status
+    }
+
+    /// Returns `true` if the container is running, including paused and restarting containers.
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self.status,
+            ContainerStatus::Running | ContainerStatus::Paused | ContainerStatus::Restarting
+        )
+    }
+
+    /// The time the container was last started, `None` if it has never been started.
+    pub fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    /// The time the container last exited, `None` if it has never exited.
+    pub fn finished_at(&self) -> Option<SystemTime> {
+        self.finished_at
+    }
+
+    /// Exit code of the container, available once it has exited.
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    /// Whether the container was killed because it ran out of memory.
+    pub fn oom_killed(&self) -> bool {
+        self.oom_killed
+    }
+
+    /// Time elapsed since the container was last started, if it's running.
+    pub fn uptime(&self) -> Option<Duration> {
+        if !self.is_running() {
+            return None;
+        }
+        self.started_at?.elapsed().ok()
+    }
+}
+
+/// Parses RFC 3339 timestamps reported by the daemon.
+///
+/// Docker uses the zero time (`0001-01-01T00:00:00Z`) for events which didn't happen yet,
+/// it can't be represented as [`SystemTime`] and is mapped to `None` as well as invalid values.
+pub(crate) fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    humantime::parse_rfc3339_weak(value)
+        .ok()
+        .filter(|time| *time > SystemTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_is_parsed_from_inspect_response() {
+        let state = ContainerStateSnapshot::from_inspect(InspectState {
+            status: Some(ContainerStateStatusEnum::EXITED),
+            started_at: Some("2024-06-11T09:08:33.123456789Z".to_string()),
+            finished_at: Some("2024-06-11T09:08:35.5Z".to_string()),
+            exit_code: Some(3),
+            oom_killed: Some(false),
+            ..Default::default()
+        })
+        .expect("status is reported");
+
+        assert_eq!(state.status(), ContainerStatus::Exited);
+        assert!(!state.is_running());
+        assert_eq!(state.exit_code(), Some(3));
+        assert_eq!(state.uptime(), None);
+
+        let started_at = state.started_at().unwrap();
+        let finished_at = state.finished_at().unwrap();
+        assert_eq!(
+            finished_at.duration_since(started_at).unwrap(),
+            Duration::from_nanos(2_376_543_211)
+        );
+    }
+
+    #[test]
+    fn zero_timestamps_and_exit_code_of_running_container_are_ignored() {
+        let state = ContainerStateSnapshot::from_inspect(InspectState {
+            status: Some(ContainerStateStatusEnum::RUNNING),
+            started_at: Some(humantime::format_rfc3339(SystemTime::now()).to_string()),
+            finished_at: Some("0001-01-01T00:00:00Z".to_string()),
+            exit_code: Some(0),
+            ..Default::default()
+        })
+        .expect("status is reported");
+
+        assert_eq!(state.status(), ContainerStatus::Running);
+        assert!(state.is_running());
+        assert_eq!(state.finished_at(), None);
+        assert_eq!(state.exit_code(), None);
+        assert!(state.uptime().is_some());
+
+        assert_eq!(
+            ContainerStateSnapshot::from_inspect(InspectState::default()),
+            None
+        );
+    }
+}
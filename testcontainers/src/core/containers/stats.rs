@@ -0,0 +1,219 @@
+use std::time::{Duration, SystemTime};
+
+use bollard::container::{CPUStats, MemoryStats, MemoryStatsStats, Stats};
+
+use crate::core::containers::state::parse_timestamp;
+
+/// A sample of the resource usage of a container, as reported by the Docker stats endpoint.
+///
+/// Obtained with [`ContainerAsync::stats`](crate::ContainerAsync::stats)
+/// or [`ContainerAsync::stats_stream`](crate::ContainerAsync::stats_stream).
+/// The values are computed the same way as by `docker stats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerStats {
+    read_at: Option<SystemTime>,
+    cpu_percent: Option<f64>,
+    cpu_time: Duration,
+    memory_usage: Option<u64>,
+    memory_limit: Option<u64>,
+    network_rx_bytes: u64,
+    network_tx_bytes: u64,
+    block_read_bytes: u64,
+    block_write_bytes: u64,
+    pids: Option<u64>,
+}
+
+impl ContainerStats {
+    pub(crate) fn from_stats(stats: &Stats) -> Self {
+        let networks = stats.networks.iter().flat_map(|networks| networks.values());
+        let (network_rx_bytes, network_tx_bytes) = networks.fold((0, 0), |(rx, tx), network| {
+            (rx + network.rx_bytes, tx + network.tx_bytes)
+        });
+
+        let block_io = stats
+            .blkio_stats
+            .io_service_bytes_recursive
+            .iter()
+            .flatten();
+        let (block_read_bytes, block_write_bytes) =
+            block_io.fold((0, 0), |(read, write), entry| {
+                match entry.op.to_ascii_lowercase().as_str() {
+                    "read" => (read + entry.value, write),
+                    "write" => (read, write + entry.value),
+                    _ => (read, write),
+                }
+            });
+
+        Self {
+            read_at: parse_timestamp(&stats.read),
+            cpu_percent: cpu_percent(&stats.cpu_stats, &stats.precpu_stats),
+            cpu_time: Duration::from_nanos(stats.cpu_stats.cpu_usage.total_usage),
+            memory_usage: memory_usage(&stats.memory_stats),
+            memory_limit: stats.memory_stats.limit,
+            network_rx_bytes,
+            network_tx_bytes,
+            block_read_bytes,
+            block_write_bytes,
+            pids: stats.pids_stats.current,
+        }
+    }
+
+    /// The time the sample was taken by the daemon.
+    pub fn read_at(&self) -> Option<SystemTime> {
+        self.read_at
+    }
+
+    /// CPU usage since the previous sample, in percents of a single CPU (so it may exceed `100` on multiple CPUs).
+    ///
+    /// `None` if there is no previous sample to compare with.
+    pub fn cpu_percent(&self) -> Option<f64> {
+        self.cpu_percent
+    }
+
+    #[cfg(test)]
+    pub(super) fn with_usage(memory_usage: Option<u64>, cpu_time: Duration) -> Self {
+        Self {
+            read_at: None,
+            cpu_percent: None,
+            cpu_time,
+            memory_usage,
+            memory_limit: None,
+            network_rx_bytes: 0,
+            network_tx_bytes: 0,
+            block_read_bytes: 0,
+            block_write_bytes: 0,
+            pids: None,
+        }
+    }
+
+    /// Total CPU time consumed by the container since its start.
+    pub fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+
+    /// Memory used by the container in bytes, excluding the page cache which can be reclaimed.
+    pub fn memory_usage(&self) -> Option<u64> {
+        self.memory_usage
+    }
+
+    /// Memory limit of the container in bytes, the memory of the host (or the VM) if the container isn't limited.
+    pub fn memory_limit(&self) -> Option<u64> {
+        self.memory_limit
+    }
+
+    /// Bytes received over all the networks of the container.
+    pub fn network_rx_bytes(&self) -> u64 {
+        self.network_rx_bytes
+    }
+
+    /// Bytes sent over all the networks of the container.
+    pub fn network_tx_bytes(&self) -> u64 {
+        self.network_tx_bytes
+    }
+
+    /// Bytes read from block devices.
+    pub fn block_read_bytes(&self) -> u64 {
+        self.block_read_bytes
+    }
+
+    /// Bytes written to block devices.
+    pub fn block_write_bytes(&self) -> u64 {
+        self.block_write_bytes
+    }
+
+    /// Number of processes (and threads) running in the container.
+    pub fn pids(&self) -> Option<u64> {
+        self.pids
+    }
+}
+
+fn cpu_percent(cpu: &CPUStats, precpu: &CPUStats) -> Option<f64> {
+    let cpu_delta = cpu
+        .cpu_usage
+        .total_usage
+        .checked_sub(precpu.cpu_usage.total_usage)?;
+    let system_delta = cpu
+        .system_cpu_usage?
+        .checked_sub(precpu.system_cpu_usage?)
+        .filter(|delta| *delta > 0)?;
+    let online_cpus = cpu.online_cpus.filter(|cpus| *cpus > 0).or_else(|| {
+        let cpus = cpu.cpu_usage.percpu_usage.as_ref()?.len();
+        Some(cpus as u64)
+    })?;
+
+    Some(cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0)
+}
+
+fn memory_usage(memory: &MemoryStats) -> Option<u64> {
+    let usage = memory.usage?;
+    let inactive_file = match memory.stats {
+        Some(MemoryStatsStats::V1(stats)) => stats.total_inactive_file,
+        Some(MemoryStatsStats::V2(stats)) => stats.inactive_file,
+        None => 0,
+    };
+    Some(usage.saturating_sub(inactive_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::container::{CPUUsage, ThrottlingData};
+
+    use super::*;
+
+    fn cpu_stats(total_usage: u64, system_cpu_usage: Option<u64>) -> CPUStats {
+        CPUStats {
+            cpu_usage: CPUUsage {
+                percpu_usage: None,
+                usage_in_usermode: 0,
+                total_usage,
+                usage_in_kernelmode: 0,
+            },
+            system_cpu_usage,
+            online_cpus: Some(4),
+            throttling_data: ThrottlingData {
+                periods: 0,
+                throttled_periods: 0,
+                throttled_time: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn cpu_percent_is_relative_to_previous_sample() {
+        let percent = cpu_percent(
+            &cpu_stats(3_000, Some(20_000)),
+            &cpu_stats(1_000, Some(10_000)),
+        );
+        assert_eq!(percent, Some(80.0));
+
+        // the first sample of a stream has no previous one
+        assert_eq!(
+            cpu_percent(&cpu_stats(3_000, Some(20_000)), &cpu_stats(0, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn memory_usage_excludes_page_cache() {
+        let memory = MemoryStats {
+            stats: None,
+            max_usage: None,
+            usage: Some(4096),
+            failcnt: None,
+            limit: Some(8192),
+            commit: None,
+            commit_peak: None,
+            commitbytes: None,
+            commitpeakbytes: None,
+            privateworkingset: None,
+        };
+        assert_eq!(memory_usage(&memory), Some(4096));
+        assert_eq!(
+            memory_usage(&MemoryStats {
+                usage: None,
+                ..memory
+            }),
+            None
+        );
+    }
+}
@@ -1,8 +1,19 @@
-use std::{fmt, io::BufRead, net::IpAddr, sync::Arc};
+use std::{
+    fmt,
+    io::{BufRead, Read, Write},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
-    core::{env, error::Result, ports::Ports, ContainerPort, ExecCommand},
-    ContainerAsync, Image,
+    core::{
+        env, error::Result, ports::Ports, ContainerPort, ContainerStateSnapshot, ContainerStats,
+        ExecCommand, ExecInfo, ExecOutput, ExecTranscript, FilesystemChange, ImageMetadata,
+        StartupReport, WaitFor,
+    },
+    ContainerAsync, GenericImage, Image,
 };
 
 pub(super) mod exec;
@@ -21,6 +32,13 @@ mod sync_reader;
 /// }
 /// ```
 ///
+/// # Errors
+///
+/// Every operation returns a [`Result`] and doesn't panic on failures of the Docker daemon,
+/// such as a lost connection or a container removed concurrently: they are reported as
+/// [`TestcontainersError`](crate::TestcontainersError), or as [`std::io::Error`] by the log readers.
+/// As with any blocking API built on top of tokio, the operations must not be called from an async context.
+///
 /// [drop_impl]: struct.Container.html#impl-Drop
 pub struct Container<I: Image> {
     inner: Option<ActiveContainer<I>>,
@@ -106,6 +124,16 @@ where
             .block_on(self.async_impl().get_host_port_ipv6(internal_port))
     }
 
+    /// Returns a snapshot of the current state of the container (status, start and finish times, exit code).
+    pub fn state(&self) -> Result<ContainerStateSnapshot> {
+        self.rt().block_on(self.async_impl().state())
+    }
+
+    /// Returns metadata (digest, creation date, size) of the image the container has been created from.
+    pub fn image_metadata(&self) -> Result<ImageMetadata> {
+        self.rt().block_on(self.async_impl().image_metadata())
+    }
+
     /// Returns the bridge ip address of docker container as specified in NetworkSettings.Networks.IPAddress
     pub fn get_bridge_ip_address(&self) -> Result<IpAddr> {
         self.rt()
@@ -118,6 +146,18 @@ where
         self.rt().block_on(self.async_impl().get_host())
     }
 
+    /// Waits until the mapped port accepts TCP connections, retrying with an exponential backoff.
+    ///
+    /// Returns the last connection error if the port doesn't accept connections within the timeout.
+    pub fn wait_for_port_connectable(
+        &self,
+        port: impl Into<ContainerPort>,
+        timeout: Duration,
+    ) -> Result<()> {
+        self.rt()
+            .block_on(self.async_impl().wait_for_port_connectable(port, timeout))
+    }
+
     /// Executes a command in the container.
     pub fn exec(&self, cmd: ExecCommand) -> Result<exec::SyncExecResult> {
         let async_exec = self.rt().block_on(self.async_impl().exec(cmd))?;
@@ -127,16 +167,138 @@ where
         })
     }
 
+    /// Executes a command in the container as the `user`, regardless of the default user of the container.
+    ///
+    /// See [`ContainerAsync::exec_as`] for more details.
+    pub fn exec_as(
+        &self,
+        user: impl Into<String>,
+        cmd: ExecCommand,
+    ) -> Result<exec::SyncExecResult> {
+        self.exec(cmd.with_user(user))
+    }
+
+    /// Executes a command in the container and collects its output once it exits,
+    /// returning an error with the output if it exits with a non-zero code.
+    ///
+    /// See [`ContainerAsync::exec_expecting_success`] for more details.
+    pub fn exec_expecting_success(&self, cmd: ExecCommand) -> Result<ExecOutput> {
+        self.rt()
+            .block_on(self.async_impl().exec_expecting_success(cmd))
+    }
+
+    /// Executes the commands in the container sequentially, stopping at the first one exiting with a non-zero code.
+    ///
+    /// See [`ContainerAsync::exec_all`] for more details.
+    pub fn exec_all(&self, cmds: impl IntoIterator<Item = ExecCommand>) -> Result<ExecTranscript> {
+        self.rt().block_on(self.async_impl().exec_all(cmds))
+    }
+
+    /// Lists the commands executed in the container, including the ones which are still running.
+    pub fn execs(&self) -> Result<Vec<ExecInfo>> {
+        self.rt().block_on(self.async_impl().execs())
+    }
+
+    /// Connects the container to the network, in addition to the networks it's already connected to.
+    ///
+    /// The container is resolvable by the `aliases` within the network.
+    pub fn connect_to_network(
+        &self,
+        network: impl Into<String>,
+        aliases: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<()> {
+        self.rt()
+            .block_on(self.async_impl().connect_to_network(network, aliases))
+    }
+
+    /// Waits for the condition to be met by the running container.
+    ///
+    /// See [`ContainerAsync::wait`](crate::ContainerAsync::wait).
+    pub fn wait(&self, condition: WaitFor) -> Result<()> {
+        self.rt().block_on(self.async_impl().wait(condition))
+    }
+
+    /// Waits for the message to be logged by the running container, from the call on.
+    ///
+    /// See [`ContainerAsync::wait_for_log`](crate::ContainerAsync::wait_for_log).
+    pub fn wait_for_log(&self, message: impl AsRef<[u8]>, timeout: Duration) -> Result<()> {
+        self.rt()
+            .block_on(self.async_impl().wait_for_log(message, timeout))
+    }
+
     /// Stops the container (not the same with `pause`).
     pub fn stop(&self) -> Result<()> {
         self.rt().block_on(self.async_impl().stop())
     }
 
+    /// Creates an image `repository:tag` from the current state of the container.
+    /// See [`ContainerAsync::commit`] for details.
+    pub fn commit(&self, repository: &str, tag: &str) -> Result<GenericImage> {
+        self.rt()
+            .block_on(self.async_impl().commit(repository, tag))
+    }
+
     /// Starts the container.
     pub fn start(&self) -> Result<()> {
         self.rt().block_on(self.async_impl().start())
     }
 
+    /// Returns the warnings reported by the Docker daemon on creation of the container.
+    ///
+    /// See [`ContainerAsync::creation_warnings`] for more details.
+    pub fn creation_warnings(&self) -> &[String] {
+        self.async_impl().creation_warnings()
+    }
+
+    /// Returns the timings of the startup of the container.
+    ///
+    /// See [`ContainerAsync::startup_report`] for more details.
+    pub fn startup_report(&self) -> &StartupReport {
+        self.async_impl().startup_report()
+    }
+
+    /// Returns the path of the cgroup of the container on the host.
+    ///
+    /// See [`ContainerAsync::cgroup_path`] for more details.
+    pub fn cgroup_path(&self) -> Result<PathBuf> {
+        self.rt().block_on(self.async_impl().cgroup_path())
+    }
+
+    /// Returns the paths of the container filesystem added, modified or deleted since its creation.
+    ///
+    /// See [`ContainerAsync::changes`] for more details.
+    pub fn changes(&self) -> Result<Vec<FilesystemChange>> {
+        self.rt().block_on(self.async_impl().changes())
+    }
+
+    /// Returns a sample of the resource usage of the container: CPU, memory, network and block IO.
+    ///
+    /// See [`ContainerAsync::stats`] for more details.
+    pub fn stats(&self) -> Result<ContainerStats> {
+        self.rt().block_on(self.async_impl().stats())
+    }
+
+    /// Returns an error if the container exceeded its resource budget.
+    ///
+    /// See [`ContainerAsync::check_resource_budget`] for more details.
+    pub fn check_resource_budget(&self) -> Result<()> {
+        self.async_impl().check_resource_budget()
+    }
+
+    /// Restarts the container and waits for it to be ready again.
+    ///
+    /// See [`ContainerAsync::restart`] for more details.
+    pub fn restart(&self) -> Result<()> {
+        self.rt().block_on(self.async_impl().restart())
+    }
+
+    /// Sends the signal to the main process of the container, `SIGKILL` if none passed.
+    ///
+    /// See [`ContainerAsync::kill`] for more details.
+    pub fn kill(&self, signal: Option<&str>) -> Result<()> {
+        self.rt().block_on(self.async_impl().kill(signal))
+    }
+
     /// Removes the container.
     pub fn rm(mut self) -> Result<()> {
         if let Some(active) = self.inner.take() {
@@ -145,6 +307,29 @@ where
         Ok(())
     }
 
+    /// Copies the directory `container_path` out of the container into `host_dir`,
+    /// preserving the directory structure and file modes.
+    pub fn copy_dir_from(
+        &self,
+        container_path: impl Into<String>,
+        host_dir: impl AsRef<Path>,
+    ) -> Result<()> {
+        self.rt()
+            .block_on(self.async_impl().copy_dir_from(container_path, host_dir))
+    }
+
+    /// Returns a reader for the contents of the file `container_path`,
+    /// streamed out of the container as it's read.
+    pub fn open_file(&self, container_path: impl Into<String>) -> Result<Box<dyn Read + Send>> {
+        let reader = self
+            .rt()
+            .block_on(self.async_impl().open_file(container_path))?;
+        Ok(Box::new(sync_reader::SyncReadBridge::new(
+            reader,
+            self.rt().clone(),
+        )))
+    }
+
     /// Returns a reader for stdout.
     ///
     /// Accepts a boolean parameter to follow the logs:
@@ -169,6 +354,17 @@ where
         ))
     }
 
+    /// Attaches to the stdin of the container and returns a writer for it.
+    ///
+    /// The container must be started with an open stdin, see [`ImageExt::with_open_stdin`](crate::ImageExt::with_open_stdin).
+    pub fn attach_stdin(&self) -> Result<Box<dyn Write + Send>> {
+        let stdin = self.rt().block_on(self.async_impl().attach_stdin())?;
+        Ok(Box::new(sync_reader::SyncWriteBridge::new(
+            stdin,
+            self.rt().clone(),
+        )))
+    }
+
     /// Returns stdout as a vector of bytes available at the moment of call (from container startup to present).
     ///
     /// If you want to read stdout in chunks, use [`Container::stdout`] instead.
@@ -218,7 +414,7 @@ impl<I: Image> Drop for Container<I> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{core::WaitFor, runners::SyncRunner, GenericImage};
+    use crate::{runners::SyncRunner, GenericImage};
 
     #[derive(Debug, Default)]
     pub struct HelloWorld;
@@ -1,9 +1,11 @@
 use std::{
-    io::{BufRead, Read},
+    io::{BufRead, Read, Write},
     sync::Arc,
 };
 
-use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
 
 /// Allows to use [`tokio::io::AsyncRead`] synchronously as [`std::io::Read`].
 /// In fact, it's almost the same as [`tokio_util::io::SyncIoBridge`], but utilizes [`tokio::runtime::Runtime`] instead of [`tokio::runtime::Handle`].
@@ -65,3 +67,33 @@ impl<T: AsyncRead + Unpin> Read for SyncReadBridge<T> {
         Ok(())
     }
 }
+
+/// Allows to use [`tokio::io::AsyncWrite`] synchronously as [`std::io::Write`].
+/// The counterpart of [`SyncReadBridge`], driving the IO with the same [`tokio::runtime::Runtime`].
+pub(super) struct SyncWriteBridge<T> {
+    inner: T,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl<T: Unpin> SyncWriteBridge<T> {
+    pub fn new(inner: T, runtime: Arc<tokio::runtime::Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+}
+
+impl<T: AsyncWrite + Unpin> Write for SyncWriteBridge<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let inner = &mut self.inner;
+        self.runtime.block_on(AsyncWriteExt::write(inner, buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let inner = &mut self.inner;
+        self.runtime.block_on(AsyncWriteExt::flush(inner))
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        let inner = &mut self.inner;
+        self.runtime.block_on(AsyncWriteExt::write_all(inner, buf))
+    }
+}
@@ -1,15 +1,20 @@
 use std::{
     io,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
+    pin::Pin,
 };
 
-#[derive(Debug, Clone)]
+use tokio::io::AsyncRead;
+use tokio_stream::StreamExt;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CopyToContainer {
     target: String,
     source: CopyDataSource,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CopyDataSource {
     File(PathBuf),
     Data(Vec<u8>),
@@ -23,6 +28,18 @@ pub enum CopyToContainerError {
     PathNameError(String),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum CopyFromContainerError {
+    #[error("io failed with error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("'{0}' is not a directory")]
+    NotADirectory(String),
+    #[error("'{0}' is not a regular file")]
+    NotAFile(String),
+    #[error("archive contains an unsafe path: {0}")]
+    UnsafePath(PathBuf),
+}
+
 impl CopyToContainer {
     pub fn new(source: impl Into<CopyDataSource>, target: impl Into<String>) -> Self {
         Self {
@@ -123,6 +140,61 @@ async fn tar_bytes(data: &Vec<u8>, target_path: &str) -> Result<Vec<u8>, CopyToC
     Ok(res)
 }
 
+/// Extracts the archive of the directory `source_path` (as returned by the Docker API) into `target_dir`,
+/// preserving the directory structure and file modes.
+pub(crate) async fn untar_dir(
+    tar: impl AsyncRead + Unpin + Send,
+    source_path: &str,
+    target_dir: &Path,
+) -> Result<(), CopyFromContainerError> {
+    let mut archive = tokio_tar::Archive::new(tar);
+    let mut entries = archive.entries()?;
+
+    let mut is_root = true;
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        if is_root && !entry.header().entry_type().is_dir() {
+            return Err(CopyFromContainerError::NotADirectory(
+                source_path.to_string(),
+            ));
+        }
+        is_root = false;
+
+        // entries are prefixed with the name of the copied directory itself
+        let path = entry.path()?.into_owned();
+        let relative_path: PathBuf = path.components().skip(1).collect();
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(CopyFromContainerError::UnsafePath(path));
+        }
+
+        let target_path = target_dir.join(relative_path);
+        if let Some(parent) = target_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        entry.unpack(&target_path).await?;
+    }
+    Ok(())
+}
+
+/// Returns the contents of the single file in the archive of `source_path` (as returned by the Docker API),
+/// read as the archive is.
+pub(crate) async fn untar_file(
+    tar: impl AsyncRead + Unpin + Send + 'static,
+    source_path: &str,
+) -> Result<Pin<Box<dyn AsyncRead + Send>>, CopyFromContainerError> {
+    let mut archive = tokio_tar::Archive::new(tar);
+    let mut entries = archive.entries()?;
+
+    let entry = entries.next().await.transpose()?;
+    match entry {
+        Some(entry) if entry.header().entry_type().is_file() => Ok(Box::pin(entry)),
+        _ => Err(CopyFromContainerError::NotAFile(source_path.to_string())),
+    }
+}
+
 fn make_path_relative(path: &str) -> String {
     // TODO support also absolute windows paths like "C:\temp\foo.txt"
     if path.starts_with("/") {
@@ -18,6 +18,18 @@ impl GetEnvValue for Os {
     }
 }
 
+/// Points `DOCKER_CONFIG` to a missing directory, so that fake environments don't depend
+/// on the Docker config of the user (e.g. a context or credentials in `~/.docker`).
+#[cfg(test)]
+pub(crate) fn without_docker_config(key: &str) -> Option<String> {
+    (key == "DOCKER_CONFIG").then(|| {
+        std::env::temp_dir()
+            .join("testcontainers-missing-docker-config")
+            .display()
+            .to_string()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,7 +41,116 @@ mod tests {
         fn get_env_value(key: &str) -> Option<String> {
             match key {
                 "TESTCONTAINERS_COMMAND" => Some("keep".to_owned()),
-                _ => None,
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvCustomLabels;
+
+    impl GetEnvValue for FakeEnvCustomLabels {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_LABELS_NAMESPACE" => Some("com.example.tc.".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvNoLabels;
+
+    impl GetEnvValue for FakeEnvNoLabels {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_LABELS_NAMESPACE" => Some("none".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvImageAllowlist;
+
+    impl GetEnvValue for FakeEnvImageAllowlist {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_IMAGE_ALLOWLIST" => Some(" ghcr.io/acme/, redis:,".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvImplicitHealthcheck;
+
+    impl GetEnvValue for FakeEnvImplicitHealthcheck {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_IMPLICIT_HEALTHCHECK_WAIT" => Some(" true".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvHostOverride;
+
+    impl GetEnvValue for FakeEnvHostOverride {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_HOST_OVERRIDE" => Some(" host.docker.internal ".to_owned()),
+                "TESTCONTAINERS_GATEWAY_NETWORK" => Some("ci-gateway".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvBlankHostOverride;
+
+    impl GetEnvValue for FakeEnvBlankHostOverride {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_HOST_OVERRIDE" | "TESTCONTAINERS_GATEWAY_NETWORK" => {
+                    Some("  ".to_owned())
+                }
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvPodman;
+
+    impl GetEnvValue for FakeEnvPodman {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_RUNTIME" => Some("podman".to_owned()),
+                "DOCKER_HOST" => Some("tcp://podman-machine:2375".to_owned()),
+                _ => without_docker_config(key),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeEnvDockerContext;
+
+    // set by the test owning the directory, as the environment is read through an associated function
+    static DOCKER_CONTEXT_CONFIG_DIR: std::sync::Mutex<Option<std::path::PathBuf>> =
+        std::sync::Mutex::new(None);
+
+    impl GetEnvValue for FakeEnvDockerContext {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "DOCKER_CONFIG" => DOCKER_CONTEXT_CONFIG_DIR
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|dir| dir.display().to_string()),
+                "DOCKER_CONTEXT" => Some("colima".to_owned()),
+                _ => without_docker_config(key),
             }
         }
     }
@@ -47,6 +168,89 @@ mod tests {
         assert!(matches!(cmd.parse::<Command>(), Ok(Command::Keep)),)
     }
 
+    #[tokio::test]
+    async fn labels_namespace_can_be_renamed_or_disabled() {
+        let default = Config::load::<FakeEnvAlwaysKeep>().await.unwrap();
+        assert_eq!(default.labels_namespace(), Some("org.testcontainers"));
+
+        let custom = Config::load::<FakeEnvCustomLabels>().await.unwrap();
+        assert_eq!(custom.labels_namespace(), Some("com.example.tc"));
+
+        let disabled = Config::load::<FakeEnvNoLabels>().await.unwrap();
+        assert_eq!(disabled.labels_namespace(), None);
+    }
+
+    #[tokio::test]
+    async fn image_allowlist_is_read_from_env() {
+        let default = Config::load::<FakeEnvAlwaysKeep>().await.unwrap();
+        assert_eq!(default.image_allowlist(), None);
+
+        let config = Config::load::<FakeEnvImageAllowlist>().await.unwrap();
+        assert_eq!(
+            config.image_allowlist(),
+            Some(&["ghcr.io/acme/".to_string(), "redis:".to_string()][..])
+        );
+    }
+
+    #[tokio::test]
+    async fn implicit_healthcheck_wait_is_read_from_env() {
+        let default = Config::load::<FakeEnvAlwaysKeep>().await.unwrap();
+        assert!(!default.implicit_healthcheck_wait());
+
+        let config = Config::load::<FakeEnvImplicitHealthcheck>().await.unwrap();
+        assert!(config.implicit_healthcheck_wait());
+    }
+
+    #[tokio::test]
+    async fn host_override_and_gateway_network_are_read_from_env() {
+        let config = Config::load::<FakeEnvHostOverride>().await.unwrap();
+        assert_eq!(config.host_override(), Some("host.docker.internal"));
+        assert_eq!(config.gateway_network(), "ci-gateway");
+
+        let blank = Config::load::<FakeEnvBlankHostOverride>().await.unwrap();
+        assert_eq!(blank.host_override(), None);
+        assert_eq!(blank.gateway_network(), "bridge");
+    }
+
+    #[tokio::test]
+    async fn podman_runtime_uses_its_default_network() {
+        let docker = Config::load::<FakeEnvAlwaysKeep>().await.unwrap();
+        assert_eq!(docker.gateway_network(), "bridge");
+
+        let podman = Config::load::<FakeEnvPodman>().await.unwrap();
+        assert_eq!(podman.docker_host(), "tcp://podman-machine:2375");
+        assert_eq!(podman.gateway_network(), "podman");
+    }
+
+    #[tokio::test]
+    async fn docker_host_is_read_from_docker_context() {
+        let config_dir = temp_dir::TempDir::new().unwrap();
+        *DOCKER_CONTEXT_CONFIG_DIR.lock().unwrap() = Some(config_dir.path().to_path_buf());
+        let meta_dir = config_dir.path().join("contexts/meta/0123abcd");
+        std::fs::create_dir_all(&meta_dir).unwrap();
+        std::fs::write(
+            meta_dir.join("meta.json"),
+            r#"{"Name":"colima","Metadata":{},"Endpoints":{"docker":{"Host":"unix:///home/user/.colima/default/docker.sock","SkipTLSVerify":false}}}"#,
+        )
+        .unwrap();
+
+        let config = Config::load::<FakeEnvDockerContext>().await.unwrap();
+        assert_eq!(
+            config.docker_host(),
+            "unix:///home/user/.colima/default/docker.sock"
+        );
+    }
+
+    #[tokio::test]
+    async fn docker_host_can_be_overridden() {
+        let config = Config::load::<FakeEnvAlwaysKeep>()
+            .await
+            .unwrap()
+            .with_docker_host("tcp://other-daemon:2375");
+
+        assert_eq!(config.docker_host(), "tcp://other-daemon:2375");
+    }
+
     #[test]
     fn default_command_is_remove() {
         let cmd = Command::default();
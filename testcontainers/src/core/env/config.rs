@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::HashMap,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -14,6 +15,10 @@ pub enum ConfigurationError {
     InvalidDockerHost(String),
     #[error("unknown command '{0}' provided via TESTCONTAINERS_COMMAND env variable")]
     UnknownCommand(String),
+    #[error("unknown runtime '{0}' provided via TESTCONTAINERS_RUNTIME env variable")]
+    UnknownRuntime(String),
+    #[error("invalid TESTCONTAINERS_HOST_OVERRIDE: {0}")]
+    InvalidHostOverride(String),
     #[cfg(feature = "properties-config")]
     #[error("failed to load testcontainers properties: {0}")]
     WrongPropertiesFormat(#[from] serde_java_properties::de::Error),
@@ -22,6 +27,16 @@ pub enum ConfigurationError {
 /// The default path to the Docker configuration file.
 const DEFAULT_DOCKER_CONFIG_PATH: &str = ".docker";
 const DOCKER_CONFIG_FILE: &str = "config.json";
+/// The directory with the metadata of Docker contexts, relative to the Docker configuration directory.
+const DOCKER_CONTEXTS_META_PATH: &str = "contexts/meta";
+
+/// The network whose gateway is used to reach containers, when running inside a container.
+const DEFAULT_GATEWAY_NETWORK: &str = "bridge";
+/// The name of the default network of Podman, it's not called `bridge` there.
+const PODMAN_GATEWAY_NETWORK: &str = "podman";
+
+/// The default namespace of the labels applied to containers created by testcontainers.
+const DEFAULT_LABELS_NAMESPACE: &str = "org.testcontainers";
 
 #[cfg(feature = "properties-config")]
 const TESTCONTAINERS_PROPERTIES: &str = ".testcontainers.properties";
@@ -38,10 +53,17 @@ pub const DEFAULT_DOCKER_HOST: &str = "npipe:////./pipe/docker_engine";
 pub(crate) struct Config {
     tc_host: Option<String>,
     host: Option<String>,
+    context_host: Option<String>,
     tls_verify: Option<bool>,
     cert_path: Option<PathBuf>,
     command: Option<Command>,
     docker_auth_config: Option<String>,
+    labels_namespace: Option<LabelsNamespace>,
+    host_override: Option<String>,
+    gateway_network: Option<String>,
+    runtime: Option<Runtime>,
+    implicit_healthcheck_wait: bool,
+    image_allowlist: Option<Vec<String>>,
 }
 
 #[cfg(feature = "properties-config")]
@@ -90,10 +112,17 @@ impl Config {
             Ok(Self {
                 tc_host: env_config.tc_host.or(properties.tc_host),
                 host: env_config.host.or(properties.host),
+                context_host: env_config.context_host,
                 tls_verify: env_config.tls_verify.or(properties.tls_verify),
                 cert_path: env_config.cert_path.or(properties.cert_path),
                 command: env_config.command,
                 docker_auth_config: env_config.docker_auth_config,
+                labels_namespace: env_config.labels_namespace,
+                host_override: env_config.host_override,
+                gateway_network: env_config.gateway_network,
+                runtime: env_config.runtime,
+                implicit_healthcheck_wait: env_config.implicit_healthcheck_wait,
+                image_allowlist: env_config.image_allowlist,
             })
         }
         #[cfg(not(feature = "properties-config"))]
@@ -111,37 +140,91 @@ impl Config {
             .filter(|v| !v.trim().is_empty())
             .map(|v| v.parse())
             .transpose()?;
+        let labels_namespace = E::get_env_value("TESTCONTAINERS_LABELS_NAMESPACE")
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| LabelsNamespace::from(v.trim()));
+        let host_override = E::get_env_value("TESTCONTAINERS_HOST_OVERRIDE")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        let gateway_network = E::get_env_value("TESTCONTAINERS_GATEWAY_NETWORK")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+
+        let runtime = E::get_env_value("TESTCONTAINERS_RUNTIME")
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.trim().parse())
+            .transpose()?;
+
+        let implicit_healthcheck_wait =
+            E::get_env_value("TESTCONTAINERS_IMPLICIT_HEALTHCHECK_WAIT")
+                .is_some_and(|v| matches!(v.trim(), "1" | "true"));
+
+        let image_allowlist = E::get_env_value("TESTCONTAINERS_IMAGE_ALLOWLIST")
+            .map(|v| {
+                v.split(',')
+                    .map(|prefix| prefix.trim().to_string())
+                    .filter(|prefix| !prefix.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|prefixes| !prefixes.is_empty());
 
         let docker_auth_config = read_docker_auth_config::<E>().await;
+        let context_host = read_docker_context_host::<E>().await;
 
         Ok(Config {
             host,
             tc_host: None,
+            context_host,
             command,
             tls_verify,
             cert_path,
             docker_auth_config,
+            labels_namespace,
+            host_override,
+            gateway_network,
+            runtime,
+            implicit_healthcheck_wait,
+            image_allowlist,
         })
     }
 
+    /// Overrides the Docker host, taking precedence over any other configured host.
+    pub(crate) fn with_docker_host(self, docker_host: impl Into<String>) -> Self {
+        Self {
+            tc_host: Some(docker_host.into()),
+            ..self
+        }
+    }
+
     /// The Docker host to use. The host is resolved in the following order:
     ///  1. Docker host from the `tc.host` property in the `~/.testcontainers.properties` file.
     ///  2. `DOCKER_HOST` environment variable.
     ///  3. Docker host from the `docker.host` property in the `~/.testcontainers.properties` file.
-    ///  4. Read the default Docker socket path, without the unix schema. E.g. `/var/run/docker.sock`.
-    ///  5. Read the rootless Docker socket path, checking in the following alternative locations:
+    ///  4. Endpoint of the Docker context selected by `DOCKER_CONTEXT` or by `docker context use`.
+    ///  5. Read the default Docker socket path, without the unix schema. E.g. `/var/run/docker.sock`.
+    ///  6. Read the rootless Docker socket path, checking in the following alternative locations:
     ///     1. `${XDG_RUNTIME_DIR}/.docker/run/docker.sock`.
     ///     2. `${HOME}/.docker/run/docker.sock`.
     ///     3. `${HOME}/.docker/desktop/docker.sock`.
-    ///  6. The default Docker socket including schema will be returned if none of the above are set.
+    ///  7. Read the Podman socket path, checking in the following locations:
+    ///     1. `${XDG_RUNTIME_DIR}/podman/podman.sock` (rootless Podman).
+    ///     2. `/run/podman/podman.sock`.
+    ///  8. The default Docker socket including schema will be returned if none of the above are set.
+    ///
+    /// The Podman sockets are checked first if `TESTCONTAINERS_RUNTIME=podman` is set.
     pub(crate) fn docker_host(&self) -> Cow<'_, str> {
         self.tc_host
             .as_deref()
             .or(self.host.as_deref())
+            .or(self.context_host.as_deref())
             .map(Cow::Borrowed)
             .unwrap_or_else(|| {
                 if cfg!(unix) {
-                    validate_path("/var/run/docker.sock".into())
+                    let podman_first = self.runtime == Some(Runtime::Podman);
+                    podman_first
+                        .then(podman_socket)
+                        .flatten()
+                        .or_else(|| validate_path("/var/run/docker.sock".into()))
                         .or_else(|| {
                             runtime_dir().and_then(|dir| {
                                 validate_path(format!("{}/.docker/run/docker.sock", dir.display()))
@@ -160,6 +243,7 @@ impl Config {
                                 ))
                             })
                         })
+                        .or_else(|| (!podman_first).then(podman_socket).flatten())
                         .map(|p| format!("unix://{p}"))
                         .map(Cow::Owned)
                         .unwrap_or(DEFAULT_DOCKER_HOST.into())
@@ -173,8 +257,12 @@ impl Config {
         self.tls_verify.unwrap_or_default()
     }
 
-    pub(crate) fn cert_path(&self) -> Option<&Path> {
-        self.cert_path.as_deref()
+    /// The directory with the TLS certificates (`ca.pem`, `cert.pem` and `key.pem`),
+    /// `~/.docker` unless set by `DOCKER_CERT_PATH`, the same as for the Docker CLI.
+    pub(crate) fn cert_path(&self) -> Option<PathBuf> {
+        self.cert_path
+            .clone()
+            .or_else(|| home_dir().map(|dir| dir.join(DEFAULT_DOCKER_CONFIG_PATH)))
     }
 
     pub(crate) fn command(&self) -> Command {
@@ -184,6 +272,55 @@ impl Config {
     pub(crate) fn docker_auth_config(&self) -> Option<&str> {
         self.docker_auth_config.as_deref()
     }
+
+    /// The host containers are reached on, overriding the one derived from the Docker host.
+    pub(crate) fn host_override(&self) -> Option<&str> {
+        self.host_override.as_deref()
+    }
+
+    /// The network whose gateway is used to reach containers, when running inside a container.
+    pub(crate) fn gateway_network(&self) -> &str {
+        self.gateway_network
+            .as_deref()
+            .unwrap_or(match self.runtime() {
+                Runtime::Docker => DEFAULT_GATEWAY_NETWORK,
+                Runtime::Podman => PODMAN_GATEWAY_NETWORK,
+            })
+    }
+
+    /// Whether to wait for the healthcheck defined by the image (`HEALTHCHECK`),
+    /// if a container has no ready conditions.
+    pub(crate) fn implicit_healthcheck_wait(&self) -> bool {
+        self.implicit_healthcheck_wait
+    }
+
+    /// The prefixes of the allowed image descriptors, set by `TESTCONTAINERS_IMAGE_ALLOWLIST`
+    /// (comma-separated), `None` if every image is allowed.
+    pub(crate) fn image_allowlist(&self) -> Option<&[String]> {
+        self.image_allowlist.as_deref()
+    }
+
+    /// The container runtime behind the Docker host, either set by `TESTCONTAINERS_RUNTIME`
+    /// or detected from the path of the socket.
+    pub(crate) fn runtime(&self) -> Runtime {
+        self.runtime.unwrap_or_else(|| {
+            if self.docker_host().contains("podman") {
+                Runtime::Podman
+            } else {
+                Runtime::Docker
+            }
+        })
+    }
+
+    /// The namespace of the labels automatically applied to containers (e.g. `org.testcontainers.managed-by`),
+    /// `None` if the automatic labels are disabled.
+    pub(crate) fn labels_namespace(&self) -> Option<&str> {
+        match &self.labels_namespace {
+            None => Some(DEFAULT_LABELS_NAMESPACE),
+            Some(LabelsNamespace::Custom(namespace)) => Some(namespace),
+            Some(LabelsNamespace::Disabled) => None,
+        }
+    }
 }
 
 /// Validate the path exists and return it if it does.
@@ -195,6 +332,13 @@ fn validate_path(path: String) -> Option<String> {
     }
 }
 
+/// Returns the path of the Podman socket, preferring the rootless one.
+fn podman_socket() -> Option<String> {
+    runtime_dir()
+        .and_then(|dir| validate_path(format!("{}/podman/podman.sock", dir.display())))
+        .or_else(|| validate_path("/run/podman/podman.sock".into()))
+}
+
 fn home_dir() -> Option<PathBuf> {
     etcetera::home_dir().ok()
 }
@@ -215,19 +359,89 @@ where
     match E::get_env_value("DOCKER_AUTH_CONFIG") {
         Some(cfg) => Some(cfg),
         None => {
-            let mut path_to_config = match E::get_env_value("DOCKER_CONFIG").map(PathBuf::from) {
-                Some(path_to_config) => path_to_config,
-                None => {
-                    let home_dir = home_dir()?;
-                    home_dir.join(DEFAULT_DOCKER_CONFIG_PATH)
-                }
-            };
-            path_to_config.push(DOCKER_CONFIG_FILE);
+            let path_to_config = docker_config_dir::<E>()?.join(DOCKER_CONFIG_FILE);
             tokio::fs::read_to_string(path_to_config).await.ok()
         }
     }
 }
 
+fn docker_config_dir<E>() -> Option<PathBuf>
+where
+    E: GetEnvValue,
+{
+    E::get_env_value("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|dir| dir.join(DEFAULT_DOCKER_CONFIG_PATH)))
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DockerCliConfig {
+    #[serde(rename = "currentContext")]
+    current_context: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DockerContextMeta {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Endpoints", default)]
+    endpoints: HashMap<String, DockerContextEndpoint>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct DockerContextEndpoint {
+    #[serde(rename = "Host")]
+    host: Option<String>,
+}
+
+/// Read the Docker host of the current Docker context, the same way as the Docker CLI does:
+///
+/// 1. The context is selected by the `DOCKER_CONTEXT` environment variable,
+///    else by the `currentContext` field of the Docker `config.json` file (set by `docker context use`).
+/// 2. The endpoint is read from the context metadata, stored in `contexts/meta/<digest>/meta.json`
+///    within the Docker configuration directory.
+///
+/// The `default` context means no context at all, leaving the resolution to the default sockets.
+async fn read_docker_context_host<E>() -> Option<String>
+where
+    E: GetEnvValue,
+{
+    let config_dir = docker_config_dir::<E>()?;
+    let context = match E::get_env_value("DOCKER_CONTEXT").filter(|v| !v.trim().is_empty()) {
+        Some(context) => context.trim().to_string(),
+        None => {
+            let content = tokio::fs::read(config_dir.join(DOCKER_CONFIG_FILE))
+                .await
+                .ok()?;
+            serde_json::from_slice::<DockerCliConfig>(&content)
+                .ok()?
+                .current_context?
+        }
+    };
+    if context.is_empty() || context == "default" {
+        return None;
+    }
+
+    // the metadata directories are named by the digest of the context name, look them up by the name instead
+    let mut entries = tokio::fs::read_dir(config_dir.join(DOCKER_CONTEXTS_META_PATH))
+        .await
+        .ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(content) = tokio::fs::read(entry.path().join("meta.json")).await else {
+            continue;
+        };
+        let Ok(mut meta) = serde_json::from_slice::<DockerContextMeta>(&content) else {
+            continue;
+        };
+        if meta.name == context {
+            return meta.endpoints.remove("docker")?.host;
+        }
+    }
+
+    log::warn!("Docker context '{context}' is not found, ignoring it");
+    None
+}
+
 /// The commands available to the `TESTCONTAINERS_COMMAND` env variable.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub(crate) enum Command {
@@ -248,6 +462,44 @@ impl FromStr for Command {
     }
 }
 
+/// The container runtimes available to the `TESTCONTAINERS_RUNTIME` env variable.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Runtime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl FromStr for Runtime {
+    type Err = ConfigurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "docker" => Ok(Runtime::Docker),
+            "podman" => Ok(Runtime::Podman),
+            other => Err(ConfigurationError::UnknownRuntime(other.to_string())),
+        }
+    }
+}
+
+/// The values of the `TESTCONTAINERS_LABELS_NAMESPACE` env variable.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum LabelsNamespace {
+    /// Automatic labels are not applied at all (`none`).
+    Disabled,
+    /// Automatic labels are applied within the custom namespace.
+    Custom(String),
+}
+
+impl From<&str> for LabelsNamespace {
+    fn from(value: &str) -> Self {
+        match value {
+            "none" => LabelsNamespace::Disabled,
+            namespace => LabelsNamespace::Custom(namespace.trim_end_matches('.').to_string()),
+        }
+    }
+}
+
 #[cfg(feature = "properties-config")]
 #[cfg(test)]
 mod tests {
@@ -277,4 +529,25 @@ mod tests {
         assert_eq!(properties.tls_verify, Some(tls_verify == 1));
         assert_eq!(properties.cert_path, Some(PathBuf::from(cert_path)));
     }
+
+    #[derive(Debug)]
+    struct FakeEnvWithoutProperties;
+
+    impl GetEnvValue for FakeEnvWithoutProperties {
+        fn get_env_value(key: &str) -> Option<String> {
+            match key {
+                "TESTCONTAINERS_IMAGE_ALLOWLIST" => Some("redis:".to_owned()),
+                "TESTCONTAINERS_IMPLICIT_HEALTHCHECK_WAIT" => Some("1".to_owned()),
+                _ => crate::core::env::without_docker_config(key),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn env_only_settings_are_kept_along_with_properties() {
+        let config = Config::load::<FakeEnvWithoutProperties>().await.unwrap();
+
+        assert_eq!(config.image_allowlist(), Some(&["redis:".to_string()][..]));
+        assert!(config.implicit_healthcheck_wait());
+    }
 }
@@ -1,7 +1,7 @@
 use std::error::Error;
 
-use crate::core::logs::WaitLogError;
 pub use crate::core::{client::ClientError, env::ConfigurationError, ContainerPort};
+use crate::core::{logs::WaitLogError, BudgetViolation, ExecOutput};
 
 pub type Result<T> = std::result::Result<T, TestcontainersError>;
 
@@ -19,6 +19,16 @@ pub enum TestcontainersError {
     /// Represents an error when a container is missing some information
     #[error(transparent)]
     MissingInfo(#[from] ContainerMissingInfo),
+    /// Represents an error when the resource usage of a container exceeds its budget
+    #[error("container '{id}' exceeded its resource budget: {violation}")]
+    ResourceBudgetExceeded {
+        id: String,
+        violation: BudgetViolation,
+    },
+    /// Represents an error when an image is denied by an image policy,
+    /// see [`set_image_policy`](crate::set_image_policy)
+    #[error("image '{descriptor}' is not allowed: {reason}")]
+    ImagePolicyViolation { descriptor: String, reason: String },
     /// Represents an error when an exec operation fails
     #[error("exec operation failed: {0}")]
     Exec(#[from] ExecError),
@@ -45,6 +55,30 @@ pub enum ExecError {
     ExitCodeMismatch { expected: i64, actual: i64 },
     #[error("failed to wait for exec log: {0}")]
     WaitLog(#[from] WaitLogError),
+    #[error(transparent)]
+    AfterStart(Box<AfterStartExecError>),
+    #[error(
+        "command {:?} exited with code {}\nstdout: {}\nstderr: {}",
+        .0.cmd(),
+        .0.exit_code(),
+        String::from_utf8_lossy(.0.stdout()),
+        String::from_utf8_lossy(.0.stderr())
+    )]
+    Failed(Box<ExecOutput>),
+}
+
+/// Failure of a command executed after start, along with its output if it has exited.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "command {cmd:?} executed after start failed\nexit code: {}\nstdout: {stdout}\nstderr: {stderr}",
+    exit_code.map_or_else(|| "unknown".to_string(), |code| code.to_string())
+)]
+pub struct AfterStartExecError {
+    cmd: Vec<String>,
+    exit_code: Option<i64>,
+    stdout: String,
+    stderr: String,
+    source: TestcontainersError,
 }
 
 /// Error type for waiting for container readiness based on [`crate::core::WaitFor`] conditions.
@@ -58,12 +92,20 @@ pub enum WaitContainerError {
     #[cfg(feature = "http_wait")]
     #[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
     HttpWait(#[from] crate::core::wait::http_strategy::HttpWaitError),
+    #[error("container is not ready: {0}")]
+    #[cfg(feature = "grpc_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+    GrpcWait(#[from] crate::core::wait::grpc_strategy::GrpcWaitError),
     #[error("healthcheck is not configured for container: {0}")]
     HealthCheckNotConfigured(String),
     #[error("container is unhealthy")]
     Unhealthy,
     #[error("container startup timeout")]
     StartupTimeout,
+    #[error("container startup timeout, diagnostics collected before the timeout:\n{0}")]
+    StartupTimeoutWithDiagnostics(String),
+    #[error("wait condition is not met within {0:?}")]
+    Timeout(std::time::Duration),
     #[error("container exited with unexpected code: expected {expected}, actual {actual:?}")]
     UnexpectedExitCode { expected: i64, actual: Option<i64> },
 }
@@ -84,6 +126,47 @@ impl TestcontainersError {
     }
 }
 
+impl AfterStartExecError {
+    pub(crate) fn new(
+        cmd: Vec<String>,
+        output: Option<&ExecOutput>,
+        source: TestcontainersError,
+    ) -> Self {
+        let lossy = |bytes: &[u8]| String::from_utf8_lossy(bytes).into_owned();
+        Self {
+            cmd,
+            exit_code: output.map(ExecOutput::exit_code),
+            stdout: output
+                .map(|output| lossy(output.stdout()))
+                .unwrap_or_default(),
+            stderr: output
+                .map(|output| lossy(output.stderr()))
+                .unwrap_or_default(),
+            source,
+        }
+    }
+
+    /// The failed command.
+    pub fn cmd(&self) -> &[String] {
+        &self.cmd
+    }
+
+    /// Exit code of the command, unknown if it hasn't exited.
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    /// Standard output of the command, empty if it hasn't exited.
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    /// Standard error of the command, empty if it hasn't exited.
+    pub fn stderr(&self) -> &str {
+        &self.stderr
+    }
+}
+
 impl ContainerMissingInfo {
     pub(crate) fn new(id: impl Into<String>, path: impl Into<String>) -> Self {
         Self {
@@ -1,9 +1,12 @@
-use std::{borrow::Cow, fmt::Debug};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, net::IpAddr};
 
-pub use exec::ExecCommand;
+pub use exec::{ExecCommand, ExecFailurePolicy};
+pub use host_config::HostConfigHints;
 pub use image_ext::ImageExt;
 #[cfg(feature = "reusable-containers")]
 pub use image_ext::ReuseDirective;
+pub use metadata::ImageMetadata;
+pub use policy::{set_image_policy, ImageDecision};
 
 use crate::{
     core::{
@@ -16,7 +19,10 @@ use crate::{
 };
 
 mod exec;
+mod host_config;
 mod image_ext;
+mod metadata;
+pub(crate) mod policy;
 
 /// Represents a docker image.
 ///
@@ -83,6 +89,14 @@ where
         &[]
     }
 
+    /// Returns the host configuration the image needs to work properly, e.g. a higher `nofile` ulimit.
+    ///
+    /// The settings of the [`ContainerRequest`](crate::ContainerRequest) take precedence over the hints,
+    /// see [`HostConfigHints`].
+    fn host_config_hints(&self) -> HostConfigHints {
+        HostConfigHints::default()
+    }
+
     /// Returns the commands that needs to be executed after a container is started i.e. commands
     /// to be run in a running container.
     ///
@@ -91,6 +105,7 @@ where
     ///
     /// This method is useful when certain re-configuration is required after the start
     /// of container for the container to be considered ready for use in tests.
+    /// A failed command fails the startup, unless configured otherwise with [`ExecCommand::with_failure_policy`].
     #[allow(unused_variables)]
     fn exec_after_start(
         &self,
@@ -100,10 +115,13 @@ where
     }
 }
 
+/// Information about a started container, passed to [`Image::exec_after_start`].
 #[derive(Debug)]
 pub struct ContainerState {
     id: String,
     ports: Ports,
+    host: url::Host,
+    ip_addresses: HashMap<String, IpAddr>,
 }
 
 impl ContainerState {
@@ -111,9 +129,45 @@ impl ContainerState {
         Self {
             id: id.into(),
             ports,
+            host: url::Host::Domain("localhost".to_string()),
+            ip_addresses: HashMap::new(),
         }
     }
 
+    /// Sets the host the container may be reached on, `localhost` by default.
+    pub fn with_host(mut self, host: url::Host) -> Self {
+        self.host = host;
+        self
+    }
+
+    /// Sets the IP address of the container in the given network.
+    pub fn with_ip_address(mut self, network: impl Into<String>, ip: IpAddr) -> Self {
+        self.ip_addresses.insert(network.into(), ip);
+        self
+    }
+
+    /// Returns the id of the container.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the host that the container may be reached on (may not be the local machine).
+    ///
+    /// Prefer it over hardcoded `127.0.0.1`, e.g. for advertised addresses, as it also works with remote daemons.
+    pub fn host(&self) -> &url::Host {
+        &self.host
+    }
+
+    /// Returns the IP address of the container in the given network (e.g. `bridge` or a custom network name).
+    pub fn ip_address(&self, network: &str) -> Option<IpAddr> {
+        self.ip_addresses.get(network).copied()
+    }
+
+    /// Returns the IP addresses of the container, keyed by network name.
+    pub fn ip_addresses(&self) -> &HashMap<String, IpAddr> {
+        &self.ip_addresses
+    }
+
     /// Returns the host port for the given internal container's port (`IPv4`).
     ///
     /// Results in an error ([`TestcontainersError::PortNotExposed`]) if the port is not exposed.
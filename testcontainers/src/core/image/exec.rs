@@ -1,10 +1,37 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bollard::exec::CreateExecOptions;
+use bytes::Bytes;
+
 use crate::core::{CmdWaitFor, WaitFor};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExecCommand {
     pub(crate) cmd: Vec<String>,
     pub(crate) cmd_ready_condition: CmdWaitFor,
     pub(crate) container_ready_conditions: Vec<WaitFor>,
+    pub(crate) env_vars: BTreeMap<String, String>,
+    pub(crate) working_dir: Option<String>,
+    pub(crate) user: Option<String>,
+    pub(crate) privileged: bool,
+    pub(crate) stdin: Option<Bytes>,
+    pub(crate) failure_policy: ExecFailurePolicy,
+}
+
+/// What to do when a command executed after start (see [`Image::exec_after_start`](crate::Image::exec_after_start))
+/// fails, i.e. its ready conditions aren't met.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecFailurePolicy {
+    /// Fails the startup of the container.
+    #[default]
+    Fail,
+    /// Logs the failure as a warning, and continues the startup.
+    Warn,
+    /// Executes the command again, up to `attempts` times in total with `backoff` in between,
+    /// and fails the startup if the last attempt fails as well.
+    ///
+    /// The command should be idempotent, since a failed attempt may have partially succeeded.
+    Retry { attempts: u32, backoff: Duration },
 }
 
 impl ExecCommand {
@@ -14,6 +41,12 @@ impl ExecCommand {
             cmd: cmd.into_iter().map(Into::into).collect(),
             cmd_ready_condition: CmdWaitFor::Nothing,
             container_ready_conditions: vec![],
+            env_vars: BTreeMap::new(),
+            working_dir: None,
+            user: None,
+            privileged: false,
+            stdin: None,
+            failure_policy: ExecFailurePolicy::Fail,
         }
     }
 
@@ -28,6 +61,62 @@ impl ExecCommand {
         self.cmd_ready_condition = ready_conditions.into();
         self
     }
+
+    /// Sets an environment variable for the command, in addition to the ones of the container.
+    pub fn with_env_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env_vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the working directory of the command. The default is the working directory of the container.
+    pub fn with_working_dir(mut self, working_dir: impl Into<String>) -> Self {
+        self.working_dir = Some(working_dir.into());
+        self
+    }
+
+    /// Sets the user (and optionally the group) running the command, in the `user`, `user:group`,
+    /// `uid` or `uid:gid` format. The default is the user of the container.
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Runs the command with extended privileges.
+    pub fn with_privileged(mut self, privileged: bool) -> Self {
+        self.privileged = privileged;
+        self
+    }
+
+    /// Sets the payload written to the stdin of the command, which is closed afterwards.
+    pub fn with_stdin(mut self, stdin: impl Into<Bytes>) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Sets what to do if the command fails when executed after start, the startup fails by default.
+    ///
+    /// Doesn't affect commands executed explicitly, e.g. with [`ContainerAsync::exec`](crate::ContainerAsync::exec).
+    pub fn with_failure_policy(mut self, policy: ExecFailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+
+    /// Returns the options to create the exec instance with, except for the attached streams.
+    pub(crate) fn create_exec_options(&self) -> CreateExecOptions<String> {
+        CreateExecOptions {
+            cmd: Some(self.cmd.clone()),
+            env: Some(
+                self.env_vars
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect(),
+            ),
+            working_dir: self.working_dir.clone(),
+            user: self.user.clone(),
+            privileged: Some(self.privileged),
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for ExecCommand {
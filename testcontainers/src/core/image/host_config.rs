@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bollard_stubs::models::ResourcesUlimits;
+
+/// Host configuration an image needs to work properly, contributed by [`Image::host_config_hints`].
+///
+/// Only a restricted set of settings can be contributed: ulimits, namespaced kernel parameters (sysctls)
+/// and the size of `/dev/shm`. The settings of the [`ContainerRequest`] take precedence over the hints,
+/// e.g. a ulimit set with [`ImageExt::with_ulimit`] replaces the hinted ulimit of the same name.
+/// Sysctls can't be set on the request, so the hinted ones are applied as they are; they can still be
+/// changed or removed one by one with [`ImageExt::with_create_body_modifier`].
+///
+/// [`Image::host_config_hints`]: crate::Image::host_config_hints
+/// [`ContainerRequest`]: crate::ContainerRequest
+/// [`ImageExt::with_ulimit`]: crate::ImageExt::with_ulimit
+/// [`ImageExt::with_create_body_modifier`]: crate::ImageExt::with_create_body_modifier
+#[must_use]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostConfigHints {
+    ulimits: BTreeMap<String, (i64, Option<i64>)>,
+    sysctls: BTreeMap<String, String>,
+    shm_size: Option<u64>,
+}
+
+impl HostConfigHints {
+    /// Creates empty hints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ulimit, e.g. `nofile` with `65536`.
+    pub fn with_ulimit(mut self, name: impl Into<String>, soft: i64, hard: Option<i64>) -> Self {
+        self.ulimits.insert(name.into(), (soft, hard));
+        self
+    }
+
+    /// Sets the namespaced kernel parameter, e.g. `net.core.somaxconn` with `1024`.
+    pub fn with_sysctl(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.sysctls.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the size of `/dev/shm` in bytes.
+    pub fn with_shm_size(mut self, bytes: u64) -> Self {
+        self.shm_size = Some(bytes);
+        self
+    }
+
+    /// Returns the ulimits by name, with their soft and hard limits.
+    pub fn ulimits(&self) -> impl Iterator<Item = (&str, i64, Option<i64>)> {
+        self.ulimits
+            .iter()
+            .map(|(name, (soft, hard))| (name.as_str(), *soft, *hard))
+    }
+
+    /// Returns the kernel parameters by name.
+    pub fn sysctls(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.sysctls
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns the size of `/dev/shm` in bytes, if set.
+    pub fn shm_size(&self) -> Option<u64> {
+        self.shm_size
+    }
+
+    /// Returns the hinted ulimits not overridden by the requested ones, followed by the requested ones.
+    pub(crate) fn merge_ulimits(&self, requested: &[ResourcesUlimits]) -> Vec<ResourcesUlimits> {
+        let hinted = self
+            .ulimits()
+            .filter(|(name, ..)| {
+                !requested
+                    .iter()
+                    .any(|ulimit| ulimit.name.as_deref() == Some(*name))
+            })
+            .map(|(name, soft, hard)| ResourcesUlimits {
+                name: Some(name.to_string()),
+                soft: Some(soft),
+                hard,
+            });
+        hinted.chain(requested.iter().cloned()).collect()
+    }
+
+    pub(crate) fn sysctls_map(&self) -> Option<HashMap<String, String>> {
+        (!self.sysctls.is_empty()).then(|| self.sysctls.clone().into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requested_ulimits_take_precedence() {
+        let hints = HostConfigHints::new()
+            .with_ulimit("nofile", 65536, Some(65536))
+            .with_ulimit("memlock", -1, Some(-1));
+        let requested = [ResourcesUlimits {
+            name: Some("nofile".to_string()),
+            soft: Some(1024),
+            hard: None,
+        }];
+
+        let merged = hints.merge_ulimits(&requested);
+        assert_eq!(
+            merged,
+            [
+                ResourcesUlimits {
+                    name: Some("memlock".to_string()),
+                    soft: Some(-1),
+                    hard: Some(-1),
+                },
+                requested[0].clone(),
+            ]
+        );
+        assert_eq!(HostConfigHints::new().sysctls_map(), None);
+    }
+}
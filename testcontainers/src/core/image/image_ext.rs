@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{future::Future, path::PathBuf, sync::Arc, time::Duration};
 
 use bollard_stubs::models::ResourcesUlimits;
 
@@ -6,18 +6,29 @@ use crate::{
     core::{
         copy::{CopyDataSource, CopyToContainer},
         logs::consumer::LogConsumer,
-        CgroupnsMode, ContainerPort, Host, Mount, PortMapping,
+        CgroupnsMode, ContainerCreateBody, ContainerHandle, ContainerPort, Host, ImageDecision,
+        Mount, PortMapping, ResourceBudget,
     },
     ContainerRequest, Image,
 };
 
 #[cfg(feature = "reusable-containers")]
-#[derive(Eq, Copy, Clone, Debug, Default, PartialEq)]
+#[derive(Eq, Copy, Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ReuseDirective {
     #[default]
     Never,
     Always,
     CurrentSession,
+    /// Shares the container between the test binaries run by the same `cargo` invocation, e.g. by a
+    /// workspace-wide `cargo test`, as well as between the processes of runners executing each test
+    /// in its own process. The invocation is identified by the parent process of the test binaries.
+    ///
+    /// The container is named after its configuration, so concurrently running binaries
+    /// don't start duplicates of it. On Linux, the containers left behind by previous invocations
+    /// are removed when the next one starts, once their `cargo` process has exited. On platforms
+    /// other than unix, this is equivalent to [`ReuseDirective::CurrentSession`].
+    CurrentInvocation,
 }
 
 #[cfg(feature = "reusable-containers")]
@@ -27,6 +38,7 @@ impl std::fmt::Display for ReuseDirective {
             Self::Never => "never",
             Self::Always => "always",
             Self::CurrentSession => "current-session",
+            Self::CurrentInvocation => "current-invocation",
         })
     }
 }
@@ -52,6 +64,26 @@ pub trait ImageExt<I: Image> {
     /// ```
     fn with_cmd(self, cmd: impl IntoIterator<Item = impl Into<String>>) -> ContainerRequest<I>;
 
+    /// Returns a new [`ContainerRequest`] running the given command line with the shell of the image,
+    /// overriding both `ENTRYPOINT` and `CMD`.
+    ///
+    /// The command is executed with the shell defined by the `SHELL` instruction of the image, falling back
+    /// to `/bin/sh -c` (or `cmd /S /C` for Windows images). This makes it possible to use shell features
+    /// (`&&`, pipes, variables expansion) without knowing how Docker combines entrypoint and command.
+    /// As a consequence, a command set with [`ImageExt::with_cmd`] is ignored, whatever the order of the calls.
+    ///
+    /// Starting the container fails with a descriptive error if the image doesn't contain the shell
+    /// (e.g. `distroless` or `scratch` based images).
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use testcontainers::{GenericImage, ImageExt};
+    ///
+    /// let request = GenericImage::new("alpine", "3.20")
+    ///     .with_shell_cmd("my-binary --flag && tail -f /dev/null");
+    /// ```
+    fn with_shell_cmd(self, cmd: impl Into<String>) -> ContainerRequest<I>;
+
     /// Overrides the fully qualified image name (consists of `{domain}/{owner}/{image}`).
     /// Can be used to specify a custom registry or owner.
     fn with_name(self, name: impl Into<String>) -> ContainerRequest<I>;
@@ -68,18 +100,31 @@ pub trait ImageExt<I: Image> {
     /// Sets the network the container will be connected to.
     fn with_network(self, network: impl Into<String>) -> ContainerRequest<I>;
 
+    /// Adds a DNS alias the container is resolvable by within the network.
+    ///
+    /// If the network differs from the one set with [`ImageExt::with_network`], the container is
+    /// connected to it in addition (the network is created if it doesn't exist), so a container may
+    /// join several networks.
+    fn with_network_alias(
+        self,
+        network: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> ContainerRequest<I>;
+
     /// Adds the specified label to the container.
     ///
-    /// **Note**: all keys in the `org.testcontainers.*` namespace should be regarded
-    /// as reserved by `testcontainers` internally, and should not be expected or relied
-    /// upon to be applied correctly if supplied as a value for `key`.
+    /// **Note**: all keys in the `org.testcontainers.*` namespace (or the one configured with
+    /// `TESTCONTAINERS_LABELS_NAMESPACE`) should be regarded as reserved by `testcontainers`
+    /// internally, and should not be expected or relied upon to be applied correctly if supplied
+    /// as a value for `key`.
     fn with_label(self, key: impl Into<String>, value: impl Into<String>) -> ContainerRequest<I>;
 
     /// Adds the specified labels to the container.
     ///
-    /// **Note**: all keys in the `org.testcontainers.*` namespace should be regarded
-    /// as reserved by `testcontainers` internally, and should not be expected or relied
-    /// upon to be applied correctly if they are included in `labels`.
+    /// **Note**: all keys in the `org.testcontainers.*` namespace (or the one configured with
+    /// `TESTCONTAINERS_LABELS_NAMESPACE`) should be regarded as reserved by `testcontainers`
+    /// internally, and should not be expected or relied upon to be applied correctly if they are
+    /// included in `labels`.
     fn with_labels(
         self,
         labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
@@ -95,6 +140,14 @@ pub trait ImageExt<I: Image> {
     /// Adds a mount to the container.
     fn with_mount(self, mount: impl Into<Mount>) -> ContainerRequest<I>;
 
+    /// Changes the owner of the writable mounts to `uid`:`gid` once the container is started,
+    /// before its ready conditions are awaited.
+    ///
+    /// Useful with rootless Docker or Podman, where bind-mounted paths are owned by a user
+    /// the process in the container can't write as. The ownership is changed recursively by
+    /// running `chown` as `root` inside the container, so the image has to provide it.
+    fn with_mount_chown(self, uid: u32, gid: u32) -> ContainerRequest<I>;
+
     /// Copies some source into the container as file
     fn with_copy_to(
         self,
@@ -127,6 +180,10 @@ pub trait ImageExt<I: Image> {
     /// Sets the container to run in privileged mode.
     fn with_privileged(self, privileged: bool) -> ContainerRequest<I>;
 
+    /// Keeps the stdin of the container open, so it can be written to
+    /// with [`ContainerAsync::attach_stdin`](crate::ContainerAsync::attach_stdin).
+    fn with_open_stdin(self, open_stdin: bool) -> ContainerRequest<I>;
+
     /// Adds the capabilities to the container
     fn with_cap_add(self, capability: impl Into<String>) -> ContainerRequest<I>;
 
@@ -149,9 +206,151 @@ pub trait ImageExt<I: Image> {
     /// Sets the startup timeout for the container. The default is 60 seconds.
     fn with_startup_timeout(self, timeout: Duration) -> ContainerRequest<I>;
 
+    /// Sets the grace period of the container to shut down cleanly before it's removed.
+    ///
+    /// By default, the container is killed and removed right away once dropped. With a stop timeout,
+    /// it's first stopped with SIGTERM, and killed only if it's still running after the timeout
+    /// (rounded up to whole seconds). Useful for databases or brokers flushing their data on shutdown,
+    /// e.g. to a bind-mounted directory. It also applies to [`ContainerAsync::stop`] and [`ContainerAsync::rm`].
+    ///
+    /// [`ContainerAsync::stop`]: crate::ContainerAsync::stop
+    /// [`ContainerAsync::rm`]: crate::ContainerAsync::rm
+    fn with_stop_timeout(self, timeout: Duration) -> ContainerRequest<I>;
+
+    /// Logs a summary of the [`StartupReport`] at info level once the container is ready,
+    /// e.g. `pull: 1.2s, create: 35.0ms, start: 210.0ms, wait: 2.1s (log: 2.1s), total: 3.6s`.
+    ///
+    /// Useful to diagnose slow startups in CI without enabling debug logs.
+    ///
+    /// [`StartupReport`]: crate::core::StartupReport
+    fn with_startup_report_logging(self) -> ContainerRequest<I>;
+
+    /// Captures the network traffic of the container into the pcap file `host_output_path`,
+    /// e.g. to inspect a failing protocol exchange with Wireshark.
+    ///
+    /// A `tcpdump` sidecar (from the `nicolaka/netshoot` image) joins the network namespace of the container
+    /// right after it's started, before its ready conditions are awaited, so the very first packets may be missed.
+    /// The capture is written once the container is removed or dropped,
+    /// the parent directory of the file is created if it doesn't exist.
+    fn with_packet_capture(self, host_output_path: impl Into<PathBuf>) -> ContainerRequest<I>;
+
+    /// Sets the deadline of each ready condition which doesn't have its own (e.g. [`LogWaitStrategy::with_timeout`]).
+    /// The default is read from the `TESTCONTAINERS_WAIT_TIMEOUT` environment variable (e.g. `30s`), if set.
+    ///
+    /// Unlike the startup timeout, the deadline applies to each condition separately.
+    ///
+    /// [`LogWaitStrategy::with_timeout`]: crate::core::wait::LogWaitStrategy::with_timeout
+    fn with_wait_timeout(self, timeout: Duration) -> ContainerRequest<I>;
+
     /// Sets the working directory. The default is defined by the underlying image, which in turn may default to `/`.
     fn with_working_dir(self, working_dir: impl Into<String>) -> ContainerRequest<I>;
 
+    /// Sets the hostname of the container, which defaults to the (short) container id.
+    ///
+    /// Unlike network aliases (see [`ImageExt::with_network_alias`]), it's the name the container sees itself by,
+    /// e.g. in `/etc/hostname`.
+    fn with_hostname(self, hostname: impl Into<String>) -> ContainerRequest<I>;
+
+    /// Sets the MAC address of the container (e.g. `02:42:ac:11:00:42`) in its primary network,
+    /// the one set with [`ImageExt::with_network`] or the default bridge network.
+    ///
+    /// To set the address in additional networks, connect the container with [`NetworkEndpoint::with_mac_address`].
+    ///
+    /// [`NetworkEndpoint::with_mac_address`]: crate::core::NetworkEndpoint::with_mac_address
+    fn with_mac_address(self, mac_address: impl Into<String>) -> ContainerRequest<I>;
+
+    /// Sets the resource budget of the container, its usage is sampled in the background once it's started.
+    ///
+    /// A violation is reported by [`ContainerAsync::check_resource_budget`](crate::ContainerAsync::check_resource_budget)
+    /// and by [`ContainerAsync::wait`](crate::ContainerAsync::wait).
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use testcontainers::{core::ResourceBudget, GenericImage, ImageExt};
+    ///
+    /// let image = GenericImage::new("image", "tag").with_resource_budget(
+    ///     ResourceBudget::new()
+    ///         .with_max_memory(256 * 1024 * 1024)
+    ///         .with_max_cpu_time(Duration::from_secs(30)),
+    /// );
+    /// ```
+    fn with_resource_budget(self, budget: ResourceBudget) -> ContainerRequest<I>;
+
+    /// Sets the Docker host used to run the container, e.g. `tcp://other-daemon:2375`.
+    ///
+    /// Takes precedence over the configured host, while the rest of the configuration
+    /// (e.g. TLS settings) is shared with the default client.
+    /// Containers started on an overridden host aren't tracked by the `watchdog`.
+    fn with_docker_host(self, docker_host: impl Into<String>) -> ContainerRequest<I>;
+
+    /// Sets a callback collecting diagnostics of a container which is slow to start.
+    ///
+    /// Once 80% of the startup timeout elapses, the callback is invoked with a handle of the container
+    /// (e.g. to execute `jstack` or `top` in it). If the container doesn't get ready in time,
+    /// the returned output is attached to the [`WaitContainerError::StartupTimeoutWithDiagnostics`] error,
+    /// since by then the evidence is often already gone. A callback still running at the timeout is
+    /// given as much time again (20% of the startup timeout), then abandoned with a plain startup timeout.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use testcontainers::{GenericImage, ImageExt};
+    ///
+    /// let image = GenericImage::new("image", "tag").with_startup_timeout_escalation(|container| async move {
+    ///     match container.exec(["top", "-b", "-n", "1"]).await {
+    ///         Ok(output) => String::from_utf8_lossy(output.stdout()).into_owned(),
+    ///         Err(err) => format!("failed to capture processes: {err}"),
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// [`WaitContainerError::StartupTimeoutWithDiagnostics`]: crate::core::error::WaitContainerError::StartupTimeoutWithDiagnostics
+    fn with_startup_timeout_escalation<F, Fut>(self, callback: F) -> ContainerRequest<I>
+    where
+        F: Fn(ContainerHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static;
+
+    /// Sets the policy deciding whether the image of this request may be used, along with the global
+    /// policy set by [`set_image_policy`](crate::set_image_policy): the image is used only if both allow it.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use testcontainers::{core::ImageDecision, GenericImage, ImageExt};
+    ///
+    /// let image = GenericImage::new("image", "tag").with_image_policy(|descriptor| {
+    ///     if descriptor.starts_with("registry.example.com/") {
+    ///         ImageDecision::Allow
+    ///     } else {
+    ///         ImageDecision::Deny("only the internal registry is allowed".to_string())
+    ///     }
+    /// });
+    /// ```
+    fn with_image_policy<F>(self, policy: F) -> ContainerRequest<I>
+    where
+        F: Fn(&str) -> ImageDecision + Send + Sync + 'static;
+
+    /// Adjusts the body of the request creating the container, right before it's submitted to the daemon.
+    ///
+    /// This is an escape hatch for the rare daemon options which aren't exposed by the request yet
+    /// (e.g. `MacAddress` or `StopSignal`). The modifier runs after all the other options are applied,
+    /// so it may override them as well. Setting the same modifier again replaces the previous one.
+    ///
+    /// **NOTE:** the modifier is unstable: [`ContainerCreateBody`] is the type of the underlying Docker client,
+    /// and it may change with its upgrades, even in minor releases of `testcontainers`.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use testcontainers::{GenericImage, ImageExt};
+    ///
+    /// let image = GenericImage::new("image", "tag").with_create_body_modifier(|body| {
+    ///     body.stop_signal = Some("SIGINT".to_string());
+    /// });
+    /// ```
+    fn with_create_body_modifier(
+        self,
+        modifier: impl Fn(&mut ContainerCreateBody) + Send + Sync + 'static,
+    ) -> ContainerRequest<I>;
+
     /// Adds the log consumer to the container.
     ///
     /// Allows to follow the container logs for the whole lifecycle of the container, starting from the creation.
@@ -166,6 +365,18 @@ pub trait ImageExt<I: Image> {
     /// `Container` or `ContainerAsync` is dropped.
     #[cfg(feature = "reusable-containers")]
     fn with_reuse(self, reuse: ReuseDirective) -> ContainerRequest<I>;
+
+    /// Reuses a container only if it was started with the same configuration, when combined with
+    /// [`ImageExt::with_reuse`].
+    ///
+    /// By default, a reusable container is matched by its name, network and labels only, so a changed
+    /// environment variable or command would silently reuse a stale container. With the reuse hash,
+    /// a hash of the whole configuration and of the image id is stored as a label of the container,
+    /// and a container with another hash isn't reused. The image is pulled beforehand if it's missing.
+    ///
+    /// The hash label isn't applied if the labels namespace is disabled (`TESTCONTAINERS_LABELS_NAMESPACE`).
+    #[cfg(feature = "reusable-containers")]
+    fn with_reuse_hash(self, enabled: bool) -> ContainerRequest<I>;
 }
 
 /// Implements the [`ImageExt`] trait for the every type that can be converted into a [`ContainerRequest`].
@@ -178,6 +389,14 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         }
     }
 
+    fn with_shell_cmd(self, cmd: impl Into<String>) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            shell_cmd: Some(cmd.into()),
+            ..container_req
+        }
+    }
+
     fn with_name(self, name: impl Into<String>) -> ContainerRequest<I> {
         let container_req = self.into();
         ContainerRequest {
@@ -211,6 +430,20 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         }
     }
 
+    fn with_network_alias(
+        self,
+        network: impl Into<String>,
+        alias: impl Into<String>,
+    ) -> ContainerRequest<I> {
+        let mut container_req = self.into();
+        container_req
+            .network_aliases
+            .entry(network.into())
+            .or_default()
+            .push(alias.into());
+        container_req
+    }
+
     fn with_label(self, key: impl Into<String>, value: impl Into<String>) -> ContainerRequest<I> {
         let mut container_req = self.into();
 
@@ -256,6 +489,14 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         container_req
     }
 
+    fn with_mount_chown(self, uid: u32, gid: u32) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            mount_owner: Some((uid, gid)),
+            ..container_req
+        }
+    }
+
     fn with_copy_to(
         self,
         target: impl Into<String>,
@@ -307,6 +548,14 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         }
     }
 
+    fn with_open_stdin(self, open_stdin: bool) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            open_stdin,
+            ..container_req
+        }
+    }
+
     fn with_cap_add(self, capability: impl Into<String>) -> ContainerRequest<I> {
         let mut container_req = self.into();
         container_req
@@ -359,6 +608,38 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         }
     }
 
+    fn with_stop_timeout(self, timeout: Duration) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            stop_timeout: Some(timeout),
+            ..container_req
+        }
+    }
+
+    fn with_packet_capture(self, host_output_path: impl Into<PathBuf>) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            packet_capture: Some(host_output_path.into()),
+            ..container_req
+        }
+    }
+
+    fn with_startup_report_logging(self) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            log_startup_report: true,
+            ..container_req
+        }
+    }
+
+    fn with_wait_timeout(self, timeout: Duration) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            wait_timeout: Some(timeout),
+            ..container_req
+        }
+    }
+
     fn with_working_dir(self, working_dir: impl Into<String>) -> ContainerRequest<I> {
         let container_req = self.into();
         ContainerRequest {
@@ -367,6 +648,72 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
         }
     }
 
+    fn with_hostname(self, hostname: impl Into<String>) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            hostname: Some(hostname.into()),
+            ..container_req
+        }
+    }
+
+    fn with_mac_address(self, mac_address: impl Into<String>) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            mac_address: Some(mac_address.into()),
+            ..container_req
+        }
+    }
+
+    fn with_resource_budget(self, budget: ResourceBudget) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            resource_budget: Some(budget),
+            ..container_req
+        }
+    }
+
+    fn with_docker_host(self, docker_host: impl Into<String>) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            docker_host: Some(docker_host.into()),
+            ..container_req
+        }
+    }
+
+    fn with_startup_timeout_escalation<F, Fut>(self, callback: F) -> ContainerRequest<I>
+    where
+        F: Fn(ContainerHandle) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        let container_req = self.into();
+        ContainerRequest {
+            timeout_escalation: Some(Arc::new(move |handle| Box::pin(callback(handle)))),
+            ..container_req
+        }
+    }
+
+    fn with_image_policy<F>(self, policy: F) -> ContainerRequest<I>
+    where
+        F: Fn(&str) -> ImageDecision + Send + Sync + 'static,
+    {
+        let container_req = self.into();
+        ContainerRequest {
+            image_policy: Some(Arc::new(policy)),
+            ..container_req
+        }
+    }
+
+    fn with_create_body_modifier(
+        self,
+        modifier: impl Fn(&mut ContainerCreateBody) + Send + Sync + 'static,
+    ) -> ContainerRequest<I> {
+        let container_req = self.into();
+        ContainerRequest {
+            create_body_modifier: Some(Arc::new(modifier)),
+            ..container_req
+        }
+    }
+
     fn with_log_consumer(self, log_consumer: impl LogConsumer + 'static) -> ContainerRequest<I> {
         let mut container_req = self.into();
         container_req.log_consumers.push(Box::new(log_consumer));
@@ -380,4 +727,12 @@ impl<RI: Into<ContainerRequest<I>>, I: Image> ImageExt<I> for RI {
             ..self.into()
         }
     }
+
+    #[cfg(feature = "reusable-containers")]
+    fn with_reuse_hash(self, enabled: bool) -> ContainerRequest<I> {
+        ContainerRequest {
+            reuse_hash: enabled,
+            ..self.into()
+        }
+    }
 }
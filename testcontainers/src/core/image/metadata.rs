@@ -0,0 +1,97 @@
+use std::time::SystemTime;
+
+use bollard_stubs::models::ImageInspect;
+
+use crate::core::containers::state::parse_timestamp;
+
+/// Metadata of the image a container has been created from.
+///
+/// Obtained with [`ContainerAsync::image_metadata`](crate::ContainerAsync::image_metadata), useful to
+/// log exactly which build of a mutable tag (e.g. `latest`) served a test run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageMetadata {
+    id: String,
+    repo_digests: Vec<String>,
+    created: Option<SystemTime>,
+    size: Option<u64>,
+    os: Option<String>,
+    architecture: Option<String>,
+}
+
+impl ImageMetadata {
+    pub(crate) fn new(id: String, inspect: ImageInspect) -> Self {
+        Self {
+            id: inspect.id.unwrap_or(id),
+            repo_digests: inspect.repo_digests.unwrap_or_default(),
+            created: inspect.created.as_deref().and_then(parse_timestamp),
+            size: inspect.size.and_then(|size| u64::try_from(size).ok()),
+            os: inspect.os,
+            architecture: inspect.architecture,
+        }
+    }
+
+    /// Id of the image (e.g. `sha256:...`).
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Digest of the image in the registry it has been pulled from (e.g. `sha256:...`), if any.
+    ///
+    /// Locally built images don't have a digest until they are pushed.
+    pub fn digest(&self) -> Option<&str> {
+        self.repo_digests
+            .first()
+            .and_then(|digest| digest.split_once('@'))
+            .map(|(_, digest)| digest)
+    }
+
+    /// Repository digests of the image (e.g. `redis@sha256:...`).
+    pub fn repo_digests(&self) -> &[String] {
+        &self.repo_digests
+    }
+
+    /// The time the image was created.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.created
+    }
+
+    /// Size of the image in bytes.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Operating system the image is built for, e.g. `linux`.
+    pub fn os(&self) -> Option<&str> {
+        self.os.as_deref()
+    }
+
+    /// Architecture the image is built for, e.g. `amd64` or `arm64`.
+    pub fn architecture(&self) -> Option<&str> {
+        self.architecture.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_is_parsed_from_image_inspect() {
+        let metadata = ImageMetadata::new(
+            "sha256:abc".to_string(),
+            ImageInspect {
+                repo_digests: Some(vec!["redis@sha256:0123".to_string()]),
+                created: Some("2024-06-11T09:08:33Z".to_string()),
+                size: Some(42),
+                architecture: Some("arm64".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(metadata.id(), "sha256:abc");
+        assert_eq!(metadata.digest(), Some("sha256:0123"));
+        assert_eq!(metadata.size(), Some(42));
+        assert_eq!(metadata.architecture(), Some("arm64"));
+        assert!(metadata.created().is_some());
+    }
+}
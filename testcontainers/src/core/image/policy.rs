@@ -0,0 +1,130 @@
+use std::sync::{Arc, RwLock};
+
+use crate::core::error::{Result, TestcontainersError};
+
+/// Decision of an image policy about an image, see [`set_image_policy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageDecision {
+    /// The image may be used.
+    Allow,
+    /// The image must not be used, for the given reason.
+    Deny(String),
+}
+
+/// A policy deciding whether an image descriptor (`name:tag`) may be used.
+pub(crate) type ImagePolicy = Arc<dyn Fn(&str) -> ImageDecision + Send + Sync>;
+
+static IMAGE_POLICY: RwLock<Option<ImagePolicy>> = RwLock::new(None);
+
+/// Sets the policy deciding which images may be used by the tests of the process,
+/// e.g. to prevent the accidental use of images which weren't approved.
+///
+/// The policy is called with the descriptor of the image (`name:tag`, as resolved with
+/// [`ImageExt::with_name`] and [`ImageExt::with_tag`]) before it's pulled or a container is started,
+/// including the sidecars started by `testcontainers`. A denied image fails with
+/// [`TestcontainersError::ImagePolicyViolation`]. Setting a policy again replaces the previous one.
+///
+/// The policy applies along with the policy of the request ([`ImageExt::with_image_policy`]) and
+/// the prefixes allowed by the `TESTCONTAINERS_IMAGE_ALLOWLIST` environment variable, if set:
+/// an image is used only if all of them allow it.
+///
+/// ```rust
+/// use testcontainers::core::ImageDecision;
+///
+/// testcontainers::set_image_policy(|descriptor| {
+///     if descriptor.ends_with(":latest") {
+///         ImageDecision::Deny("floating tags are not allowed".to_string())
+///     } else {
+///         ImageDecision::Allow
+///     }
+/// });
+/// ```
+///
+/// [`ImageExt::with_name`]: crate::ImageExt::with_name
+/// [`ImageExt::with_tag`]: crate::ImageExt::with_tag
+/// [`ImageExt::with_image_policy`]: crate::ImageExt::with_image_policy
+pub fn set_image_policy<F>(policy: F)
+where
+    F: Fn(&str) -> ImageDecision + Send + Sync + 'static,
+{
+    *IMAGE_POLICY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(Arc::new(policy));
+}
+
+/// Checks the descriptor against the policy of the request, the global policy and the allowed prefixes.
+pub(crate) fn check_image_policy(
+    descriptor: &str,
+    request_policy: Option<&ImagePolicy>,
+    allowlist: Option<&[String]>,
+) -> Result<()> {
+    let global_policy = IMAGE_POLICY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+
+    let decision = [request_policy, global_policy.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|policy| policy(descriptor))
+        .find(|decision| matches!(decision, ImageDecision::Deny(_)))
+        .or_else(|| allowlist_decision(descriptor, allowlist?));
+
+    match decision {
+        Some(ImageDecision::Deny(reason)) => Err(TestcontainersError::ImagePolicyViolation {
+            descriptor: descriptor.to_string(),
+            reason,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn allowlist_decision(descriptor: &str, allowlist: &[String]) -> Option<ImageDecision> {
+    if allowlist
+        .iter()
+        .any(|prefix| descriptor.starts_with(prefix.as_str()))
+    {
+        None
+    } else {
+        Some(ImageDecision::Deny(format!(
+            "not allowed by TESTCONTAINERS_IMAGE_ALLOWLIST ({})",
+            allowlist.join(",")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_policy_must_allow_the_image() {
+        let allowlist = ["ghcr.io/acme/".to_string(), "redis:".to_string()];
+        let no_latest: ImagePolicy = Arc::new(|descriptor: &str| {
+            if descriptor.ends_with(":latest") {
+                ImageDecision::Deny("floating tag".to_string())
+            } else {
+                ImageDecision::Allow
+            }
+        });
+
+        assert!(check_image_policy("redis:7.2.4", Some(&no_latest), Some(&allowlist)).is_ok());
+        assert!(check_image_policy("ghcr.io/acme/app:1.0", None, Some(&allowlist)).is_ok());
+        assert!(check_image_policy("postgres:16", Some(&no_latest), None).is_ok());
+
+        let err =
+            check_image_policy("redis:latest", Some(&no_latest), Some(&allowlist)).unwrap_err();
+        assert!(
+            matches!(&err, TestcontainersError::ImagePolicyViolation { descriptor, reason }
+                if descriptor == "redis:latest" && reason == "floating tag"),
+            "{err}"
+        );
+
+        let err = check_image_policy("postgres:16", None, Some(&allowlist)).unwrap_err();
+        assert!(
+            matches!(&err, TestcontainersError::ImagePolicyViolation { reason, .. }
+                if reason.contains("TESTCONTAINERS_IMAGE_ALLOWLIST")),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Lifecycle events of containers (pull, create, start, wait, exec, removal).
+//!
+//! With the `tracing` feature, the events are emitted with the [`tracing`](https://docs.rs/tracing) crate
+//! as structured fields under the `testcontainers` target, within the `pull`, `create` and `startup` spans.
+//! Otherwise, they are logged at debug level.
+
+/// Emits an event of the container lifecycle, e.g. `lifecycle_event!("created", id = id, elapsed = elapsed)`.
+///
+/// The values are recorded with their `Debug` representation.
+macro_rules! lifecycle_event {
+    ($stage:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!(target: "testcontainers", $($field = ?$value,)* $stage);
+        #[cfg(not(feature = "tracing"))]
+        ::log::debug!(
+            concat!("Container ", $stage $(, ", ", stringify!($field), ": {:?}")*)
+            $(, $value)*
+        );
+    }};
+}
+
+/// Instruments the future with a span of the container lifecycle, e.g. `lifecycle_span!(pull, "pull", image = image)`.
+///
+/// Without the `tracing` feature, the future is returned as is.
+macro_rules! lifecycle_span {
+    ($future:expr, $stage:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        let future = ::tracing::Instrument::instrument(
+            $future,
+            ::tracing::debug_span!(target: "testcontainers", $stage, $($field = ?$value,)*),
+        );
+        #[cfg(not(feature = "tracing"))]
+        let future = $future;
+        future
+    }};
+}
+
+pub(crate) use lifecycle_event;
+pub(crate) use lifecycle_span;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_emitted_within_lifecycle_spans() {
+        let output = Output::default();
+        let writer = output.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let id = "4f2b";
+        lifecycle_span!(
+            async { lifecycle_event!("created", id = id) },
+            "create",
+            image = "redis:7.2.4"
+        )
+        .await;
+        lifecycle_event!("removed", id = id);
+
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 2, "{output}");
+        assert!(
+            lines[0].contains(r#"create{image="redis:7.2.4"}: testcontainers: created id="4f2b""#),
+            "{output}"
+        );
+        assert!(
+            lines[1].contains(r#"testcontainers: removed id="4f2b""#),
+            "{output}"
+        );
+    }
+}
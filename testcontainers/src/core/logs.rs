@@ -1,18 +1,29 @@
-use std::{borrow::Cow, fmt, io};
+use std::{borrow::Cow, fmt, io, time::SystemTime};
 
 use bytes::Bytes;
 use futures::{stream::BoxStream, StreamExt};
 use memchr::memmem::Finder;
 
 pub mod consumer;
-pub(crate) mod stream;
+pub mod stream;
 
+/// A chunk of the container logs, as received from Docker.
 #[derive(Debug, Clone)]
 pub enum LogFrame {
     StdOut(Bytes),
     StdErr(Bytes),
 }
 
+/// A complete line of the container logs, without the trailing newline.
+///
+/// See [`LogStream::into_lines`](stream::LogStream::into_lines).
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    source: LogSource,
+    timestamp: Option<SystemTime>,
+    content: Bytes,
+}
+
 /// Defines error cases when waiting for a message in a stream.
 #[derive(Debug, thiserror::Error)]
 pub enum WaitLogError {
@@ -24,6 +35,7 @@ pub enum WaitLogError {
     Io(#[from] io::Error),
 }
 
+/// The output the logs are written to.
 #[derive(Copy, Clone, Debug, parse_display::Display)]
 #[display(style = "lowercase")]
 pub enum LogSource {
@@ -57,6 +69,26 @@ impl LogFrame {
     }
 }
 
+impl LogLine {
+    pub fn source(&self) -> LogSource {
+        self.source
+    }
+
+    /// Returns the time the line was logged at, if the logs were requested with timestamps.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+
+    pub fn content(&self) -> &Bytes {
+        &self.content
+    }
+
+    /// Returns the content of the line, replacing invalid UTF-8 sequences.
+    pub fn content_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.content)
+    }
+}
+
 // TODO: extract caching functionality to a separate wrapper
 pub(crate) struct WaitingStreamWrapper {
     inner: BoxStream<'static, Result<Bytes, io::Error>>,
@@ -113,6 +145,55 @@ impl WaitingStreamWrapper {
         Err(WaitLogError::EndOfStream(messages))
     }
 
+    /// Waits for lines (separated by `\n`) accepted by the matcher to appear in the stream `times` times.
+    ///
+    /// Unlike [`WaitingStreamWrapper::wait_for_message`], the matcher is applied to complete lines,
+    /// even if they are split across multiple chunks of the stream.
+    pub(crate) async fn wait_for_line(
+        &mut self,
+        matcher: impl Fn(&[u8]) -> bool,
+        times: usize,
+    ) -> Result<(), WaitLogError> {
+        let mut messages = Vec::new();
+        let mut line_buf = Vec::new();
+        let mut found_times: usize = 0;
+        let mut check_line = |line: &[u8]| {
+            if matcher(line) {
+                found_times += 1;
+            }
+            found_times == times
+        };
+
+        while let Some(message) = self.inner.next().await.transpose()? {
+            messages.push(message.clone());
+            if self.enable_cache {
+                self.cache.push(Ok(message.clone()));
+            }
+
+            line_buf.extend_from_slice(message.as_ref());
+            while let Some(pos) = memchr::memchr(b'\n', &line_buf) {
+                let line: Vec<u8> = line_buf.drain(..=pos).collect();
+                if check_line(&line[..pos]) {
+                    log::debug!(
+                        "Matching line found {times} times after comparing {} chunks",
+                        messages.len()
+                    );
+                    return Ok(());
+                }
+            }
+        }
+
+        if !line_buf.is_empty() && check_line(&line_buf) {
+            return Ok(());
+        }
+
+        log::warn!(
+            "Failed to find matching line {times} times after comparing {} chunks.",
+            messages.len()
+        );
+        Err(WaitLogError::EndOfStream(messages))
+    }
+
     pub(crate) fn into_inner(self) -> BoxStream<'static, Result<Bytes, io::Error>> {
         futures::stream::iter(self.cache).chain(self.inner).boxed()
     }
@@ -163,4 +244,33 @@ mod tests {
         let result = log_stream().wait_for_message("Message three", 3).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn given_logs_when_line_is_split_across_chunks_should_match_it() {
+        let log_stream = || {
+            WaitingStreamWrapper::new(Box::pin(futures::stream::iter([
+                Ok("first line\nsecond ".into()),
+                Ok("line\nthird".into()),
+                Ok(" line".into()),
+            ])))
+        };
+
+        let result = log_stream()
+            .wait_for_line(|line| line == b"second line", 1)
+            .await;
+        assert!(result.is_ok());
+
+        let result = log_stream()
+            .wait_for_line(|line| line == b"third line", 1)
+            .await;
+        assert!(result.is_ok());
+
+        let result = log_stream()
+            .wait_for_line(|line| line.ends_with(b"line"), 3)
+            .await;
+        assert!(result.is_ok());
+
+        let result = log_stream().wait_for_line(|line| line == b"line", 1).await;
+        assert!(result.is_err());
+    }
 }
@@ -2,6 +2,7 @@ use futures::{future::BoxFuture, FutureExt};
 
 use crate::core::logs::LogFrame;
 
+pub mod file_consumer;
 pub mod logging_consumer;
 
 /// Log consumer is a trait that allows to consume log frames.
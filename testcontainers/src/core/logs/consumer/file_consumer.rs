@@ -0,0 +1,130 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+
+use crate::core::logs::{consumer::LogConsumer, LogFrame};
+
+/// A consumer that writes the output of container to a file, e.g. to keep the logs as CI artifacts.
+///
+/// Standard out and standard error are written to the same file, in the order they are received.
+/// The file is flushed after each frame, so it's complete even if the test panics.
+///
+/// # Example
+/// ```rust,no_run
+/// use testcontainers::{
+///     core::logs::consumer::file_consumer::FileLogConsumer, GenericImage, ImageExt,
+/// };
+///
+/// # fn example() -> std::io::Result<()> {
+/// // e.g. `target/container-logs/tests__redis_is_reachable.log` for the test `tests::redis_is_reachable`
+/// let image = GenericImage::new("redis", "7.2.4")
+///     .with_log_consumer(FileLogConsumer::per_test("target/container-logs")?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileLogConsumer {
+    path: PathBuf,
+    file: Mutex<tokio::fs::File>,
+    source_prefix: bool,
+}
+
+impl FileLogConsumer {
+    /// Creates the file (and its parent directories), truncating the previous logs if it exists.
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(&path)?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(tokio::fs::File::from_std(file)),
+            source_prefix: false,
+        })
+    }
+
+    /// Creates a file in the directory named after the current test, so each test gets its own logs.
+    ///
+    /// The test is recognized by the name of the current thread, as set by the test harness
+    /// (`::` is replaced by `__`). Must be called from the test itself, not from a spawned task.
+    /// Outside of tests, the file is named `container.log`.
+    pub fn per_test(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let name = std::thread::current()
+            .name()
+            .filter(|name| *name != "main")
+            .map(|name| name.replace("::", "__"))
+            .unwrap_or_else(|| "container".to_string());
+        Self::new(dir.as_ref().join(format!("{name}.log")))
+    }
+
+    /// Prefixes each frame with its source, `[stdout] ` or `[stderr] `. Disabled by default.
+    pub fn with_source_prefix(mut self) -> Self {
+        self.source_prefix = true;
+        self
+    }
+
+    /// Path of the file the logs are written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn write(&self, record: &LogFrame) -> io::Result<()> {
+        let mut file = self.file.lock().await;
+        if self.source_prefix {
+            let prefix: &[u8] = match record {
+                LogFrame::StdOut(_) => b"[stdout] ",
+                LogFrame::StdErr(_) => b"[stderr] ",
+            };
+            file.write_all(prefix).await?;
+        }
+        file.write_all(record.bytes()).await?;
+        file.flush().await
+    }
+}
+
+impl LogConsumer for FileLogConsumer {
+    fn accept<'a>(&'a self, record: &'a LogFrame) -> BoxFuture<'a, ()> {
+        async move {
+            if let Err(err) = self.write(record).await {
+                log::warn!("Failed to write container logs to {:?}: {err}", self.path);
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn frames_are_written_in_order() -> anyhow::Result<()> {
+        let dir = temp_dir::TempDir::new()?;
+        let consumer = FileLogConsumer::per_test(dir.path())?.with_source_prefix();
+        assert_eq!(
+            consumer.path(),
+            dir.path().join(
+                "core__logs__consumer__file_consumer__tests__frames_are_written_in_order.log"
+            )
+        );
+
+        consumer
+            .accept(&LogFrame::StdOut(Bytes::from_static(b"started\n")))
+            .await;
+        consumer
+            .accept(&LogFrame::StdErr(Bytes::from_static(b"warning\n")))
+            .await;
+
+        let logs = std::fs::read_to_string(consumer.path())?;
+        assert_eq!(logs, "[stdout] started\n[stderr] warning\n");
+        Ok(())
+    }
+}
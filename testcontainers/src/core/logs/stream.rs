@@ -1,32 +1,87 @@
+//! Streams of container logs.
+
 use std::{
-    io,
+    fmt, io,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::SystemTime,
 };
 
 use bytes::Bytes;
-use futures::{stream::BoxStream, Stream, StreamExt};
+use futures::{stream::BoxStream, Stream, StreamExt, TryStreamExt};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use crate::core::logs::LogFrame;
+use crate::core::logs::{LogFrame, LogLine, LogSource};
 
-pub(crate) type RawLogStream = BoxStream<'static, Result<Bytes, io::Error>>;
+/// A stream of raw log chunks of a single source, as they are received from Docker.
+pub type RawLogStream = BoxStream<'static, Result<Bytes, io::Error>>;
 
 pin_project_lite::pin_project! {
-    pub(crate) struct LogStream {
+    /// A stream of log frames of both stdout and stderr of a container, in the order they are received.
+    ///
+    /// Frames don't necessarily contain complete lines, use [`LogStream::into_lines`] to get them.
+    pub struct LogStream {
         #[pin]
         inner: BoxStream<'static, Result<LogFrame, io::Error>>,
+        timestamped: bool,
     }
 }
 
 impl LogStream {
     pub fn new(stream: BoxStream<'static, Result<LogFrame, io::Error>>) -> Self {
-        Self { inner: stream }
+        Self {
+            inner: stream,
+            timestamped: false,
+        }
+    }
+
+    /// Merges streams of stdout and stderr into a single stream.
+    ///
+    /// Frames of each source keep their order, while frames of different sources are
+    /// interleaved in the order they are received. This is the inverse of [`LogStream::split`].
+    pub fn merge(stdout: RawLogStream, stderr: RawLogStream) -> Self {
+        let stdout = stdout.map_ok(LogFrame::StdOut);
+        let stderr = stderr.map_ok(LogFrame::StdErr);
+        Self::new(futures::stream::select(stdout, stderr).boxed())
+    }
+
+    /// Indicates that each line of the stream is prefixed with its RFC 3339 timestamp,
+    /// as done by Docker when logs are requested with timestamps.
+    ///
+    /// The timestamps are then parsed by [`LogStream::into_lines`].
+    pub fn with_timestamps(mut self) -> Self {
+        self.timestamped = true;
+        self
+    }
+
+    /// Converts the stream into a stream of complete lines.
+    ///
+    /// Lines of stdout and stderr are assembled separately, so frames of one source don't break
+    /// the lines of the other. A trailing line without a newline is emitted once the stream ends.
+    pub fn into_lines(self) -> BoxStream<'static, Result<LogLine, io::Error>> {
+        let splitter = LineSplitter {
+            timestamped: self.timestamped,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        };
+        futures::stream::unfold(Some((self.inner, splitter)), |state| async move {
+            let (mut inner, mut splitter) = state?;
+            match inner.next().await {
+                Some(Ok(frame)) => {
+                    let lines = splitter.push(frame).into_iter().map(Ok).collect();
+                    Some((lines, Some((inner, splitter))))
+                }
+                Some(Err(err)) => Some((vec![Err(err)], Some((inner, splitter)))),
+                None => Some((splitter.finish().into_iter().map(Ok).collect(), None)),
+            }
+        })
+        .flat_map(futures::stream::iter)
+        .boxed()
     }
 
     /// Filters the log stream to only include stdout messages.
-    pub(crate) fn into_stdout(self) -> RawLogStream {
+    pub fn into_stdout(self) -> RawLogStream {
         self.inner
             .filter_map(|record| async move {
                 match record {
@@ -39,7 +94,7 @@ impl LogStream {
     }
 
     /// Filters the log stream to only include stderr messages.
-    pub(crate) fn into_stderr(self) -> RawLogStream {
+    pub fn into_stderr(self) -> RawLogStream {
         self.inner
             .filter_map(|record| async move {
                 match record {
@@ -52,7 +107,9 @@ impl LogStream {
     }
 
     /// Splits the log stream into two streams, one for stdout and one for stderr.
-    pub(crate) async fn split(self) -> (RawLogStream, RawLogStream) {
+    ///
+    /// The frames are distributed by a background task, so both streams can be consumed independently.
+    pub async fn split(self) -> (RawLogStream, RawLogStream) {
         let (stdout_tx, stdout_rx) = tokio::sync::mpsc::unbounded_channel();
         let (stderr_tx, stderr_rx) = tokio::sync::mpsc::unbounded_channel();
 
@@ -96,6 +153,84 @@ impl LogStream {
     }
 }
 
+/// Assembles complete lines out of the frames of both sources.
+struct LineSplitter {
+    timestamped: bool,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+impl LineSplitter {
+    fn push(&mut self, frame: LogFrame) -> Vec<LogLine> {
+        let source = frame.source();
+        let buf = self.buffer(source);
+        buf.extend_from_slice(frame.bytes());
+
+        let mut lines = Vec::new();
+        while let Some(pos) = memchr::memchr(b'\n', buf) {
+            let mut line: Vec<u8> = buf.drain(..=pos).collect();
+            line.pop();
+            lines.push(line);
+        }
+        lines
+            .into_iter()
+            .map(|line| self.line(source, line))
+            .collect()
+    }
+
+    fn finish(&mut self) -> Vec<LogLine> {
+        [LogSource::StdOut, LogSource::StdErr]
+            .into_iter()
+            .filter_map(|source| {
+                let line = std::mem::take(self.buffer(source));
+                (!line.is_empty()).then(|| self.line(source, line))
+            })
+            .collect()
+    }
+
+    fn buffer(&mut self, source: LogSource) -> &mut Vec<u8> {
+        match source {
+            LogSource::StdOut => &mut self.stdout,
+            LogSource::StdErr => &mut self.stderr,
+        }
+    }
+
+    fn line(&self, source: LogSource, line: Vec<u8>) -> LogLine {
+        let line = Bytes::from(line);
+        let (timestamp, content) = if self.timestamped {
+            split_timestamp(line)
+        } else {
+            (None, line)
+        };
+        LogLine {
+            source,
+            timestamp,
+            content,
+        }
+    }
+}
+
+fn split_timestamp(line: Bytes) -> (Option<SystemTime>, Bytes) {
+    let Some(pos) = memchr::memchr(b' ', &line) else {
+        return (None, line);
+    };
+    let timestamp = std::str::from_utf8(&line[..pos])
+        .ok()
+        .and_then(|timestamp| humantime::parse_rfc3339(timestamp).ok());
+    match timestamp {
+        Some(timestamp) => (Some(timestamp), line.slice(pos + 1..)),
+        None => (None, line),
+    }
+}
+
+impl fmt::Debug for LogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogStream")
+            .field("timestamped", &self.timestamped)
+            .finish()
+    }
+}
+
 impl Stream for LogStream {
     type Item = Result<LogFrame, io::Error>;
 
@@ -104,3 +239,128 @@ impl Stream for LogStream {
         this.inner.poll_next(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn frames(frames: Vec<LogFrame>) -> LogStream {
+        LogStream::new(futures::stream::iter(frames.into_iter().map(Ok)).boxed())
+    }
+
+    #[tokio::test]
+    async fn console_output_is_read_as_stdout() {
+        let output = futures::stream::iter([
+            Ok(bollard::container::LogOutput::Console {
+                message: "with a tty\n".into(),
+            }),
+            Ok(bollard::container::LogOutput::StdErr {
+                message: "error\n".into(),
+            }),
+        ]);
+        let lines: Vec<LogLine> = LogStream::from(output)
+            .into_lines()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].source().is_stdout());
+        assert_eq!(lines[0].content(), "with a tty");
+        assert!(lines[1].source().is_stderr());
+    }
+
+    #[tokio::test]
+    async fn stdin_frames_are_skipped() {
+        let output = futures::stream::iter([
+            Ok(bollard::container::LogOutput::StdIn {
+                message: "input\n".into(),
+            }),
+            Ok(bollard::container::LogOutput::StdOut {
+                message: "output\n".into(),
+            }),
+        ]);
+        let lines: Vec<LogLine> = LogStream::from(output)
+            .into_lines()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].source().is_stdout());
+        assert_eq!(lines[0].content(), "output");
+    }
+
+    #[tokio::test]
+    async fn into_lines_assembles_lines_per_source() {
+        let lines: Vec<LogLine> = frames(vec![
+            LogFrame::StdOut("first ".into()),
+            LogFrame::StdErr("error\n".into()),
+            LogFrame::StdOut("line\nsecond".into()),
+            LogFrame::StdOut(" line".into()),
+        ])
+        .into_lines()
+        .try_collect()
+        .await
+        .unwrap();
+
+        let lines: Vec<_> = lines
+            .iter()
+            .map(|line| (line.source().to_string(), line.content().clone()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                ("stderr".to_string(), Bytes::from("error")),
+                ("stdout".to_string(), Bytes::from("first line")),
+                ("stdout".to_string(), Bytes::from("second line")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn into_lines_parses_timestamps() {
+        let lines: Vec<LogLine> = frames(vec![LogFrame::StdOut(
+            "2024-05-01T10:00:00.5Z ready\nnot-a-timestamp message\n".into(),
+        )])
+        .with_timestamps()
+        .into_lines()
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            lines[0].timestamp(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_714_557_600_500))
+        );
+        assert_eq!(lines[0].content(), "ready");
+        assert_eq!(lines[1].timestamp(), None);
+        assert_eq!(lines[1].content(), "not-a-timestamp message");
+    }
+
+    #[tokio::test]
+    async fn merge_is_inverse_of_split() {
+        let (stdout, stderr) = frames(vec![
+            LogFrame::StdOut("out".into()),
+            LogFrame::StdErr("err".into()),
+        ])
+        .split()
+        .await;
+
+        let mut merged: Vec<_> = LogStream::merge(stdout, stderr)
+            .map_ok(|frame| (frame.source().to_string(), frame.bytes().clone()))
+            .try_collect()
+            .await
+            .unwrap();
+        merged.sort();
+        assert_eq!(
+            merged,
+            [
+                ("stderr".to_string(), Bytes::from("err")),
+                ("stdout".to_string(), Bytes::from("out")),
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 /// Represents a filesystem mount.
 /// For more information see [Docker Storage](https://docs.docker.com/storage/) documentation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Mount {
     access_mode: AccessMode,
     mount_type: MountType,
@@ -8,19 +8,22 @@ pub struct Mount {
     target: Option<String>,
 }
 
-#[derive(parse_display::Display, Debug, Copy, Clone)]
+#[derive(parse_display::Display, Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 #[display(style = "snake_case")]
+#[serde(rename_all = "snake_case")]
 pub enum MountType {
     Bind,
     Volume,
     Tmpfs,
 }
 
-#[derive(parse_display::Display, Debug, Copy, Clone)]
+#[derive(parse_display::Display, Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AccessMode {
     #[display("ro")]
+    #[serde(rename = "ro")]
     ReadOnly,
     #[display("rw")]
+    #[serde(rename = "rw")]
     ReadWrite,
 }
 
@@ -40,7 +43,8 @@ impl Mount {
 
     /// Creates a named `volume`.
     /// Can be used to share data between containers or persist data on the host system.
-    /// The volume isn't removed when the container is removed.
+    /// The volume isn't removed when the container is removed, see [`Volume`](crate::core::Volume)
+    /// for a volume removed on drop.
     ///
     /// See [volumes documentation](https://docs.docker.com/storage/volumes/) for more information.
     pub fn volume_mount(name: impl Into<String>, container_path: impl Into<String>) -> Self {
@@ -1,15 +1,22 @@
 use std::{
     collections::HashMap,
     fmt,
+    hash::{BuildHasher, RandomState},
     sync::{Arc, OnceLock, Weak},
 };
 
+use bollard::network::CreateNetworkOptions;
+use bollard_stubs::models::{EndpointIpamConfig, EndpointSettings, Ipam, IpamConfig};
 use tokio::sync::Mutex;
 
-use crate::core::{
-    async_drop,
-    client::{Client, ClientError},
-    env,
+use crate::{
+    core::{
+        async_drop,
+        client::{Client, ClientError},
+        env,
+        error::Result,
+    },
+    ContainerAsync, Image,
 };
 
 pub(crate) static CREATED_NETWORKS: OnceLock<Mutex<HashMap<String, Weak<Network>>>> =
@@ -19,17 +26,92 @@ fn created_networks() -> &'static Mutex<HashMap<String, Weak<Network>>> {
     CREATED_NETWORKS.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-pub(crate) struct Network {
+/// A docker network managed by Testcontainers.
+///
+/// Networks are created either implicitly, by starting a container with
+/// [`ImageExt::with_network`](crate::ImageExt::with_network), or explicitly with [`Network::builder`].
+/// A network is removed once it's dropped and no longer used by containers started with it.
+///
+/// # Example
+/// ```rust,no_run
+/// use testcontainers::{core::Network, runners::AsyncRunner, GenericImage, ImageExt};
+///
+/// # async fn example() -> Result<(), testcontainers::TestcontainersError> {
+/// let network = Network::builder()
+///     .with_subnet("172.30.0.0/16")
+///     .with_label("purpose", "kafka-tests")
+///     .create()
+///     .await?;
+/// let kafka = GenericImage::new("kafka", "latest")
+///     .with_network(network.name())
+///     .start()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Network {
     name: String,
     id: String,
     client: Arc<Client>,
 }
 
+/// Builder of a [`Network`] with a custom configuration.
+#[must_use]
+#[derive(Debug, Clone, Default)]
+pub struct NetworkBuilder {
+    name: Option<String>,
+    driver: Option<String>,
+    subnet: Option<String>,
+    ip_range: Option<String>,
+    gateway: Option<String>,
+    internal: bool,
+    enable_ipv6: bool,
+    labels: HashMap<String, String>,
+    docker_host: Option<String>,
+}
+
+/// Configuration of a container connected to a [`Network`].
+#[must_use]
+#[derive(Debug, Clone, Default)]
+pub struct NetworkEndpoint {
+    aliases: Vec<String>,
+    ipv4_address: Option<String>,
+    ipv6_address: Option<String>,
+    mac_address: Option<String>,
+}
+
 impl Network {
+    /// Returns a builder of a new network.
+    pub fn builder() -> NetworkBuilder {
+        NetworkBuilder::default()
+    }
+
+    /// Returns the name of the network.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the id of the network.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Connects the started container to the network, in addition to the networks it's already connected to.
+    pub async fn connect<I: Image>(
+        &self,
+        container: &ContainerAsync<I>,
+        endpoint: NetworkEndpoint,
+    ) -> Result<()> {
+        self.client
+            .connect_network(&self.name, container.id(), endpoint.into())
+            .await?;
+        Ok(())
+    }
+
     pub(crate) async fn new(
         name: impl Into<String>,
         client: Arc<Client>,
-    ) -> Result<Option<Arc<Self>>, ClientError> {
+    ) -> std::result::Result<Option<Arc<Self>>, ClientError> {
         let name = name.into();
         let mut guard = created_networks().lock().await;
         let network = if let Some(network) = guard.get(&name).and_then(Weak::upgrade) {
@@ -40,7 +122,11 @@ impl Network {
                 return Ok(None);
             }
 
-            let id = client.create_network(&name).await?;
+            let options = CreateNetworkOptions {
+                name: name.clone(),
+                ..Default::default()
+            };
+            let id = client.create_network(options).await?;
 
             let created = Arc::new(Self {
                 name: name.clone(),
@@ -57,6 +143,172 @@ impl Network {
     }
 }
 
+impl NetworkBuilder {
+    /// Sets the name of the network. By default, a random name is generated.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the driver of the network. The default is `bridge`.
+    pub fn with_driver(mut self, driver: impl Into<String>) -> Self {
+        self.driver = Some(driver.into());
+        self
+    }
+
+    /// Sets the subnet of the network in CIDR format, e.g. `172.30.0.0/16`.
+    ///
+    /// Required to assign static IP addresses to containers, see [`NetworkEndpoint::with_ipv4_address`].
+    pub fn with_subnet(mut self, subnet: impl Into<String>) -> Self {
+        self.subnet = Some(subnet.into());
+        self
+    }
+
+    /// Sets the range of the subnet to allocate container IP addresses from, in CIDR format.
+    pub fn with_ip_range(mut self, ip_range: impl Into<String>) -> Self {
+        self.ip_range = Some(ip_range.into());
+        self
+    }
+
+    /// Sets the gateway of the subnet.
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = Some(gateway.into());
+        self
+    }
+
+    /// Restricts external access to the network.
+    pub fn with_internal(mut self, internal: bool) -> Self {
+        self.internal = internal;
+        self
+    }
+
+    /// Enables IPv6 networking.
+    pub fn with_ipv6(mut self, enable_ipv6: bool) -> Self {
+        self.enable_ipv6 = enable_ipv6;
+        self
+    }
+
+    /// Adds a label to the network.
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds the labels to the network.
+    pub fn with_labels(
+        mut self,
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.labels
+            .extend(labels.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Sets the Docker host the network is created on, e.g. `tcp://other-daemon:2375`,
+    /// for containers started with [`ImageExt::with_docker_host`](crate::ImageExt::with_docker_host).
+    pub fn with_docker_host(mut self, docker_host: impl Into<String>) -> Self {
+        self.docker_host = Some(docker_host.into());
+        self
+    }
+
+    /// Creates the network.
+    ///
+    /// Containers can join it with [`ImageExt::with_network`](crate::ImageExt::with_network) using its [name](Network::name),
+    /// or be connected after start with [`Network::connect`].
+    pub async fn create(self) -> Result<Arc<Network>> {
+        let client = Client::lazy_client_for(self.docker_host.as_deref()).await?;
+        let name = self
+            .name
+            .unwrap_or_else(|| format!("testcontainers-{:016x}", RandomState::new().hash_one(0u8)));
+
+        let has_ipam_config =
+            self.subnet.is_some() || self.ip_range.is_some() || self.gateway.is_some();
+        let ipam = Ipam {
+            config: has_ipam_config.then(|| {
+                vec![IpamConfig {
+                    subnet: self.subnet,
+                    ip_range: self.ip_range,
+                    gateway: self.gateway,
+                    ..Default::default()
+                }]
+            }),
+            ..Default::default()
+        };
+        let options = CreateNetworkOptions {
+            name: name.clone(),
+            driver: self.driver.unwrap_or_default(),
+            internal: self.internal,
+            enable_ipv6: self.enable_ipv6,
+            ipam,
+            labels: self.labels,
+            ..Default::default()
+        };
+
+        let mut guard = created_networks().lock().await;
+        let id = client.create_network(options).await?;
+        let network = Arc::new(Network { name, id, client });
+        // allows containers to share the network by its name
+        guard.insert(network.name.clone(), Arc::downgrade(&network));
+
+        Ok(network)
+    }
+}
+
+impl NetworkEndpoint {
+    /// Creates a configuration with no aliases and a dynamically assigned IP address.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a DNS alias the container is resolvable by in the network.
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Adds DNS aliases the container is resolvable by in the network.
+    pub fn with_aliases(mut self, aliases: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.aliases.extend(aliases.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets a static IPv4 address of the container in the network.
+    ///
+    /// The network must be created with a [subnet](NetworkBuilder::with_subnet) the address belongs to.
+    pub fn with_ipv4_address(mut self, address: impl Into<String>) -> Self {
+        self.ipv4_address = Some(address.into());
+        self
+    }
+
+    /// Sets a static IPv6 address of the container in the network.
+    pub fn with_ipv6_address(mut self, address: impl Into<String>) -> Self {
+        self.ipv6_address = Some(address.into());
+        self
+    }
+
+    /// Sets the MAC address of the container in the network (e.g. `02:42:ac:11:00:42`).
+    pub fn with_mac_address(mut self, address: impl Into<String>) -> Self {
+        self.mac_address = Some(address.into());
+        self
+    }
+}
+
+impl From<NetworkEndpoint> for EndpointSettings {
+    fn from(endpoint: NetworkEndpoint) -> Self {
+        let has_static_address = endpoint.ipv4_address.is_some() || endpoint.ipv6_address.is_some();
+        EndpointSettings {
+            aliases: (!endpoint.aliases.is_empty()).then_some(endpoint.aliases),
+            ipam_config: has_static_address.then_some(EndpointIpamConfig {
+                ipv4_address: endpoint.ipv4_address,
+                ipv6_address: endpoint.ipv6_address,
+                ..Default::default()
+            }),
+            mac_address: endpoint.mac_address,
+            ..Default::default()
+        }
+    }
+}
+
 impl Drop for Network {
     fn drop(&mut self) {
         if self.client.config.command() == env::Command::Remove {
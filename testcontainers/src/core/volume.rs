@@ -0,0 +1,179 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use bollard::volume::CreateVolumeOptions;
+
+use crate::core::{async_drop, client::Client, env, error::Result, Mount};
+
+/// A named docker volume managed by Testcontainers.
+///
+/// The volume is removed once it's dropped, unless `TESTCONTAINERS_COMMAND=keep` is set.
+/// Since the daemon refuses to remove a volume used by a container, the containers using it
+/// should be dropped first (e.g. declared after the volume).
+///
+/// Note that the volumes created implicitly by [`Mount::volume_mount`] aren't removed.
+///
+/// # Example
+/// ```rust,no_run
+/// use testcontainers::{core::Volume, runners::AsyncRunner, GenericImage, ImageExt};
+///
+/// # async fn example() -> Result<(), testcontainers::TestcontainersError> {
+/// let volume = Volume::builder()
+///     .with_label("purpose", "postgres-data")
+///     .create()
+///     .await?;
+/// let postgres = GenericImage::new("postgres", "16-alpine")
+///     .with_mount(volume.mount("/var/lib/postgresql/data"))
+///     .start()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Volume {
+    name: String,
+    client: Arc<Client>,
+    removed: bool,
+}
+
+/// Builder of a [`Volume`] with a custom configuration.
+#[must_use]
+#[derive(Debug, Clone, Default)]
+pub struct VolumeBuilder {
+    name: Option<String>,
+    driver: Option<String>,
+    driver_opts: HashMap<String, String>,
+    labels: HashMap<String, String>,
+}
+
+impl Volume {
+    /// Returns a builder of a new volume.
+    pub fn builder() -> VolumeBuilder {
+        VolumeBuilder::default()
+    }
+
+    /// Returns the name of the volume.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a mount of the volume at the path in a container.
+    pub fn mount(&self, container_path: impl Into<String>) -> Mount {
+        Mount::volume_mount(self.name.clone(), container_path)
+    }
+
+    /// Removes the volume right away, instead of on drop.
+    pub async fn remove(mut self) -> Result<()> {
+        self.removed = true;
+        self.client.remove_volume(&self.name, false).await?;
+        Ok(())
+    }
+
+    /// Lists the names of the volumes having all the labels, including the ones not created by Testcontainers.
+    pub async fn list(
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Result<Vec<String>> {
+        let labels: Vec<_> = labels
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key.into(), value.into()))
+            .collect();
+        let filters = if labels.is_empty() {
+            HashMap::new()
+        } else {
+            HashMap::from([("label".to_string(), labels)])
+        };
+
+        let client = Client::lazy_client().await?;
+        Ok(client.list_volumes(filters).await?)
+    }
+
+    /// Removes the volume by its name, e.g. a volume created implicitly by [`Mount::volume_mount`].
+    ///
+    /// If `force` is set, the volume is removed even if it's used by a container.
+    pub async fn remove_by_name(name: &str, force: bool) -> Result<()> {
+        let client = Client::lazy_client().await?;
+        client.remove_volume(name, force).await?;
+        Ok(())
+    }
+}
+
+impl VolumeBuilder {
+    /// Sets the name of the volume. By default, a random name is generated by the daemon.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the driver of the volume. The default is `local`.
+    pub fn with_driver(mut self, driver: impl Into<String>) -> Self {
+        self.driver = Some(driver.into());
+        self
+    }
+
+    /// Adds a driver specific option, e.g. `type=tmpfs` of the `local` driver.
+    pub fn with_driver_opt(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.driver_opts.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a label to the volume.
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds the labels to the volume.
+    pub fn with_labels(
+        mut self,
+        labels: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        self.labels
+            .extend(labels.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Creates the volume.
+    pub async fn create(self) -> Result<Volume> {
+        let client = Client::lazy_client().await?;
+        let options = CreateVolumeOptions {
+            name: self.name.unwrap_or_default(),
+            driver: self.driver.unwrap_or_default(),
+            driver_opts: self.driver_opts,
+            labels: self.labels,
+        };
+        let name = client.create_volume(options).await?;
+
+        Ok(Volume {
+            name,
+            client,
+            removed: false,
+        })
+    }
+}
+
+impl Drop for Volume {
+    fn drop(&mut self) {
+        if !self.removed && self.client.config.command() == env::Command::Remove {
+            let client = self.client.clone();
+            let name = self.name.clone();
+
+            let drop_task = async move {
+                log::trace!("Drop was called for volume {name}, cleaning up");
+                match client.remove_volume(&name, false).await {
+                    Ok(_) => {
+                        log::trace!("Volume {name} was successfully dropped");
+                    }
+                    Err(err) => {
+                        log::error!("Failed to remove volume {name} on drop: {err}");
+                    }
+                }
+            };
+
+            async_drop::async_drop(drop_task);
+        }
+    }
+}
+
+impl fmt::Debug for Volume {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Volume").field("name", &self.name).finish()
+    }
+}
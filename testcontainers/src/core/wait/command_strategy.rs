@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::{
+    core::{client::Client, wait::WaitStrategy, ExecCommand},
+    ContainerAsync, Image,
+};
+
+/// Waits for a command executed inside the container to exit with code `0`, repeating it until it does.
+///
+/// Useful for images providing a dedicated readiness probe, e.g. `pg_isready` for PostgreSQL.
+/// Only the exit code of the command is checked, ready conditions of the [`ExecCommand`] are ignored.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use testcontainers::core::{wait::CommandWaitStrategy, ExecCommand, WaitFor};
+///
+/// let ready = CommandWaitStrategy::new(ExecCommand::new(["pg_isready", "-U", "postgres"]))
+///     .with_poll_interval(Duration::from_millis(500));
+/// let wait_for = WaitFor::from(ready);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandWaitStrategy {
+    command: ExecCommand,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl CommandWaitStrategy {
+    /// Create a new `CommandWaitStrategy` for the command.
+    pub fn new(command: ExecCommand) -> Self {
+        Self {
+            command,
+            poll_interval: Duration::from_millis(100),
+            timeout: None,
+        }
+    }
+
+    /// Set the interval between the end of a failed execution and the next one.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl WaitStrategy for CommandWaitStrategy {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        _client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        loop {
+            let output = container.exec_output(&self.command).await?;
+            if output.is_success() {
+                log::debug!("Command {:?} succeeded", output.cmd());
+                break;
+            }
+            log::debug!(
+                "Command {:?} exited with code {}: {}",
+                output.cmd(),
+                output.exit_code(),
+                String::from_utf8_lossy(output.stderr()).trim_end()
+            );
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        Ok(())
+    }
+}
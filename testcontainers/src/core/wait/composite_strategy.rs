@@ -0,0 +1,133 @@
+use std::time::{Duration, SystemTime};
+
+use futures::future::{self, BoxFuture, FutureExt};
+
+use crate::{
+    core::{
+        client::Client,
+        wait::{WaitFor, WaitStrategy},
+    },
+    ContainerAsync, Image,
+};
+
+/// Represents a combination of wait conditions, satisfied when all (or any) of them are met.
+///
+/// The conditions are awaited concurrently, optionally within a dedicated deadline.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+///
+/// use testcontainers::core::{wait::CompositeWaitStrategy, WaitFor};
+///
+/// // either the log line or the healthy status, whichever comes first
+/// let ready = CompositeWaitStrategy::any([
+///     WaitFor::message_on_stdout("server started"),
+///     WaitFor::healthcheck(),
+/// ])
+/// .with_timeout(Duration::from_secs(30));
+/// let wait_for = WaitFor::from(ready);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompositeWaitStrategy {
+    mode: Mode,
+    conditions: Vec<WaitFor>,
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    All,
+    Any,
+}
+
+impl CompositeWaitStrategy {
+    /// Create a strategy satisfied once all the conditions are met.
+    pub fn all(conditions: impl IntoIterator<Item = WaitFor>) -> Self {
+        Self::new(Mode::All, conditions)
+    }
+
+    /// Create a strategy satisfied as soon as any of the conditions is met.
+    ///
+    /// Failed conditions are ignored unless all of them fail.
+    pub fn any(conditions: impl IntoIterator<Item = WaitFor>) -> Self {
+        Self::new(Mode::Any, conditions)
+    }
+
+    fn new(mode: Mode, conditions: impl IntoIterator<Item = WaitFor>) -> Self {
+        Self {
+            mode,
+            conditions: conditions.into_iter().collect(),
+            timeout: None,
+        }
+    }
+
+    /// Set the deadline for the combined conditions to be met.
+    ///
+    /// Otherwise, the strategy is limited only by the startup timeout of the container.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub(crate) fn conditions(&self) -> &[WaitFor] {
+        &self.conditions
+    }
+
+    pub(crate) fn with_logs_since(self, since: SystemTime) -> Self {
+        Self {
+            conditions: self
+                .conditions
+                .into_iter()
+                .map(|condition| condition.with_logs_since(since))
+                .collect(),
+            ..self
+        }
+    }
+
+    async fn wait<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        // the conditions may be composite as well, boxing breaks the recursion of futures
+        let waits = self
+            .conditions
+            .iter()
+            .map(|condition| wait_boxed(condition, client, container));
+
+        match self.mode {
+            Mode::All => future::try_join_all(waits).await.map(|_| ()),
+            Mode::Any => {
+                let waits: Vec<_> = waits.collect();
+                if waits.is_empty() {
+                    return Ok(());
+                }
+                future::select_ok(waits).await.map(|_| ())
+            }
+        }
+    }
+}
+
+fn wait_boxed<'a, I: Image>(
+    condition: &'a WaitFor,
+    client: &'a Client,
+    container: &'a ContainerAsync<I>,
+) -> BoxFuture<'a, crate::core::error::Result<()>> {
+    condition.wait_until_ready(client, container).boxed()
+}
+
+impl WaitStrategy for CompositeWaitStrategy {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        // the deadline is applied by `WaitFor`, the same way as for the other strategies
+        self.wait(client, container).await
+    }
+}
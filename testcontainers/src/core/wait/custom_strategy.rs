@@ -0,0 +1,62 @@
+use std::{fmt::Debug, sync::Arc};
+
+use futures::future::BoxFuture;
+
+use crate::core::{error::Result, ContainerHandle};
+
+/// A user-defined wait strategy, see [`WaitFor::custom`](crate::core::WaitFor::custom).
+///
+/// The strategy is borrowed while waiting rather than consumed, so it can keep state
+/// (e.g. a connection pool or counters) using interior mutability, and be shared by multiple
+/// containers by wrapping it into an [`Arc`].
+///
+/// # Example
+/// ```rust
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// use futures::{future::BoxFuture, FutureExt};
+/// use testcontainers::core::{wait::CustomWaitStrategy, ContainerHandle, WaitFor};
+///
+/// #[derive(Debug, Default)]
+/// struct CountingWait {
+///     attempts: AtomicUsize,
+/// }
+///
+/// impl CustomWaitStrategy for CountingWait {
+///     fn wait_until_ready<'a>(
+///         &'a self,
+///         container: &'a ContainerHandle,
+///     ) -> BoxFuture<'a, testcontainers::core::error::Result<()>> {
+///         async move {
+///             while !container.exec(["test", "-f", "/tmp/ready"]).await?.is_success() {
+///                 self.attempts.fetch_add(1, Ordering::Relaxed);
+///                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+///             }
+///             Ok(())
+///         }
+///         .boxed()
+///     }
+/// }
+///
+/// let strategy = Arc::new(CountingWait::default());
+/// let first = WaitFor::custom(strategy.clone());
+/// let second = WaitFor::custom(strategy);
+/// ```
+pub trait CustomWaitStrategy: Debug + Send + Sync {
+    /// Waits until the container is ready, failing if it never can be.
+    ///
+    /// The waiting is limited by the startup timeout of the container.
+    fn wait_until_ready<'a>(&'a self, container: &'a ContainerHandle) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<S> CustomWaitStrategy for Arc<S>
+where
+    S: CustomWaitStrategy + ?Sized,
+{
+    fn wait_until_ready<'a>(&'a self, container: &'a ContainerHandle) -> BoxFuture<'a, Result<()>> {
+        (**self).wait_until_ready(container)
+    }
+}
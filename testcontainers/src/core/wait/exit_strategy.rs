@@ -9,6 +9,7 @@ use crate::{
 pub struct ExitWaitStrategy {
     expected_code: Option<i64>,
     poll_interval: Duration,
+    timeout: Option<Duration>,
 }
 
 impl ExitWaitStrategy {
@@ -17,6 +18,7 @@ impl ExitWaitStrategy {
         Self {
             expected_code: None,
             poll_interval: Duration::from_millis(100),
+            timeout: None,
         }
     }
 
@@ -26,6 +28,16 @@ impl ExitWaitStrategy {
         self
     }
 
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Set the expected exit code of the container.
     pub fn with_exit_code(mut self, expected_code: i64) -> Self {
         self.expected_code = Some(expected_code);
@@ -35,7 +47,7 @@ impl ExitWaitStrategy {
 
 impl WaitStrategy for ExitWaitStrategy {
     async fn wait_until_ready<I: Image>(
-        self,
+        &self,
         client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()> {
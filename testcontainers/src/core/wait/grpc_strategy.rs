@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use tonic::transport::Endpoint;
+use tonic_health::pb::{
+    health_check_response::ServingStatus, health_client::HealthClient, HealthCheckRequest,
+};
+use url::Host;
+
+use crate::{
+    core::{client::Client, error::WaitContainerError, wait::WaitStrategy, ContainerPort},
+    ContainerAsync, Image,
+};
+
+/// Error type for waiting for container readiness based on gRPC health checks.
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcWaitError {
+    #[error("container has no exposed ports")]
+    NoExposedPortsForGrpcWait,
+    #[error("invalid endpoint: {0}")]
+    InvalidEndpoint(#[from] tonic::transport::Error),
+}
+
+/// Represents a strategy for waiting until the container reports `SERVING` status
+/// through the standard [gRPC health checking protocol] (`grpc.health.v1.Health/Check`).
+///
+/// # Example
+/// ```rust,no_run
+/// use testcontainers::{
+///     core::{wait::GrpcHealthWaitStrategy, IntoContainerPort, WaitFor},
+///     GenericImage, ImageExt,
+/// };
+///
+/// let request = GenericImage::new("my-grpc-service", "latest")
+///     .with_exposed_port(50051.tcp())
+///     .with_wait_for(WaitFor::grpc(
+///         GrpcHealthWaitStrategy::new().with_service("my.package.MyService"),
+///     ));
+/// ```
+///
+/// [gRPC health checking protocol]: https://github.com/grpc/grpc/blob/master/doc/health-checking.md
+#[derive(Debug, Clone)]
+pub struct GrpcHealthWaitStrategy {
+    port: Option<ContainerPort>,
+    service: String,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl GrpcHealthWaitStrategy {
+    /// Create a new `GrpcHealthWaitStrategy` checking the overall health of the server.
+    pub fn new() -> Self {
+        Self {
+            port: None,
+            service: String::new(),
+            poll_interval: Duration::from_millis(100),
+            timeout: None,
+        }
+    }
+
+    /// Set the port to be used for the health check.
+    ///
+    /// It will use mapped host port for the passed container port. By default, first exposed port is used.
+    pub fn with_port(mut self, port: ContainerPort) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    pub(crate) fn port(&self) -> Option<ContainerPort> {
+        self.port
+    }
+
+    /// Set the name of the service to check, instead of the overall health of the server.
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = service.into();
+        self
+    }
+
+    /// Set the poll interval for the wait strategy.
+    ///
+    /// This is the time to wait between each poll for the expected condition to be met.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    async fn check(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<ServingStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let channel = endpoint.connect().await?;
+        let response = HealthClient::new(channel)
+            .check(HealthCheckRequest {
+                service: self.service.clone(),
+            })
+            .await?;
+
+        Ok(response.into_inner().status())
+    }
+}
+
+impl Default for GrpcHealthWaitStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitStrategy for GrpcHealthWaitStrategy {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        _client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        let host = container.get_host().await?;
+        let container_port = self
+            .port
+            .or_else(|| container.image().expose_ports().first().copied())
+            .ok_or(WaitContainerError::from(
+                GrpcWaitError::NoExposedPortsForGrpcWait,
+            ))?;
+
+        let host_port = match host {
+            Host::Domain(ref domain) => match container.get_host_port_ipv4(container_port).await {
+                Ok(port) => port,
+                Err(_) => {
+                    log::debug!("IPv4 port not found for domain: {domain}, checking for IPv6");
+                    container.get_host_port_ipv6(container_port).await?
+                }
+            },
+            Host::Ipv4(_) => container.get_host_port_ipv4(container_port).await?,
+            Host::Ipv6(_) => container.get_host_port_ipv6(container_port).await?,
+        };
+
+        let endpoint = Endpoint::from_shared(format!("http://{host}:{host_port}"))
+            .map_err(GrpcWaitError::from)
+            .map_err(WaitContainerError::from)?;
+
+        self.wait_serving(&endpoint).await;
+        Ok(())
+    }
+}
+
+impl GrpcHealthWaitStrategy {
+    /// Polls the endpoint until the service is reported as `SERVING`.
+    async fn wait_serving(&self, endpoint: &Endpoint) {
+        loop {
+            match self.check(endpoint).await {
+                Ok(ServingStatus::Serving) => {
+                    log::debug!("gRPC health check condition met");
+                    break;
+                }
+                Ok(status) => {
+                    log::debug!("gRPC health check condition not met, status: {status:?}");
+                }
+                Err(err) => {
+                    log::debug!("Error while waiting for gRPC health check: {err}");
+                }
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_stream::wrappers::TcpListenerStream;
+    use tonic_health::server::HealthReporter;
+
+    use super::*;
+    use crate::core::WaitFor;
+
+    /// Serves the health checking protocol on a random local port.
+    async fn health_server() -> (HealthReporter, Endpoint) {
+        let (reporter, service) = tonic_health::server::health_reporter();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint =
+            Endpoint::from_shared(format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .serve_with_incoming(service, TcpListenerStream::new(listener)),
+        );
+        (reporter, endpoint)
+    }
+
+    #[tokio::test]
+    async fn status_of_the_checked_service_is_reported() {
+        let (reporter, endpoint) = health_server().await;
+        reporter
+            .set_service_status("my.Service", tonic_health::ServingStatus::NotServing)
+            .await;
+
+        let overall = GrpcHealthWaitStrategy::new();
+        assert_eq!(
+            overall.check(&endpoint).await.unwrap(),
+            ServingStatus::Serving
+        );
+
+        let service = GrpcHealthWaitStrategy::new().with_service("my.Service");
+        assert_eq!(
+            service.check(&endpoint).await.unwrap(),
+            ServingStatus::NotServing
+        );
+        reporter
+            .set_service_status("my.Service", tonic_health::ServingStatus::Serving)
+            .await;
+        assert_eq!(
+            service.check(&endpoint).await.unwrap(),
+            ServingStatus::Serving
+        );
+
+        let unknown = GrpcHealthWaitStrategy::new().with_service("unknown.Service");
+        assert!(unknown.check(&endpoint).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn waits_until_the_service_is_serving() {
+        let (reporter, endpoint) = health_server().await;
+        reporter
+            .set_service_status("my.Service", tonic_health::ServingStatus::NotServing)
+            .await;
+        let strategy = GrpcHealthWaitStrategy::new()
+            .with_service("my.Service")
+            .with_poll_interval(Duration::from_millis(10));
+
+        let not_serving =
+            tokio::time::timeout(Duration::from_millis(300), strategy.wait_serving(&endpoint))
+                .await;
+        assert!(not_serving.is_err());
+
+        reporter
+            .set_service_status("my.Service", tonic_health::ServingStatus::Serving)
+            .await;
+        tokio::time::timeout(Duration::from_secs(5), strategy.wait_serving(&endpoint))
+            .await
+            .expect("the service is serving");
+    }
+
+    #[test]
+    fn own_timeout_takes_precedence() {
+        let own = Duration::from_secs(5);
+        let condition = WaitFor::grpc(GrpcHealthWaitStrategy::new().with_timeout(own));
+        assert_eq!(condition.timeout(), Some(own));
+        assert_eq!(
+            condition
+                .with_default_timeout(Duration::from_secs(30))
+                .timeout(),
+            Some(own)
+        );
+        assert_eq!(WaitFor::grpc(GrpcHealthWaitStrategy::new()).timeout(), None);
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct HealthWaitStrategy {
     poll_interval: Duration,
+    timeout: Option<Duration>,
 }
 
 impl HealthWaitStrategy {
@@ -17,6 +18,7 @@ impl HealthWaitStrategy {
     pub fn new() -> Self {
         Self {
             poll_interval: Duration::from_millis(100),
+            timeout: None,
         }
     }
 
@@ -25,11 +27,21 @@ impl HealthWaitStrategy {
         self.poll_interval = poll_interval;
         self
     }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl WaitStrategy for HealthWaitStrategy {
     async fn wait_until_ready<I: Image>(
-        self,
+        &self,
         client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()> {
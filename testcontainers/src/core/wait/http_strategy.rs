@@ -15,6 +15,8 @@ pub enum HttpWaitError {
     NoExposedPortsForHttpWait,
     #[error("invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
+    #[error("invalid TLS configuration: {0}")]
+    InvalidTlsConfig(reqwest::Error),
 }
 
 /// Represents a strategy for waiting for a certain HTTP response.
@@ -28,8 +30,10 @@ pub struct HttpWaitStrategy {
     body: Option<Bytes>,
     auth: Option<Auth>,
     use_tls: bool,
+    tls: TlsConfig,
     response_matcher: Option<ResponseMatcher>,
     poll_interval: Duration,
+    timeout: Option<Duration>,
 }
 
 type ResponseMatcher = Arc<
@@ -42,6 +46,14 @@ enum Auth {
     Bearer(String),
 }
 
+/// TLS settings of the default client, PEM-encoded certificates and keys are kept as is.
+#[derive(Clone, Default)]
+struct TlsConfig {
+    accept_invalid_certs: bool,
+    root_certificates: Vec<Bytes>,
+    client_identity: Option<Bytes>,
+}
+
 impl HttpWaitStrategy {
     /// Create a new `HttpWaitStrategy` for the given resource path (using GET method by default).
     pub fn new(path: impl Into<String>) -> Self {
@@ -54,11 +66,20 @@ impl HttpWaitStrategy {
             body: None,
             auth: None,
             use_tls: false,
+            tls: TlsConfig::default(),
             response_matcher: None,
             poll_interval: Duration::from_millis(100),
+            timeout: None,
         }
     }
 
+    /// Create a new `HttpWaitStrategy` for the given resource path, using `https` scheme.
+    ///
+    /// Shortcut for `HttpWaitStrategy::new(path).with_tls()`.
+    pub fn https(path: impl Into<String>) -> Self {
+        Self::new(path).with_tls()
+    }
+
     /// Set the port to be used for the request.
     ///
     /// It will use mapped host port for the passed container port. By default, first exposed port is used.
@@ -67,9 +88,14 @@ impl HttpWaitStrategy {
         self
     }
 
+    pub(crate) fn port(&self) -> Option<ContainerPort> {
+        self.port
+    }
+
     /// Set the custom client for the request.
     ///
     /// Allows to customize the client, enabling features like TLS, accept_invalid_certs, proxies, etc.
+    /// The TLS settings of the strategy (e.g. [`HttpWaitStrategy::with_root_certificate`]) are ignored in this case.
     /// If you need to use particular features of `reqwest`, just add `reqwest` to your dependencies with desired features enabled.
     /// After that, you can create a client with the desired configuration and pass it to the wait strategy.
     pub fn with_client(mut self, client: reqwest::Client) -> Self {
@@ -122,12 +148,44 @@ impl HttpWaitStrategy {
 
     /// Use TLS for the request.
     ///
-    /// This will use `https` scheme for the request. TLS configuration can be customized using the [`HttpWaitStrategy::with_client`].
+    /// This will use `https` scheme for the request. TLS configuration can be customized using
+    /// [`HttpWaitStrategy::with_root_certificate`], [`HttpWaitStrategy::with_client_identity`]
+    /// and [`HttpWaitStrategy::with_danger_accept_invalid_certs`], or the [`HttpWaitStrategy::with_client`].
     pub fn with_tls(mut self) -> Self {
         self.use_tls = true;
         self
     }
 
+    /// Accept any certificate presented by the container, including self-signed and expired ones.
+    /// Enables TLS for the request.
+    ///
+    /// Only meant for test services, as it makes the connection vulnerable to man-in-the-middle attacks.
+    pub fn with_danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.tls.accept_invalid_certs = accept_invalid_certs;
+        self.with_tls()
+    }
+
+    /// Trust the PEM-encoded root certificate (e.g. the CA which signed the certificate of the service),
+    /// in addition to the system ones. Enables TLS for the request.
+    pub fn with_root_certificate(mut self, pem: impl Into<Bytes>) -> Self {
+        self.tls.root_certificates.push(pem.into());
+        self.with_tls()
+    }
+
+    /// Authenticate with the PEM-encoded client certificate (chain) and its private key,
+    /// for services requiring mutual TLS. Enables TLS for the request.
+    pub fn with_client_identity(
+        mut self,
+        certificate_pem: impl AsRef<[u8]>,
+        private_key_pem: impl AsRef<[u8]>,
+    ) -> Self {
+        let mut identity = certificate_pem.as_ref().to_vec();
+        identity.push(b'\n');
+        identity.extend_from_slice(private_key_pem.as_ref());
+        self.tls.client_identity = Some(identity.into());
+        self.with_tls()
+    }
+
     /// Set the poll interval for the wait strategy.
     ///
     /// This is the time to wait between each poll for the expected condition to be met.
@@ -136,6 +194,16 @@ impl HttpWaitStrategy {
         self
     }
 
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Wait for expected status code.
     /// Shortcut for `with_response_matcher(|response| response.status() == status)`.
     pub fn with_expected_status_code(self, status: impl Into<u16>) -> Self {
@@ -177,19 +245,42 @@ impl HttpWaitStrategy {
         self.response_matcher.clone()
     }
 
-    pub(crate) fn into_request(
-        self,
+    /// Returns the custom client, or builds the default one with the TLS settings of the strategy.
+    fn client(&self) -> Result<reqwest::Client, HttpWaitError> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+
+        let mut builder =
+            reqwest::Client::builder().danger_accept_invalid_certs(self.tls.accept_invalid_certs);
+        for pem in &self.tls.root_certificates {
+            let certificate =
+                reqwest::Certificate::from_pem(pem).map_err(HttpWaitError::InvalidTlsConfig)?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(pem) = &self.tls.client_identity {
+            let identity =
+                reqwest::Identity::from_pem(pem).map_err(HttpWaitError::InvalidTlsConfig)?;
+            builder = builder.identity(identity);
+        }
+        builder.build().map_err(HttpWaitError::InvalidTlsConfig)
+    }
+
+    pub(crate) fn request(
+        &self,
+        client: &reqwest::Client,
         base_url: &Url,
     ) -> Result<reqwest::RequestBuilder, HttpWaitError> {
-        let client = self.client.unwrap_or_default();
         let url = base_url.join(&self.path).map_err(HttpWaitError::from)?;
-        let mut request = client.request(self.method, url).headers(self.headers);
+        let mut request = client
+            .request(self.method.clone(), url)
+            .headers(self.headers.clone());
 
-        if let Some(body) = self.body {
-            request = request.body(body);
+        if let Some(body) = &self.body {
+            request = request.body(body.clone());
         }
 
-        if let Some(auth) = self.auth {
+        if let Some(auth) = &self.auth {
             match auth {
                 Auth::Basic { username, password } => {
                     request = request.basic_auth(username, Some(password));
@@ -206,7 +297,7 @@ impl HttpWaitStrategy {
 
 impl WaitStrategy for HttpWaitStrategy {
     async fn wait_until_ready<I: Image>(
-        self,
+        &self,
         _client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()> {
@@ -230,6 +321,9 @@ impl WaitStrategy for HttpWaitStrategy {
             Host::Ipv6(_) => container.get_host_port_ipv6(container_port).await?,
         };
 
+        // the client is built once, as parsing of the certificates can fail
+        let client = self.client().map_err(WaitContainerError::from)?;
+
         let scheme = if self.use_tls { "https" } else { "http" };
         let base_url = Url::parse(&format!("{scheme}://{host}:{host_port}"))
             .map_err(HttpWaitError::from)
@@ -242,8 +336,7 @@ impl WaitStrategy for HttpWaitStrategy {
                 )));
             };
             let result = self
-                .clone()
-                .into_request(&base_url)
+                .request(&client, &base_url)
                 .map_err(WaitContainerError::from)?
                 .send()
                 .await;
@@ -275,6 +368,29 @@ impl Debug for HttpWaitStrategy {
             .field("headers", &self.headers)
             .field("body", &self.body)
             .field("auth", &self.auth)
+            .field("use_tls", &self.use_tls)
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_settings_enable_https() {
+        let strategy = HttpWaitStrategy::new("/").with_danger_accept_invalid_certs(true);
+        assert!(strategy.use_tls);
+        assert!(strategy.client().is_ok());
+        assert!(HttpWaitStrategy::https("/health").use_tls);
+    }
+
+    #[test]
+    fn invalid_client_identity_is_reported() {
+        let err = HttpWaitStrategy::new("/")
+            .with_client_identity("not a certificate", "not a key")
+            .client()
+            .unwrap_err();
+        assert!(matches!(err, HttpWaitError::InvalidTlsConfig(_)), "{err}");
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use futures::{StreamExt, TryStreamExt};
+
+use crate::{
+    core::{
+        client::Client,
+        error::WaitContainerError,
+        logs::{LogSource, WaitLogError},
+        wait::WaitStrategy,
+    },
+    ContainerAsync, Image,
+};
+
+/// Waits for the container to stop logging for a given period, e.g. for batch jobs
+/// whose completion is only signaled by the end of their output.
+#[derive(Debug, Clone)]
+pub struct LogQuietWaitStrategy {
+    /// `None` stands for both stdout and stderr.
+    source: Option<LogSource>,
+    quiet_period: Duration,
+    timeout: Option<Duration>,
+}
+
+impl LogQuietWaitStrategy {
+    /// Create a new [`LogQuietWaitStrategy`] met once neither stdout nor stderr have received
+    /// any output for `quiet_period`.
+    pub fn new(quiet_period: Duration) -> Self {
+        Self {
+            source: None,
+            quiet_period,
+            timeout: None,
+        }
+    }
+
+    /// Only consider the output of the given source.
+    pub fn with_source(mut self, source: LogSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl WaitStrategy for LogQuietWaitStrategy {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        let mut log_stream = match self.source {
+            Some(LogSource::StdOut) => client
+                .stdout_logs(container.id(), true)
+                .map_ok(drop)
+                .boxed(),
+            Some(LogSource::StdErr) => client
+                .stderr_logs(container.id(), true)
+                .map_ok(drop)
+                .boxed(),
+            None => client.logs(container.id(), true).map_ok(drop).boxed(),
+        };
+
+        // the logs written so far are replayed at once, so the period starts with the wait at the earliest
+        loop {
+            match tokio::time::timeout(self.quiet_period, log_stream.next()).await {
+                Err(_) => {
+                    log::debug!(
+                        "Container {} has been quiet for {:?}",
+                        container.id(),
+                        self.quiet_period
+                    );
+                    return Ok(());
+                }
+                Ok(Some(frame)) => {
+                    frame.map_err(|err| WaitContainerError::from(WaitLogError::from(err)))?
+                }
+                // a stopped container can't log anymore, but a crashed one isn't ready
+                Ok(None) => {
+                    let exit_code = client
+                        .inspect(container.id())
+                        .await?
+                        .state
+                        .and_then(|state| state.exit_code);
+                    return match exit_code {
+                        None | Some(0) => Ok(()),
+                        actual => Err(WaitContainerError::UnexpectedExitCode {
+                            expected: 0,
+                            actual,
+                        }
+                        .into()),
+                    };
+                }
+            }
+        }
+    }
+}
@@ -1,20 +1,46 @@
-use bytes::Bytes;
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{StreamExt, TryStreamExt};
+use memchr::memmem::Finder;
 
 use crate::{
     core::{
         client::Client,
         error::WaitContainerError,
-        logs::{LogSource, WaitingStreamWrapper},
+        logs::{LogSource, WaitLogError, WaitingStreamWrapper},
         wait::WaitStrategy,
+        ContainerEventKind,
     },
     ContainerAsync, Image,
 };
 
 #[derive(Debug, Clone)]
 pub struct LogWaitStrategy {
-    source: LogSource,
-    message: Bytes,
+    /// `None` stands for both stdout and stderr.
+    source: Option<LogSource>,
+    matcher: LogMatcher,
     times: usize,
+    timeout: Option<Duration>,
+    since: Option<SystemTime>,
+    follow_restarts: bool,
+}
+
+type JsonPredicate = Arc<dyn Fn(&serde_json::Value) -> bool + Send + Sync + 'static>;
+
+#[derive(Clone)]
+enum LogMatcher {
+    /// Substring of the logs.
+    Message(Bytes),
+    /// Predicate applied to each log line parsed as JSON.
+    Json(JsonPredicate),
+    /// Regular expression matching a log line.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
 }
 
 impl LogWaitStrategy {
@@ -34,35 +60,346 @@ impl LogWaitStrategy {
     /// The message is expected to appear in the logs exactly once by default.
     pub fn new(source: LogSource, message: impl AsRef<[u8]>) -> Self {
         Self {
-            source,
-            message: Bytes::from(message.as_ref().to_vec()),
+            source: Some(source),
+            matcher: LogMatcher::Message(Bytes::from(message.as_ref().to_vec())),
+            times: 1,
+            timeout: None,
+            since: None,
+            follow_restarts: false,
+        }
+    }
+
+    /// Create a new [`LogWaitStrategy`] that waits for a JSON line matching the given predicate to appear in
+    /// the standard output logs.
+    ///
+    /// Each log line is parsed as JSON and passed to the predicate, lines that are not valid JSON are ignored.
+    /// This allows to match structured logs regardless of the fields ordering or formatting.
+    ///
+    /// # Example
+    /// ```rust
+    /// use testcontainers::core::wait::LogWaitStrategy;
+    ///
+    /// let strategy = LogWaitStrategy::json(|value| {
+    ///     value["level"] == "info" && value["msg"] == "server started"
+    /// });
+    /// ```
+    pub fn json<P>(predicate: P) -> Self
+    where
+        P: Fn(&serde_json::Value) -> bool + Send + Sync + 'static,
+    {
+        Self::new_json(LogSource::StdOut, predicate)
+    }
+
+    /// Create a new [`LogWaitStrategy`] that waits for a JSON line matching the given predicate to appear in
+    /// the standard error logs.
+    ///
+    /// See [`LogWaitStrategy::json`] for more details.
+    pub fn json_stderr<P>(predicate: P) -> Self
+    where
+        P: Fn(&serde_json::Value) -> bool + Send + Sync + 'static,
+    {
+        Self::new_json(LogSource::StdErr, predicate)
+    }
+
+    fn new_json<P>(source: LogSource, predicate: P) -> Self
+    where
+        P: Fn(&serde_json::Value) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            source: Some(source),
+            matcher: LogMatcher::Json(Arc::new(predicate)),
             times: 1,
+            timeout: None,
+            since: None,
+            follow_restarts: false,
         }
     }
 
+    /// Create a new [`LogWaitStrategy`] that waits for a line matching the regular expression to appear in
+    /// the standard output logs.
+    ///
+    /// The expression is applied to each log line without the trailing newline, so `^` and `$` anchor
+    /// to the line boundaries. Use it for lines containing variable parts, such as ports or timestamps.
+    ///
+    /// # Example
+    /// ```rust
+    /// use regex::Regex;
+    /// use testcontainers::core::{logs::LogSource, wait::LogWaitStrategy};
+    ///
+    /// let strategy = LogWaitStrategy::matching(Regex::new(r"listening on port \d+$").unwrap())
+    ///     .with_source(LogSource::StdErr)
+    ///     .with_times(2);
+    /// ```
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn matching(regex: regex::Regex) -> Self {
+        Self {
+            source: Some(LogSource::StdOut),
+            matcher: LogMatcher::Regex(regex),
+            times: 1,
+            timeout: None,
+            since: None,
+            follow_restarts: false,
+        }
+    }
+
+    /// Set the source of the logs to look into.
+    pub fn with_source(mut self, source: LogSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Look into both the standard output and the standard error logs.
+    ///
+    /// The logs are matched line by line, since lines of both sources are interleaved.
+    pub fn with_any_source(mut self) -> Self {
+        self.source = None;
+        self
+    }
+
     /// Set the number of times the message should appear in the logs.
     pub fn with_times(mut self, times: usize) -> Self {
         self.times = times;
         self
     }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Only count the lines logged from now on, e.g. to wait for the outcome of a request sent to
+    /// a running container. The time is captured when this method is called, so call it before the request.
+    ///
+    /// The lines are compared by their timestamps, so the count continues across the restarts of
+    /// the container instead of starting over with the whole logs. Messages are matched within lines.
+    ///
+    /// Note that the timestamps are assigned by the Docker daemon, whose clock must be in sync with this host.
+    pub fn from_now(mut self) -> Self {
+        self.since = Some(SystemTime::now());
+        self
+    }
+
+    /// Follow the logs across the restarts of the container, e.g. of images restarting on purpose
+    /// during their initialization. Disabled by default, the strategy fails once the container stops.
+    ///
+    /// When the container stops, the strategy waits for it to be started again (by its restart policy
+    /// or by the test) and attaches to its logs again, until the condition is met or its timeout elapses.
+    /// The lines are compared by their timestamps, so the lines logged before a restart are counted once.
+    pub fn with_follow_restarts(mut self, follow_restarts: bool) -> Self {
+        self.follow_restarts = follow_restarts;
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Only count the lines logged after `since`, unless the strategy already starts later.
+    pub(crate) fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = self.since.max(Some(since));
+        self
+    }
 }
 
 impl WaitStrategy for LogWaitStrategy {
     async fn wait_until_ready<I: Image>(
-        self,
+        &self,
         client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()> {
+        if self.since.is_some() || self.follow_restarts {
+            let since = self.since.unwrap_or(SystemTime::UNIX_EPOCH);
+            return self.wait_since(client, container, since).await;
+        }
+
         let log_stream = match self.source {
-            LogSource::StdOut => client.stdout_logs(container.id(), true),
-            LogSource::StdErr => client.stderr_logs(container.id(), true),
+            Some(LogSource::StdOut) => client.stdout_logs(container.id(), true),
+            Some(LogSource::StdErr) => client.stderr_logs(container.id(), true),
+            None => client
+                .logs(container.id(), true)
+                .into_lines()
+                .map_ok(|line| {
+                    let mut bytes = BytesMut::from(&line.content()[..]);
+                    bytes.put_u8(b'\n');
+                    bytes.freeze()
+                })
+                .boxed(),
         };
 
-        WaitingStreamWrapper::new(log_stream)
-            .wait_for_message(self.message, self.times)
-            .await
-            .map_err(WaitContainerError::from)?;
+        let mut log_stream = WaitingStreamWrapper::new(log_stream);
+        match &self.matcher {
+            LogMatcher::Message(message) => log_stream.wait_for_message(message, self.times).await,
+            LogMatcher::Json(predicate) => {
+                log_stream
+                    .wait_for_line(|line| json_line_matches(line, predicate), self.times)
+                    .await
+            }
+            #[cfg(feature = "regex")]
+            LogMatcher::Regex(regex) => {
+                log_stream
+                    .wait_for_line(
+                        |line| regex.is_match(&String::from_utf8_lossy(line)),
+                        self.times,
+                    )
+                    .await
+            }
+        }
+        .map_err(WaitContainerError::from)?;
 
         Ok(())
     }
 }
+
+impl LogWaitStrategy {
+    /// Counts the matches in the lines logged after `since`, following the logs again
+    /// from the last seen line while the container is restarting, or once it's started again
+    /// if the restarts are followed.
+    async fn wait_since<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+        since: SystemTime,
+    ) -> crate::core::error::Result<()> {
+        let mut last_seen: Option<SystemTime> = None;
+        let mut found_times = 0;
+        let mut messages = Vec::new();
+        loop {
+            let from = last_seen.unwrap_or(since);
+            let mut lines = client
+                .timestamped_logs_since(container.id(), self.source, from)
+                .into_lines();
+            while let Some(line) = lines
+                .next()
+                .await
+                .transpose()
+                .map_err(|err| WaitContainerError::from(WaitLogError::from(err)))?
+            {
+                let Some(timestamp) = line.timestamp() else {
+                    continue;
+                };
+                // lines of the same second are sent again, as the logs are filtered by whole seconds
+                if timestamp < since || last_seen.is_some_and(|last_seen| timestamp <= last_seen) {
+                    continue;
+                }
+                last_seen = Some(timestamp);
+
+                found_times += self.matcher.matches(line.content());
+                if found_times >= self.times {
+                    log::debug!("Message found {found_times} times since {since:?}");
+                    return Ok(());
+                }
+                messages.push(line.content().clone());
+            }
+
+            // the events are requested from before the inspection, so that a start in between isn't missed
+            let mut events = self.follow_restarts.then(|| container.events());
+            let state = client
+                .inspect(container.id())
+                .await?
+                .state
+                .unwrap_or_default();
+            if !(state.running == Some(true) || state.restarting == Some(true)) {
+                let Some(events) = &mut events else {
+                    return Err(
+                        WaitContainerError::from(WaitLogError::EndOfStream(messages)).into(),
+                    );
+                };
+                log::debug!(
+                    "Container {} is stopped, waiting for it to be started again",
+                    container.id()
+                );
+                loop {
+                    match events
+                        .next()
+                        .await
+                        .transpose()?
+                        .map(|event| event.kind().clone())
+                    {
+                        Some(ContainerEventKind::Start | ContainerEventKind::Restart) => break,
+                        Some(ContainerEventKind::Destroy) | None => {
+                            return Err(WaitContainerError::from(WaitLogError::EndOfStream(
+                                messages,
+                            ))
+                            .into());
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            log::debug!(
+                "Logs of container {} ended while it's running, following them again",
+                container.id()
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+impl LogMatcher {
+    /// Returns the number of matches in the line.
+    fn matches(&self, line: &[u8]) -> usize {
+        match self {
+            Self::Message(message) => Finder::new(message).find_iter(line).count(),
+            Self::Json(predicate) => usize::from(json_line_matches(line, predicate)),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => usize::from(regex.is_match(&String::from_utf8_lossy(line))),
+        }
+    }
+}
+
+fn json_line_matches(line: &[u8], predicate: &JsonPredicate) -> bool {
+    serde_json::from_slice::<serde_json::Value>(line.trim_ascii())
+        .map(|value| predicate(&value))
+        .unwrap_or(false)
+}
+
+impl fmt::Debug for LogMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message(message) => f.debug_tuple("Message").field(message).finish(),
+            Self::Json(_) => f.write_str("Json(..)"),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => f.debug_tuple("Regex").field(regex).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_predicate_is_applied_to_parsed_lines() {
+        let predicate: JsonPredicate =
+            Arc::new(|value| value["level"] == "info" && value["msg"] == "server started");
+
+        assert!(json_line_matches(
+            br#"{"msg":"server started","level":"info","port":8080}"#,
+            &predicate
+        ));
+        assert!(json_line_matches(
+            b"  {\"level\": \"info\", \"msg\": \"server started\"}\r",
+            &predicate
+        ));
+        assert!(!json_line_matches(
+            br#"{"level":"debug","msg":"server started"}"#,
+            &predicate
+        ));
+        assert!(!json_line_matches(b"server started", &predicate));
+    }
+
+    #[test]
+    fn messages_are_counted_within_lines() {
+        let matcher = LogMatcher::Message(Bytes::from_static(b"processed"));
+        assert_eq!(
+            matcher.matches(b"request 1 processed, request 2 processed"),
+            2
+        );
+        assert_eq!(matcher.matches(b"request 3 received"), 0);
+
+        let matcher = LogMatcher::Json(Arc::new(|value| value["msg"] == "processed"));
+        assert_eq!(matcher.matches(br#"{"msg":"processed"}"#), 1);
+    }
+}
@@ -1,39 +1,69 @@
-use std::{env::var, fmt::Debug, time::Duration};
+use std::{
+    env::var,
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
+pub use command_strategy::CommandWaitStrategy;
+pub use composite_strategy::CompositeWaitStrategy;
+pub use custom_strategy::CustomWaitStrategy;
 pub use exit_strategy::ExitWaitStrategy;
+#[cfg(feature = "grpc_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+pub use grpc_strategy::GrpcHealthWaitStrategy;
 pub use health_strategy::HealthWaitStrategy;
 #[cfg(feature = "http_wait")]
 #[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
 pub use http_strategy::HttpWaitStrategy;
+pub use log_quiet_strategy::LogQuietWaitStrategy;
 pub use log_strategy::LogWaitStrategy;
+pub use port_strategy::PortWaitStrategy;
+
+use url::Host;
 
 use crate::{
-    core::{client::Client, logs::LogSource},
+    core::{
+        client::Client, error::WaitContainerError, logs::LogSource, ContainerPort, ExecCommand,
+    },
     ContainerAsync, Image,
 };
 
 pub(crate) mod cmd_wait;
+pub(crate) mod command_strategy;
+pub(crate) mod composite_strategy;
+pub(crate) mod custom_strategy;
 pub(crate) mod exit_strategy;
+#[cfg(feature = "grpc_wait")]
+pub(crate) mod grpc_strategy;
 pub(crate) mod health_strategy;
 #[cfg(feature = "http_wait")]
 pub(crate) mod http_strategy;
+pub(crate) mod log_quiet_strategy;
 pub(crate) mod log_strategy;
+pub(crate) mod port_strategy;
 
 pub(crate) trait WaitStrategy {
     async fn wait_until_ready<I: Image>(
-        self,
+        &self,
         client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()>;
 }
 
 /// Represents a condition that needs to be met before a container is considered ready.
+///
+/// Conditions without a deadline of their own are limited by the default wait timeout of the container request
+/// (see `TESTCONTAINERS_WAIT_TIMEOUT`), if any, except for fixed durations and custom strategies.
+/// All of them are limited by the startup timeout of the container.
 #[derive(Debug, Clone)]
 pub enum WaitFor {
     /// An empty condition. Useful for default cases or fallbacks.
     Nothing,
     /// Wait for a certain message to appear in the container's logs.
     Log(LogWaitStrategy),
+    /// Wait for the container to stop logging for a certain amount of time.
+    LogQuiet(LogQuietWaitStrategy),
     /// Wait for a certain amount of time.
     Duration { length: Duration },
     /// Wait for the container's status to become `healthy`.
@@ -42,8 +72,20 @@ pub enum WaitFor {
     #[cfg(feature = "http_wait")]
     #[cfg_attr(docsrs, doc(cfg(feature = "http_wait")))]
     Http(HttpWaitStrategy),
+    /// Wait for the container to report `SERVING` status through the gRPC health checking protocol.
+    #[cfg(feature = "grpc_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+    Grpc(GrpcHealthWaitStrategy),
     /// Wait for the container to exit.
     Exit(ExitWaitStrategy),
+    /// Wait for a container port to accept TCP connections.
+    Port(PortWaitStrategy),
+    /// Wait for a command executed inside the container to exit with code `0`.
+    Command(CommandWaitStrategy),
+    /// Wait for all (or any) of the nested conditions.
+    Composite(CompositeWaitStrategy),
+    /// Wait for a user-defined condition, shared between the clones of the condition.
+    Custom(Arc<dyn CustomWaitStrategy>),
 }
 
 impl WaitFor {
@@ -57,11 +99,30 @@ impl WaitFor {
         Self::log(LogWaitStrategy::new(LogSource::StdErr, message))
     }
 
+    /// Wait for a line matching the regular expression to appear on the container's stdout.
+    ///
+    /// Use [`LogWaitStrategy::matching`] to customize the source or the number of occurrences.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn log_matching(regex: regex::Regex) -> WaitFor {
+        Self::log(LogWaitStrategy::matching(regex))
+    }
+
     /// Wait for the message to appear on the container's stdout.
     pub fn log(log_strategy: LogWaitStrategy) -> WaitFor {
         WaitFor::Log(log_strategy)
     }
 
+    /// Wait for the container to log nothing on stdout and stderr for the given period,
+    /// e.g. for batch imports whose completion is only signaled by the end of their output.
+    ///
+    /// The period starts over with each new output. The condition is also met once the container stops,
+    /// unless it exits with a non-zero code.
+    /// Use [`LogQuietWaitStrategy`] to only consider one source or to set a timeout.
+    pub fn log_quiet_for(quiet_period: Duration) -> WaitFor {
+        WaitFor::LogQuiet(LogQuietWaitStrategy::new(quiet_period))
+    }
+
     /// Wait for the container to become healthy.
     ///
     /// If you need to customize polling interval, use [`HealthWaitStrategy::with_poll_interval`]
@@ -77,11 +138,53 @@ impl WaitFor {
         WaitFor::Http(http_strategy)
     }
 
+    /// Wait for the container to report `SERVING` status through the gRPC health checking protocol.
+    #[cfg(feature = "grpc_wait")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+    pub fn grpc(grpc_strategy: GrpcHealthWaitStrategy) -> WaitFor {
+        WaitFor::Grpc(grpc_strategy)
+    }
+
     /// Wait for the container to exit.
     pub fn exit(exit_strategy: ExitWaitStrategy) -> WaitFor {
         WaitFor::Exit(exit_strategy)
     }
 
+    /// Wait for the container port to accept TCP connections (checked from the host).
+    ///
+    /// Use [`PortWaitStrategy`] directly to customize the strategy, e.g. to check the port from inside the container.
+    pub fn port(port: impl Into<ContainerPort>) -> WaitFor {
+        WaitFor::Port(PortWaitStrategy::new(port))
+    }
+
+    /// Wait for the command executed inside the container to exit with code `0`, repeating it until it does.
+    ///
+    /// Use [`CommandWaitStrategy`] directly to customize the poll interval or the deadline.
+    pub fn command(command: ExecCommand) -> WaitFor {
+        WaitFor::Command(CommandWaitStrategy::new(command))
+    }
+
+    /// Wait for all the conditions to be met, awaiting them concurrently.
+    ///
+    /// Use [`CompositeWaitStrategy::with_timeout`] to limit the time of waiting.
+    pub fn all(conditions: impl IntoIterator<Item = WaitFor>) -> WaitFor {
+        WaitFor::Composite(CompositeWaitStrategy::all(conditions))
+    }
+
+    /// Wait for any of the conditions to be met, awaiting them concurrently.
+    ///
+    /// Use [`CompositeWaitStrategy::with_timeout`] to limit the time of waiting.
+    pub fn any(conditions: impl IntoIterator<Item = WaitFor>) -> WaitFor {
+        WaitFor::Composite(CompositeWaitStrategy::any(conditions))
+    }
+
+    /// Wait for a user-defined condition.
+    ///
+    /// The strategy is borrowed while waiting, pass an [`Arc`] to share a stateful strategy between containers.
+    pub fn custom(strategy: impl CustomWaitStrategy + 'static) -> WaitFor {
+        WaitFor::Custom(Arc::new(strategy))
+    }
+
     /// Wait for a certain amount of seconds.
     ///
     /// Generally, it's not recommended to use this method, as it's better to wait for a specific condition to be met.
@@ -115,6 +218,24 @@ impl WaitFor {
         })()
         .unwrap_or(WaitFor::Nothing)
     }
+
+    /// Container ports the condition connects to through their mapped host ports,
+    /// `None` standing for the first exposed port.
+    pub(crate) fn probed_ports(&self) -> Vec<Option<ContainerPort>> {
+        match self {
+            #[cfg(feature = "http_wait")]
+            WaitFor::Http(strategy) => vec![strategy.port()],
+            #[cfg(feature = "grpc_wait")]
+            WaitFor::Grpc(strategy) => vec![strategy.port()],
+            WaitFor::Port(strategy) => strategy.host_port().map(Some).into_iter().collect(),
+            WaitFor::Composite(strategy) => strategy
+                .conditions()
+                .iter()
+                .flat_map(WaitFor::probed_ports)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 #[cfg(feature = "http_wait")]
@@ -125,16 +246,121 @@ impl From<HttpWaitStrategy> for WaitFor {
     }
 }
 
-impl WaitStrategy for WaitFor {
-    async fn wait_until_ready<I: Image>(
-        self,
+#[cfg(feature = "grpc_wait")]
+#[cfg_attr(docsrs, doc(cfg(feature = "grpc_wait")))]
+impl From<GrpcHealthWaitStrategy> for WaitFor {
+    fn from(value: GrpcHealthWaitStrategy) -> Self {
+        Self::Grpc(value)
+    }
+}
+
+impl From<LogQuietWaitStrategy> for WaitFor {
+    fn from(value: LogQuietWaitStrategy) -> Self {
+        Self::LogQuiet(value)
+    }
+}
+
+impl From<CommandWaitStrategy> for WaitFor {
+    fn from(value: CommandWaitStrategy) -> Self {
+        Self::Command(value)
+    }
+}
+
+impl From<CompositeWaitStrategy> for WaitFor {
+    fn from(value: CompositeWaitStrategy) -> Self {
+        Self::Composite(value)
+    }
+}
+
+/// Resolves the host the container may be reached on, along with the host port mapped to the container port.
+///
+/// For domain hosts, the IPv4 mapping is preferred and IPv6 is used as a fallback.
+pub(crate) async fn mapped_host_port<I: Image>(
+    container: &ContainerAsync<I>,
+    container_port: ContainerPort,
+) -> crate::core::error::Result<(Host, u16)> {
+    let host = container.get_host().await?;
+    let host_port = match host {
+        Host::Domain(ref domain) => match container.get_host_port_ipv4(container_port).await {
+            Ok(port) => port,
+            Err(_) => {
+                log::debug!("IPv4 port not found for domain: {domain}, checking for IPv6");
+                container.get_host_port_ipv6(container_port).await?
+            }
+        },
+        Host::Ipv4(_) => container.get_host_port_ipv4(container_port).await?,
+        Host::Ipv6(_) => container.get_host_port_ipv6(container_port).await?,
+    };
+    Ok((host, host_port))
+}
+
+impl WaitFor {
+    /// Returns the deadline of the condition, if any.
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        match self {
+            WaitFor::Log(strategy) => strategy.timeout(),
+            WaitFor::LogQuiet(strategy) => strategy.timeout(),
+            WaitFor::Healthcheck(strategy) => strategy.timeout(),
+            #[cfg(feature = "http_wait")]
+            WaitFor::Http(strategy) => strategy.timeout(),
+            #[cfg(feature = "grpc_wait")]
+            WaitFor::Grpc(strategy) => strategy.timeout(),
+            WaitFor::Exit(strategy) => strategy.timeout(),
+            WaitFor::Port(strategy) => strategy.timeout(),
+            WaitFor::Command(strategy) => strategy.timeout(),
+            WaitFor::Composite(strategy) => strategy.timeout(),
+            WaitFor::Duration { .. } | WaitFor::Custom(_) | WaitFor::Nothing => None,
+        }
+    }
+
+    /// Sets the deadline of the condition, unless it already has one.
+    ///
+    /// Fixed durations are left as they are, since they don't wait for anything to happen,
+    /// as well as custom strategies, which are limited by the startup timeout only.
+    pub(crate) fn with_default_timeout(self, timeout: Duration) -> Self {
+        if self.timeout().is_some() {
+            return self;
+        }
+        match self {
+            WaitFor::Log(strategy) => WaitFor::Log(strategy.with_timeout(timeout)),
+            WaitFor::LogQuiet(strategy) => WaitFor::LogQuiet(strategy.with_timeout(timeout)),
+            WaitFor::Healthcheck(strategy) => WaitFor::Healthcheck(strategy.with_timeout(timeout)),
+            #[cfg(feature = "http_wait")]
+            WaitFor::Http(strategy) => WaitFor::Http(strategy.with_timeout(timeout)),
+            #[cfg(feature = "grpc_wait")]
+            WaitFor::Grpc(strategy) => WaitFor::Grpc(strategy.with_timeout(timeout)),
+            WaitFor::Exit(strategy) => WaitFor::Exit(strategy.with_timeout(timeout)),
+            WaitFor::Port(strategy) => WaitFor::Port(strategy.with_timeout(timeout)),
+            WaitFor::Command(strategy) => WaitFor::Command(strategy.with_timeout(timeout)),
+            WaitFor::Composite(strategy) => WaitFor::Composite(strategy.with_timeout(timeout)),
+            condition @ (WaitFor::Duration { .. } | WaitFor::Custom(_) | WaitFor::Nothing) => {
+                condition
+            }
+        }
+    }
+
+    /// Limits the log conditions to the lines logged after `since`, e.g. to skip the output
+    /// of the previous run of a restarted container.
+    pub(crate) fn with_logs_since(self, since: SystemTime) -> Self {
+        match self {
+            WaitFor::Log(strategy) => WaitFor::Log(strategy.with_since(since)),
+            WaitFor::Composite(strategy) => WaitFor::Composite(strategy.with_logs_since(since)),
+            condition => condition,
+        }
+    }
+
+    async fn wait<I: Image>(
+        &self,
         client: &Client,
         container: &ContainerAsync<I>,
     ) -> crate::core::error::Result<()> {
         match self {
             WaitFor::Log(strategy) => strategy.wait_until_ready(client, container).await?,
+            WaitFor::LogQuiet(strategy) => {
+                strategy.wait_until_ready(client, container).await?;
+            }
             WaitFor::Duration { length } => {
-                tokio::time::sleep(length).await;
+                tokio::time::sleep(*length).await;
             }
             WaitFor::Healthcheck(strategy) => {
                 strategy.wait_until_ready(client, container).await?;
@@ -143,11 +369,77 @@ impl WaitStrategy for WaitFor {
             WaitFor::Http(strategy) => {
                 strategy.wait_until_ready(client, container).await?;
             }
+            #[cfg(feature = "grpc_wait")]
+            WaitFor::Grpc(strategy) => {
+                strategy.wait_until_ready(client, container).await?;
+            }
             WaitFor::Exit(strategy) => {
                 strategy.wait_until_ready(client, container).await?;
             }
+            WaitFor::Port(strategy) => {
+                strategy.wait_until_ready(client, container).await?;
+            }
+            WaitFor::Command(strategy) => {
+                strategy.wait_until_ready(client, container).await?;
+            }
+            WaitFor::Composite(strategy) => {
+                strategy.wait_until_ready(client, container).await?;
+            }
+            WaitFor::Custom(strategy) => {
+                strategy.wait_until_ready(&container.handle()).await?;
+            }
             WaitFor::Nothing => {}
         }
         Ok(())
     }
 }
+
+impl WaitStrategy for WaitFor {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        match self.timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, self.wait(client, container))
+                .await
+                .map_err(|_| WaitContainerError::Timeout(timeout))?,
+            None => self.wait(client, container).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_timeout_does_not_override_own_timeout() {
+        let default = Duration::from_secs(30);
+        let own = Duration::from_secs(5);
+
+        let condition = WaitFor::log(LogWaitStrategy::stdout("ready").with_timeout(own));
+        assert_eq!(condition.with_default_timeout(default).timeout(), Some(own));
+
+        let condition = WaitFor::message_on_stdout("ready");
+        assert_eq!(
+            condition.with_default_timeout(default).timeout(),
+            Some(default)
+        );
+
+        let condition = WaitFor::log_quiet_for(Duration::from_secs(1));
+        assert_eq!(
+            condition.with_default_timeout(default).timeout(),
+            Some(default)
+        );
+
+        let condition = WaitFor::all([WaitFor::healthcheck()]);
+        assert_eq!(
+            condition.with_default_timeout(default).timeout(),
+            Some(default)
+        );
+
+        let condition = WaitFor::seconds(60);
+        assert_eq!(condition.with_default_timeout(default).timeout(), None);
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use crate::{
+    core::{
+        client::Client,
+        wait::{mapped_host_port, WaitStrategy},
+        ContainerPort,
+    },
+    ContainerAsync, Image,
+};
+
+/// Represents a strategy for waiting until a container port is accepting TCP connections.
+///
+/// By default, the mapped port is probed from the host. Keep in mind that with some setups
+/// (e.g. `docker-proxy` or Docker Desktop port forwarding) connections to mapped ports are accepted
+/// even before the service inside the container is listening,
+/// use [`PortWaitStrategy::in_container`] to check the port from inside the container instead.
+#[derive(Debug, Clone)]
+pub struct PortWaitStrategy {
+    port: ContainerPort,
+    in_container: bool,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl PortWaitStrategy {
+    /// Create a new `PortWaitStrategy` for the given container port.
+    pub fn new(port: impl Into<ContainerPort>) -> Self {
+        Self {
+            port: port.into(),
+            in_container: false,
+            poll_interval: Duration::from_millis(100),
+            timeout: None,
+        }
+    }
+
+    /// Check the port from inside the container, by executing a command in it.
+    ///
+    /// Requires a shell in the container (the listening sockets are looked up in `/proc/net/tcp*`,
+    /// falling back to `nc` and `bash` if available).
+    pub fn in_container(mut self) -> Self {
+        self.in_container = true;
+        self
+    }
+
+    /// Set the poll interval for the wait strategy.
+    ///
+    /// This is the time to wait between each poll for the expected condition to be met.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set the deadline for the condition to be met, see [`WaitFor`](crate::core::WaitFor) for the default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The port probed from the host, if it isn't checked from inside the container.
+    pub(crate) fn host_port(&self) -> Option<ContainerPort> {
+        (!self.in_container).then_some(self.port)
+    }
+
+    async fn is_listening_in_container(
+        &self,
+        client: &Client,
+        container_id: &str,
+    ) -> crate::core::error::Result<bool> {
+        let port = self.port.as_u16();
+        // state `0A` stands for `LISTEN` in `/proc/net/tcp*`
+        let script = format!(
+            "grep -qiE ':{port:04X} [0-9A-F]+:0000 0A' /proc/net/tcp /proc/net/tcp6 2>/dev/null \
+             || nc -z 127.0.0.1 {port} 2>/dev/null \
+             || bash -c '</dev/tcp/127.0.0.1/{port}' 2>/dev/null"
+        );
+        let cmd = vec!["/bin/sh".to_string(), "-c".to_string(), script];
+        let exec_id = client.exec(container_id, cmd).await?.id;
+        loop {
+            if let Some(code) = client.inspect_exec(&exec_id).await?.exit_code {
+                return Ok(code == 0);
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+impl WaitStrategy for PortWaitStrategy {
+    async fn wait_until_ready<I: Image>(
+        &self,
+        client: &Client,
+        container: &ContainerAsync<I>,
+    ) -> crate::core::error::Result<()> {
+        if self.in_container {
+            while !self
+                .is_listening_in_container(client, container.id())
+                .await?
+            {
+                log::debug!("Port {} is not listening in the container yet", self.port);
+                tokio::time::sleep(self.poll_interval).await;
+            }
+            return Ok(());
+        }
+
+        let (host, host_port) = mapped_host_port(container, self.port).await?;
+        let address = format!("{host}:{host_port}");
+        loop {
+            match tokio::net::TcpStream::connect(&address).await {
+                Ok(_) => {
+                    log::debug!("Port {} is accepting connections on {address}", self.port);
+                    break;
+                }
+                Err(err) => {
+                    log::debug!("Error while waiting for port {}: {err}", self.port);
+                }
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        Ok(())
+    }
+}
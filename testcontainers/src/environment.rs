@@ -0,0 +1,435 @@
+//! Multi-service environments, started in the order of the dependencies between the services.
+//!
+//! An [`Environment`] is a set of [services](Service) (e.g. a database, its migrations and the tested app),
+//! each declaring the services it depends on. The services are started in a topological order on a network
+//! created for the environment, where they are resolvable by their names. Once the [`RunningEnvironment`]
+//! is dropped, the containers are removed in the reverse order, followed by the network.
+//!
+//! ```rust,no_run
+//! use testcontainers::{
+//!     core::{wait::ExitWaitStrategy, WaitFor},
+//!     environment::{Environment, Service},
+//!     GenericImage, ImageExt,
+//! };
+//!
+//! # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! let env = Environment::new()
+//!     .with_service(Service::new(
+//!         "db",
+//!         GenericImage::new("postgres", "16-alpine")
+//!             .with_wait_for(WaitFor::message_on_stderr(
+//!                 "database system is ready to accept connections",
+//!             ))
+//!             .with_env_var("POSTGRES_PASSWORD", "postgres"),
+//!     ))
+//!     .with_service(
+//!         Service::new("migrations", GenericImage::new("my-migrations", "latest")).depends_on("db"),
+//!     )
+//!     .with_service(
+//!         Service::new("app", GenericImage::new("my-app", "latest"))
+//!             .depends_on("db")
+//!             // the migrations must be completed before the app starts
+//!             .depends_on_with(
+//!                 "migrations",
+//!                 WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)),
+//!             ),
+//!     )
+//!     .start()
+//!     .await?;
+//!
+//! let app_port = env.container("app")?.get_host_port_ipv4(8080).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use futures::future::{BoxFuture, FutureExt};
+
+use crate::{
+    core::{error::Result, ContainerHandle, ContainerRequest, Image, ImageExt, Network, WaitFor},
+    runners::AsyncRunner,
+    ContainerAsync, TestcontainersError,
+};
+
+/// A set of services started in the order of their dependencies, see the [module documentation](self).
+#[derive(Default)]
+pub struct Environment {
+    services: Vec<Service>,
+}
+
+/// A container of an [`Environment`], resolvable by its name by the other services.
+pub struct Service {
+    name: String,
+    start: StartFn,
+    dependencies: Vec<Dependency>,
+}
+
+type StartFn =
+    Box<dyn FnOnce(String) -> BoxFuture<'static, Result<Box<dyn ServiceContainer>>> + Send>;
+
+struct Dependency {
+    service: String,
+    ready_conditions: Vec<WaitFor>,
+}
+
+/// Error of an [`Environment`], either invalid dependencies or a failed start of a service.
+#[derive(Debug, thiserror::Error)]
+pub enum EnvironmentError {
+    #[error("service '{0}' is declared more than once")]
+    DuplicateService(String),
+    #[error("service '{service}' depends on the unknown service '{dependency}'")]
+    UnknownDependency { service: String, dependency: String },
+    #[error("dependencies of the services form a cycle: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
+    #[error("failed to create the network of the environment: {0}")]
+    Network(TestcontainersError),
+    #[error("failed to start service '{service}': {source}")]
+    Start {
+        service: String,
+        source: TestcontainersError,
+    },
+}
+
+impl Environment {
+    /// Creates an empty environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the service to the environment.
+    pub fn with_service(mut self, service: Service) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    /// Starts the services in the order of their dependencies.
+    ///
+    /// The services which don't depend on each other are started in the order they were added.
+    /// If a service fails to start, the services started so far are removed.
+    pub async fn start(self) -> std::result::Result<RunningEnvironment, EnvironmentError> {
+        let order = start_order(&self.services)?;
+        let network = Network::builder()
+            .create()
+            .await
+            .map_err(EnvironmentError::Network)?;
+
+        let mut services: Vec<_> = self.services.into_iter().map(Some).collect();
+        let mut running = RunningEnvironment {
+            containers: Vec::with_capacity(order.len()),
+            network,
+        };
+        for index in order {
+            let service = services[index]
+                .take()
+                .expect("each service is started once");
+            let name = service.name.clone();
+            log::debug!("Starting service '{name}'");
+            match running.start_service(service).await {
+                Ok(container) => running.containers.push((name, container)),
+                Err(source) => {
+                    return Err(EnvironmentError::Start {
+                        service: name,
+                        source,
+                    })
+                }
+            }
+        }
+
+        Ok(running)
+    }
+}
+
+impl fmt::Debug for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Environment")
+            .field("services", &self.services)
+            .finish()
+    }
+}
+
+impl Service {
+    /// Creates a service started from the request, it joins the network of the environment with its name as alias.
+    pub fn new<I: Image + 'static>(
+        name: impl Into<String>,
+        request: impl Into<ContainerRequest<I>>,
+    ) -> Self {
+        let name = name.into();
+        let request = request.into();
+        let alias = name.clone();
+        let start: StartFn = Box::new(move |network| {
+            async move {
+                let container = request
+                    .with_network(network.clone())
+                    .with_network_alias(network, alias)
+                    .start()
+                    .await?;
+                Ok(Box::new(container) as Box<dyn ServiceContainer>)
+            }
+            .boxed()
+        });
+        Self {
+            name,
+            start,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Starts the service only once the other service is started (and ready by its own conditions).
+    pub fn depends_on(mut self, service: impl Into<String>) -> Self {
+        self.dependencies.push(Dependency {
+            service: service.into(),
+            ready_conditions: Vec::new(),
+        });
+        self
+    }
+
+    /// Starts the service only once the other service meets the condition as well,
+    /// e.g. a migrations container has exited.
+    pub fn depends_on_with(mut self, service: impl Into<String>, condition: WaitFor) -> Self {
+        let service = service.into();
+        match self
+            .dependencies
+            .iter_mut()
+            .find(|dep| dep.service == service)
+        {
+            Some(dependency) => dependency.ready_conditions.push(condition),
+            None => self.dependencies.push(Dependency {
+                service,
+                ready_conditions: vec![condition],
+            }),
+        }
+        self
+    }
+
+    /// Returns the name of the service.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dependencies: Vec<_> = self.dependencies.iter().map(|dep| &dep.service).collect();
+        f.debug_struct("Service")
+            .field("name", &self.name)
+            .field("dependencies", &dependencies)
+            .finish()
+    }
+}
+
+/// A started [`Environment`], removing its containers in the reverse order of their start once dropped.
+pub struct RunningEnvironment {
+    containers: Vec<(String, Box<dyn ServiceContainer>)>,
+    network: Arc<Network>,
+}
+
+impl RunningEnvironment {
+    /// Returns the handle of the service container.
+    pub fn container(&self, service: &str) -> Result<ContainerHandle> {
+        self.service(service)
+            .map(|container| container.handle())
+            .ok_or_else(|| {
+                TestcontainersError::other(format!("service '{service}' is not in the environment"))
+            })
+    }
+
+    /// Returns the network the services are connected to.
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+
+    fn service(&self, name: &str) -> Option<&dyn ServiceContainer> {
+        self.containers
+            .iter()
+            .find(|(service, _)| service == name)
+            .map(|(_, container)| container.as_ref())
+    }
+
+    /// Starts the service once its dependencies meet the additional conditions.
+    async fn start_service(&self, service: Service) -> Result<Box<dyn ServiceContainer>> {
+        for dependency in service.dependencies {
+            if dependency.ready_conditions.is_empty() {
+                continue;
+            }
+            log::debug!(
+                "Waiting for service '{}' before starting '{}'",
+                dependency.service,
+                service.name
+            );
+            self.service(&dependency.service)
+                .expect("dependencies are started first")
+                .wait(dependency.ready_conditions)
+                .await?;
+        }
+        (service.start)(self.network.name().to_string()).await
+    }
+}
+
+impl Drop for RunningEnvironment {
+    fn drop(&mut self) {
+        // the dependents are removed before their dependencies, and the network is removed last
+        while let Some((name, container)) = self.containers.pop() {
+            log::debug!("Removing service '{name}'");
+            drop(container);
+        }
+    }
+}
+
+impl fmt::Debug for RunningEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let services: Vec<_> = self.containers.iter().map(|(name, _)| name).collect();
+        f.debug_struct("RunningEnvironment")
+            .field("services", &services)
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
+/// Returns the indexes of the services in a topological order, keeping the order of independent services.
+fn start_order(services: &[Service]) -> std::result::Result<Vec<usize>, EnvironmentError> {
+    let mut indexes = HashMap::new();
+    for (index, service) in services.iter().enumerate() {
+        if indexes.insert(service.name.as_str(), index).is_some() {
+            return Err(EnvironmentError::DuplicateService(service.name.clone()));
+        }
+    }
+    let dependencies = services
+        .iter()
+        .map(|service| {
+            service
+                .dependencies
+                .iter()
+                .map(|dependency| {
+                    indexes
+                        .get(dependency.service.as_str())
+                        .copied()
+                        .ok_or_else(|| EnvironmentError::UnknownDependency {
+                            service: service.name.clone(),
+                            dependency: dependency.service.clone(),
+                        })
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut order = Vec::with_capacity(services.len());
+    let mut started = vec![false; services.len()];
+    while order.len() < services.len() {
+        let next = (0..services.len())
+            .find(|&index| !started[index] && dependencies[index].iter().all(|&dep| started[dep]));
+        match next {
+            Some(index) => {
+                started[index] = true;
+                order.push(index);
+            }
+            None => {
+                let cycle = find_cycle(&dependencies, &started)
+                    .into_iter()
+                    .map(|index| services[index].name.clone())
+                    .collect();
+                return Err(EnvironmentError::DependencyCycle(cycle));
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Follows the dependencies of the not started services until one of them repeats.
+fn find_cycle(dependencies: &[Vec<usize>], started: &[bool]) -> Vec<usize> {
+    let mut path = Vec::new();
+    let mut current = (0..started.len())
+        .find(|&index| !started[index])
+        .expect("a service is not started");
+    while !path.contains(&current) {
+        path.push(current);
+        // a service which isn't started has a dependency which isn't started either
+        current = dependencies[current]
+            .iter()
+            .copied()
+            .find(|&dep| !started[dep])
+            .expect("a dependency is not started");
+    }
+    let start = path.iter().position(|&index| index == current).unwrap();
+    let mut cycle = path.split_off(start);
+    cycle.push(current);
+    cycle
+}
+
+/// Type-erased container of a service, owning it until the environment is dropped.
+trait ServiceContainer: Send + Sync {
+    fn handle(&self) -> ContainerHandle;
+
+    fn wait(&self, conditions: Vec<WaitFor>) -> BoxFuture<'_, Result<()>>;
+}
+
+impl<I: Image> ServiceContainer for ContainerAsync<I> {
+    fn handle(&self) -> ContainerHandle {
+        ContainerAsync::handle(self)
+    }
+
+    fn wait(&self, conditions: Vec<WaitFor>) -> BoxFuture<'_, Result<()>> {
+        self.block_until_ready(conditions).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenericImage;
+
+    fn service(name: &str, dependencies: &[&str]) -> Service {
+        dependencies.iter().fold(
+            Service::new(name, GenericImage::new("image", "tag")),
+            |service, dependency| service.depends_on(*dependency),
+        )
+    }
+
+    fn names(services: &[Service], order: Vec<usize>) -> Vec<&str> {
+        order
+            .into_iter()
+            .map(|index| services[index].name())
+            .collect()
+    }
+
+    #[test]
+    fn services_are_started_after_their_dependencies() {
+        let services = [
+            service("app", &["db", "migrations"]),
+            service("migrations", &["db"]),
+            service("cache", &[]),
+            service("db", &[]),
+        ];
+
+        let order = start_order(&services).unwrap();
+
+        assert_eq!(
+            names(&services, order),
+            ["cache", "db", "migrations", "app"]
+        );
+    }
+
+    #[test]
+    fn invalid_dependencies_are_rejected() {
+        let err = start_order(&[service("app", &["db"])]).unwrap_err();
+        assert!(
+            matches!(&err, EnvironmentError::UnknownDependency { service, dependency } if service == "app" && dependency == "db"),
+            "{err}"
+        );
+
+        let err = start_order(&[service("db", &[]), service("db", &[])]).unwrap_err();
+        assert!(matches!(&err, EnvironmentError::DuplicateService(name) if name == "db"));
+
+        let err = start_order(&[
+            service("cache", &[]),
+            service("app", &["db"]),
+            service("db", &["migrations"]),
+            service("migrations", &["app"]),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "dependencies of the services form a cycle: app -> db -> migrations -> app"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Blocking test harness for non-async test frameworks.
+//!
+//! [`TestHarness`] owns the runtime used to drive containers, so blocking tests (e.g. plain `libtest`)
+//! can start and control containers without dealing with `async` code.
+//! Everything usually needed by such tests is available with a single import of the [`prelude`].
+//!
+//! ```rust,no_run
+//! use testcontainers::harness::prelude::*;
+//!
+//! #[test]
+//! fn test_redis() -> Result<(), TestcontainersError> {
+//!     let harness = TestHarness::new()?;
+//!     let redis = harness.start(
+//!         GenericImage::new("redis", "7.2.4")
+//!             .with_exposed_port(6379.tcp())
+//!             .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections")),
+//!     )?;
+//!     let port = redis.get_host_port_ipv4(6379.tcp())?;
+//!     // ...
+//!     Ok(())
+//! }
+//! ```
+use std::{fmt, future::Future, sync::Arc};
+
+use crate::{
+    core::error::Result,
+    runners::{sync_runner, AsyncRunner},
+    Container, ContainerRequest, Image,
+};
+
+/// Items commonly used by blocking tests.
+pub mod prelude {
+    pub use super::TestHarness;
+    pub use crate::{
+        core::{ExecCommand, IntoContainerPort, WaitFor},
+        Container, GenericImage, Image, ImageExt, TestcontainersError,
+    };
+}
+
+/// Entry point for blocking tests, owning the runtime used to drive containers.
+///
+/// The runtime is shared with [`SyncRunner`](crate::runners::SyncRunner), containers started with
+/// the harness remain usable after the harness is dropped.
+#[derive(Clone)]
+pub struct TestHarness {
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl TestHarness {
+    /// Creates a new harness, reusing the runtime of already running containers if any.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            runtime: sync_runner::lazy_sync_runner()?,
+        })
+    }
+
+    /// Starts the container and returns an instance of [`Container`].
+    pub fn start<I: Image>(&self, request: impl Into<ContainerRequest<I>>) -> Result<Container<I>> {
+        let async_container = self.runtime.block_on(AsyncRunner::start(request.into()))?;
+        Ok(Container::new(self.runtime.clone(), async_container))
+    }
+
+    /// Pulls the image from the registry.
+    /// Useful if you want to pull the image before starting the container.
+    pub fn pull_image<I: Image>(
+        &self,
+        request: impl Into<ContainerRequest<I>>,
+    ) -> Result<ContainerRequest<I>> {
+        self.runtime
+            .block_on(AsyncRunner::pull_image(request.into()))
+    }
+
+    /// Runs a future to completion on the runtime of the harness.
+    ///
+    /// Useful to call async helpers (e.g. clients of the tested services) from blocking tests.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl fmt::Debug for TestHarness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestHarness").finish()
+    }
+}
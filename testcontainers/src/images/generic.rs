@@ -1,6 +1,6 @@
 use crate::{
     core::{ports::ContainerPort, WaitFor},
-    Image,
+    ContainerRequest, Image, ImageExt,
 };
 
 /// A configurable image from which a [`Container`] or [`ContainerAsync`] can be started.
@@ -38,6 +38,8 @@ use crate::{
 /// The extension traits [`SyncRunner`] and [`AsyncRunner`] each provide the method `start()` to
 /// start the container once it is configured.
 ///
+/// Use [`GenericImage::builder`] instead to have the configuration validated before starting the container.
+///
 /// [`Container`]: crate::Container
 /// [`ContainerAsync`]: crate::ContainerAsync
 /// [`ImageExt`]: crate::core::ImageExt
@@ -53,7 +55,49 @@ pub struct GenericImage {
     exposed_ports: Vec<ContainerPort>,
 }
 
+/// Builder of a [`GenericImage`], validating the configuration once it's built.
+///
+/// ```
+/// use testcontainers::{
+///     core::{IntoContainerPort, WaitFor},
+///     GenericImage,
+/// };
+///
+/// let image = GenericImage::builder("nginx", "1.27-alpine")
+///     .with_exposed_port(80.tcp())
+///     .with_wait_for(WaitFor::port(80.tcp()))
+///     .build()
+///     .expect("valid image");
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct GenericImageBuilder {
+    image: GenericImage,
+}
+
+/// Invalid configuration of a [`GenericImage`], reported by [`GenericImageBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GenericImageError {
+    #[error("image name is empty")]
+    EmptyName,
+    #[error("image tag is empty")]
+    EmptyTag,
+    #[error("port {0} is exposed more than once")]
+    DuplicateExposedPort(ContainerPort),
+    #[error("ready condition connects to port {0}, which isn't exposed")]
+    UnexposedWaitPort(ContainerPort),
+    #[error("ready condition connects to the first exposed port, but no port is exposed")]
+    NoExposedPort,
+}
+
 impl GenericImage {
+    /// Returns a builder of an image, validating its configuration once it's built.
+    pub fn builder(name: impl Into<String>, tag: impl Into<String>) -> GenericImageBuilder {
+        GenericImageBuilder {
+            image: GenericImage::new(name.into(), tag.into()),
+        }
+    }
+
     pub fn new<S: Into<String>>(name: S, tag: S) -> GenericImage {
         Self {
             name: name.into(),
@@ -78,6 +122,96 @@ impl GenericImage {
         self.exposed_ports.push(port);
         self
     }
+
+    /// Applies the settings required to boot Elasticsearch or OpenSearch in a container:
+    /// single-node discovery, locked memory with `memlock` and `nofile` ulimits, 512MB of heap,
+    /// and disabled security (TLS and authentication), so the REST API is available over plain HTTP.
+    ///
+    /// OpenSearch is recognized by the image name, any other image is configured as Elasticsearch.
+    /// As it returns a [`ContainerRequest`], the ports and the ready conditions should be configured first:
+    ///
+    /// ```
+    /// use testcontainers::{
+    ///     core::{IntoContainerPort, WaitFor},
+    ///     GenericImage, ImageExt,
+    /// };
+    ///
+    /// let request = GenericImage::new("docker.elastic.co/elasticsearch/elasticsearch", "8.15.0")
+    ///     .with_exposed_port(9200.tcp())
+    ///     .with_wait_for(WaitFor::message_on_stdout("\"message\":\"started"))
+    ///     .with_search_engine_defaults()
+    ///     .with_env_var("cluster.name", "test");
+    /// ```
+    pub fn with_search_engine_defaults(self) -> ContainerRequest<GenericImage> {
+        let is_opensearch = self.name.contains("opensearch");
+        let request = self
+            .with_env_var("discovery.type", "single-node")
+            .with_env_var("bootstrap.memory_lock", "true")
+            .with_ulimit("memlock", -1, Some(-1))
+            .with_ulimit("nofile", 65536, Some(65536));
+
+        // unknown settings fail the startup, so each engine gets only its own ones
+        if is_opensearch {
+            request
+                .with_env_var("OPENSEARCH_JAVA_OPTS", "-Xms512m -Xmx512m")
+                .with_env_var("DISABLE_SECURITY_PLUGIN", "true")
+                .with_env_var("DISABLE_INSTALL_DEMO_CONFIG", "true")
+        } else {
+            request
+                .with_env_var("ES_JAVA_OPTS", "-Xms512m -Xmx512m")
+                .with_env_var("xpack.security.enabled", "false")
+        }
+    }
+}
+
+impl GenericImageBuilder {
+    /// See [`GenericImage::with_wait_for`].
+    pub fn with_wait_for(mut self, wait_for: WaitFor) -> Self {
+        self.image = self.image.with_wait_for(wait_for);
+        self
+    }
+
+    /// See [`GenericImage::with_entrypoint`].
+    pub fn with_entrypoint(mut self, entrypoint: &str) -> Self {
+        self.image = self.image.with_entrypoint(entrypoint);
+        self
+    }
+
+    /// See [`GenericImage::with_exposed_port`].
+    pub fn with_exposed_port(mut self, port: ContainerPort) -> Self {
+        self.image = self.image.with_exposed_port(port);
+        self
+    }
+
+    /// Returns the image, unless the name or the tag is empty, a port is exposed twice or
+    /// a ready condition connects to a port which isn't exposed (it would never be satisfied).
+    pub fn build(self) -> Result<GenericImage, GenericImageError> {
+        let image = self.image;
+        if image.name.trim().is_empty() {
+            return Err(GenericImageError::EmptyName);
+        }
+        if image.tag.trim().is_empty() {
+            return Err(GenericImageError::EmptyTag);
+        }
+        for (index, port) in image.exposed_ports.iter().enumerate() {
+            if image.exposed_ports[..index].contains(port) {
+                return Err(GenericImageError::DuplicateExposedPort(*port));
+            }
+        }
+        let probed_ports = image.wait_for.iter().flat_map(WaitFor::probed_ports);
+        for port in probed_ports {
+            match port {
+                Some(port) if !image.exposed_ports.contains(&port) => {
+                    return Err(GenericImageError::UnexposedWaitPort(port));
+                }
+                None if image.exposed_ports.is_empty() => {
+                    return Err(GenericImageError::NoExposedPort);
+                }
+                _ => {}
+            }
+        }
+        Ok(image)
+    }
 }
 
 impl Image for GenericImage {
@@ -122,4 +256,79 @@ mod tests {
         assert_eq!(second_key, "two-key");
         assert_eq!(second_value, "two-value");
     }
+
+    #[test]
+    fn builder_validates_configuration() {
+        use crate::core::{wait::PortWaitStrategy, IntoContainerPort};
+
+        let image = GenericImage::builder("redis", "7.2.4")
+            .with_exposed_port(6379.tcp())
+            .with_wait_for(WaitFor::port(6379))
+            .build()
+            .unwrap();
+        assert_eq!(image.expose_ports(), [6379.tcp()]);
+
+        assert_eq!(
+            GenericImage::builder("", "latest").build().unwrap_err(),
+            GenericImageError::EmptyName
+        );
+        assert_eq!(
+            GenericImage::builder("redis", " ").build().unwrap_err(),
+            GenericImageError::EmptyTag
+        );
+        assert_eq!(
+            GenericImage::builder("redis", "7.2.4")
+                .with_exposed_port(6379.tcp())
+                .with_exposed_port(6379.tcp())
+                .build()
+                .unwrap_err(),
+            GenericImageError::DuplicateExposedPort(6379.tcp())
+        );
+        assert_eq!(
+            GenericImage::builder("redis", "7.2.4")
+                .with_exposed_port(6379.tcp())
+                .with_wait_for(WaitFor::all([WaitFor::port(6380)]))
+                .build()
+                .unwrap_err(),
+            GenericImageError::UnexposedWaitPort(6380.tcp())
+        );
+        // checked from inside the container, so the port doesn't need to be exposed
+        assert!(GenericImage::builder("redis", "7.2.4")
+            .with_wait_for(WaitFor::Port(PortWaitStrategy::new(6379).in_container()))
+            .build()
+            .is_ok());
+    }
+
+    #[cfg(feature = "http_wait")]
+    #[test]
+    fn builder_requires_exposed_port_of_http_wait() {
+        use crate::core::wait::HttpWaitStrategy;
+
+        assert_eq!(
+            GenericImage::builder("nginx", "1.27-alpine")
+                .with_wait_for(WaitFor::http(HttpWaitStrategy::new("/")))
+                .build()
+                .unwrap_err(),
+            GenericImageError::NoExposedPort
+        );
+    }
+
+    #[test]
+    fn search_engine_defaults_depend_on_engine() {
+        let elasticsearch =
+            GenericImage::new("docker.elastic.co/elasticsearch/elasticsearch", "8.15.0")
+                .with_search_engine_defaults();
+        assert_eq!(elasticsearch.env_vars["discovery.type"], "single-node");
+        assert_eq!(elasticsearch.env_vars["xpack.security.enabled"], "false");
+        assert!(!elasticsearch
+            .env_vars
+            .contains_key("DISABLE_SECURITY_PLUGIN"));
+        assert_eq!(elasticsearch.ulimits.as_ref().map(Vec::len), Some(2));
+
+        let opensearch = GenericImage::new("opensearchproject/opensearch", "2.17.0")
+            .with_search_engine_defaults();
+        assert_eq!(opensearch.env_vars["discovery.type"], "single-node");
+        assert_eq!(opensearch.env_vars["DISABLE_SECURITY_PLUGIN"], "true");
+        assert!(!opensearch.env_vars.contains_key("xpack.security.enabled"));
+    }
 }
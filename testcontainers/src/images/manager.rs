@@ -0,0 +1,217 @@
+//! Management of the local images of the Docker daemon.
+//!
+//! Useful to clean up throwaway images created by tests, or to check the images available locally.
+//!
+//! # Example
+//! ```rust,no_run
+//! use testcontainers::images::manager::{image_exists, remove_image, tag_image};
+//!
+//! # async fn example() -> Result<(), testcontainers::TestcontainersError> {
+//! tag_image("redis:7.2.4", "my-redis:test").await?;
+//! assert!(image_exists("my-redis:test").await?);
+//!
+//! remove_image("my-redis:test").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use bollard::errors::Error as BollardError;
+use bollard_stubs::models::ImageSummary;
+
+use crate::core::{
+    client::{Client, ClientError},
+    error::Result,
+};
+
+/// Returns `true` if the image (e.g. `redis:7.2.4` or an image id) is available locally.
+pub async fn image_exists(descriptor: &str) -> Result<bool> {
+    let client = Client::lazy_client().await?;
+    match client.inspect_image(descriptor).await {
+        Ok(_) => Ok(true),
+        Err(ClientError::InspectImage {
+            err:
+                BollardError::DockerResponseServerError {
+                    status_code: 404, ..
+                },
+            ..
+        }) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Removes the image (or the tag, if the image has other tags).
+///
+/// Fails if the image is used by a container, see [`force_remove_image`] to remove it anyway.
+pub async fn remove_image(descriptor: &str) -> Result<()> {
+    let client = Client::lazy_client().await?;
+    client
+        .remove_image(descriptor, false)
+        .await
+        .map_err(Into::into)
+}
+
+/// Removes the image along with all its tags, even if it's used by stopped containers.
+pub async fn force_remove_image(descriptor: &str) -> Result<()> {
+    let client = Client::lazy_client().await?;
+    client
+        .remove_image(descriptor, true)
+        .await
+        .map_err(Into::into)
+}
+
+/// Tags the `source` image as `target` (e.g. `my-app:test`), the tag defaults to `latest`.
+pub async fn tag_image(source: &str, target: &str) -> Result<()> {
+    let (repo, tag) = split_reference(target);
+    let client = Client::lazy_client().await?;
+    client
+        .tag_image(source, repo, tag)
+        .await
+        .map_err(Into::into)
+}
+
+/// Lists the local images matching the filter.
+pub async fn list_images(filter: &ImageFilter) -> Result<Vec<LocalImage>> {
+    let client = Client::lazy_client().await?;
+    let images = client.list_images(filter.to_filters()).await?;
+    Ok(images.into_iter().map(LocalImage::from).collect())
+}
+
+/// Filter of the images returned by [`list_images`], matching all the images by default.
+#[derive(Debug, Clone, Default)]
+pub struct ImageFilter {
+    references: Vec<String>,
+    labels: Vec<String>,
+    dangling: Option<bool>,
+}
+
+impl ImageFilter {
+    /// Creates a filter matching all the images.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches the images by the reference, which may contain wildcards (e.g. `my-app:*`).
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.references.push(reference.into());
+        self
+    }
+
+    /// Matches the images having the label, either a key or a `key=value` pair.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Matches only the dangling (untagged) images, or only the tagged ones.
+    pub fn with_dangling(mut self, dangling: bool) -> Self {
+        self.dangling = Some(dangling);
+        self
+    }
+
+    fn to_filters(&self) -> HashMap<&str, Vec<&str>> {
+        let mut filters = HashMap::new();
+        if !self.references.is_empty() {
+            filters.insert(
+                "reference",
+                self.references.iter().map(String::as_str).collect(),
+            );
+        }
+        if !self.labels.is_empty() {
+            filters.insert("label", self.labels.iter().map(String::as_str).collect());
+        }
+        if let Some(dangling) = self.dangling {
+            filters.insert("dangling", vec![if dangling { "true" } else { "false" }]);
+        }
+        filters
+    }
+}
+
+/// An image available locally, as returned by [`list_images`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalImage {
+    id: String,
+    tags: Vec<String>,
+    size: u64,
+    labels: HashMap<String, String>,
+}
+
+impl LocalImage {
+    /// Id of the image (e.g. `sha256:...`).
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Tags of the image (e.g. `redis:7.2.4`), empty for dangling images.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Size of the image in bytes, including the layers shared with other images.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Labels of the image.
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+}
+
+impl From<ImageSummary> for LocalImage {
+    fn from(summary: ImageSummary) -> Self {
+        Self {
+            id: summary.id,
+            // dangling images are reported with the `<none>:<none>` tag by older daemons
+            tags: summary
+                .repo_tags
+                .into_iter()
+                .filter(|tag| tag != "<none>:<none>")
+                .collect(),
+            size: u64::try_from(summary.size).unwrap_or_default(),
+            labels: summary.labels,
+        }
+    }
+}
+
+/// Splits the reference into the repository and the tag, the port of a registry is not a tag.
+fn split_reference(reference: &str) -> (&str, &str) {
+    match reference.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+        _ => (reference, "latest"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_is_split_into_repo_and_tag() {
+        assert_eq!(split_reference("my-app:test"), ("my-app", "test"));
+        assert_eq!(split_reference("my-app"), ("my-app", "latest"));
+        assert_eq!(
+            split_reference("localhost:5000/my-app"),
+            ("localhost:5000/my-app", "latest")
+        );
+        assert_eq!(
+            split_reference("localhost:5000/my-app:1.0"),
+            ("localhost:5000/my-app", "1.0")
+        );
+    }
+
+    #[test]
+    fn filter_is_converted_to_daemon_filters() {
+        assert!(ImageFilter::new().to_filters().is_empty());
+
+        let filter = ImageFilter::new()
+            .with_reference("my-app:*")
+            .with_label("team=db")
+            .with_dangling(false);
+        let filters = filter.to_filters();
+        assert_eq!(filters["reference"], ["my-app:*"]);
+        assert_eq!(filters["label"], ["team=db"]);
+        assert_eq!(filters["dangling"], ["false"]);
+    }
+}
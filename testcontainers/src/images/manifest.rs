@@ -0,0 +1,171 @@
+use bollard_stubs::models::{DistributionInspect, OciPlatform};
+
+use crate::core::{client::Client, error::Result};
+
+/// Manifest of an image in a registry, resolved without pulling the image.
+#[derive(Debug, Clone)]
+pub struct RemoteManifest {
+    digest: Option<String>,
+    media_type: Option<String>,
+    size: Option<u64>,
+    platforms: Vec<ImagePlatform>,
+}
+
+/// A platform an image is available for, e.g. `linux/arm64/v8`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePlatform {
+    os: String,
+    architecture: String,
+    variant: Option<String>,
+}
+
+/// Queries the registry for the manifest of the image (e.g. `redis:7.2.4`), without pulling it.
+///
+/// The registry is contacted by the Docker daemon, using the same credentials as for pulling images.
+/// Allows to check that the image exists for the required platforms, and to fail fast before pulling it.
+///
+/// # Example
+/// ```rust,no_run
+/// use testcontainers::images::remote_manifest;
+///
+/// # async fn example() -> Result<(), testcontainers::TestcontainersError> {
+/// let manifest = remote_manifest("redis:7.2.4").await?;
+/// assert!(manifest.supports("linux/arm64"), "redis is not available for arm64");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn remote_manifest(descriptor: &str) -> Result<RemoteManifest> {
+    let client = Client::lazy_client().await?;
+    let inspect = client.inspect_registry_image(descriptor).await?;
+    Ok(inspect.into())
+}
+
+impl RemoteManifest {
+    /// Digest of the manifest (list), which can be used to pin the image, e.g. `redis@sha256:...`.
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// Media type of the manifest, e.g. `application/vnd.oci.image.index.v1+json` for multi-platform images.
+    pub fn media_type(&self) -> Option<&str> {
+        self.media_type.as_deref()
+    }
+
+    /// Size of the manifest in bytes (not the size of the image layers).
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Platforms the image is available for.
+    pub fn platforms(&self) -> &[ImagePlatform] {
+        &self.platforms
+    }
+
+    /// Checks whether the image is available for the platform in the `os/architecture[/variant]` format,
+    /// e.g. `linux/amd64` or `linux/arm/v7`. The variant is ignored if not specified.
+    pub fn supports(&self, platform: &str) -> bool {
+        let mut parts = platform.split('/');
+        let (Some(os), Some(architecture)) = (parts.next(), parts.next()) else {
+            return false;
+        };
+        let variant = parts.next();
+        self.platforms.iter().any(|candidate| {
+            candidate.os == os
+                && candidate.architecture == architecture
+                && variant.map_or(true, |variant| candidate.variant() == Some(variant))
+        })
+    }
+}
+
+impl ImagePlatform {
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    pub fn architecture(&self) -> &str {
+        &self.architecture
+    }
+
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+}
+
+impl std::fmt::Display for ImagePlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.os, self.architecture)?;
+        if let Some(variant) = &self.variant {
+            write!(f, "/{variant}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<DistributionInspect> for RemoteManifest {
+    fn from(inspect: DistributionInspect) -> Self {
+        Self {
+            digest: inspect.descriptor.digest,
+            media_type: inspect.descriptor.media_type,
+            size: inspect
+                .descriptor
+                .size
+                .and_then(|size| u64::try_from(size).ok()),
+            platforms: inspect
+                .platforms
+                .into_iter()
+                .map(ImagePlatform::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<OciPlatform> for ImagePlatform {
+    fn from(platform: OciPlatform) -> Self {
+        Self {
+            os: platform.os.unwrap_or_default(),
+            architecture: platform.architecture.unwrap_or_default(),
+            variant: platform.variant.filter(|variant| !variant.is_empty()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard_stubs::models::OciDescriptor;
+
+    use super::*;
+
+    fn platform(os: &str, architecture: &str, variant: Option<&str>) -> OciPlatform {
+        OciPlatform {
+            os: Some(os.to_string()),
+            architecture: Some(architecture.to_string()),
+            variant: variant.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn supports_platforms_of_manifest_list() {
+        let manifest = RemoteManifest::from(DistributionInspect {
+            descriptor: OciDescriptor {
+                media_type: Some("application/vnd.oci.image.index.v1+json".to_string()),
+                digest: Some("sha256:abc".to_string()),
+                size: Some(1024),
+            },
+            platforms: vec![
+                platform("linux", "amd64", None),
+                platform("linux", "arm", Some("v7")),
+            ],
+        });
+
+        assert_eq!(manifest.digest(), Some("sha256:abc"));
+        assert_eq!(manifest.size(), Some(1024));
+        assert!(manifest.supports("linux/amd64"));
+        assert!(manifest.supports("linux/arm"));
+        assert!(manifest.supports("linux/arm/v7"));
+        assert!(!manifest.supports("linux/arm/v6"));
+        assert!(!manifest.supports("linux/arm64"));
+        assert!(!manifest.supports("linux"));
+        assert_eq!(manifest.platforms()[1].to_string(), "linux/arm/v7");
+    }
+}
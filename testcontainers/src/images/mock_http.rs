@@ -0,0 +1,269 @@
+//! HTTP stub server, based on [WireMock](https://wiremock.org), along with a typed client to stub it.
+//!
+//! Useful to replace third-party HTTP APIs the tested code depends on.
+//!
+//! # Example
+//! ```rust,no_run
+//! use reqwest::Method;
+//! use testcontainers::{
+//!     images::mock_http::{MockHttpClient, MockHttpServer},
+//!     runners::AsyncRunner,
+//! };
+//!
+//! # async fn example() -> testcontainers::core::error::Result<()> {
+//! let server = MockHttpServer::default().start().await?;
+//! let mock = MockHttpClient::new(&server).await?;
+//!
+//! mock.stub_for(Method::GET, "/users/1")
+//!     .with_response_header("Content-Type", "application/json")
+//!     .respond(200, r#"{"id": 1, "name": "Jane"}"#)
+//!     .await?;
+//!
+//! // point the tested code to `mock.url("/users/1")`
+//! assert_eq!(mock.count_requests(Method::GET, "/users/1").await?, 0);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::{
+    core::{error::Result, wait::HttpWaitStrategy, ContainerPort, WaitFor},
+    ContainerAsync, Image, TestcontainersError,
+};
+
+/// Port of the WireMock server, serving both the stubs and the admin API.
+pub const MOCK_HTTP_PORT: ContainerPort = ContainerPort::Tcp(8080);
+
+const NAME: &str = "wiremock/wiremock";
+const TAG: &str = "3.9.2";
+
+/// WireMock server image, the stubs are registered with a [`MockHttpClient`].
+///
+/// The tag may be overridden with [`ImageExt::with_tag`](crate::ImageExt::with_tag).
+#[derive(Debug, Clone, Default)]
+pub struct MockHttpServer {
+    _priv: (),
+}
+
+impl Image for MockHttpServer {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn tag(&self) -> &str {
+        TAG
+    }
+
+    fn ready_conditions(&self) -> Vec<WaitFor> {
+        vec![WaitFor::http(
+            HttpWaitStrategy::new("/__admin/health")
+                .with_port(MOCK_HTTP_PORT)
+                .with_expected_status_code(200u16),
+        )]
+    }
+
+    fn expose_ports(&self) -> &[ContainerPort] {
+        &[MOCK_HTTP_PORT]
+    }
+}
+
+/// Client of a started [`MockHttpServer`], registering stubs through its admin API.
+#[derive(Debug, Clone)]
+pub struct MockHttpClient {
+    base_url: Url,
+    http: reqwest::Client,
+}
+
+impl MockHttpClient {
+    /// Creates a client of the server, connected to its mapped port.
+    pub async fn new<I: Image>(server: &ContainerAsync<I>) -> Result<Self> {
+        let host = server.get_host().await?;
+        let port = server.get_host_port_ipv4(MOCK_HTTP_PORT).await?;
+        let base_url =
+            Url::parse(&format!("http://{host}:{port}")).map_err(TestcontainersError::other)?;
+
+        Ok(Self {
+            base_url,
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Base URL of the server the tested code should call, e.g. `http://localhost:32768`.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// URL of the path on the server, e.g. `http://localhost:32768/users/1`.
+    pub fn url(&self, path: &str) -> Url {
+        // the base URL has no path, so joining can't fail
+        self.base_url
+            .join(path)
+            .unwrap_or_else(|_| self.base_url.clone())
+    }
+
+    /// Starts a stub of the requests with the method and the path, the query string isn't matched.
+    ///
+    /// The stub is registered once the response is set with [`StubBuilder::respond`].
+    pub fn stub_for(&self, method: Method, path: impl Into<String>) -> StubBuilder<'_> {
+        StubBuilder {
+            client: self,
+            method,
+            path: path.into(),
+            request_headers: BTreeMap::new(),
+            response_headers: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of requests with the method and the path received by the server so far.
+    pub async fn count_requests(&self, method: Method, path: &str) -> Result<u64> {
+        #[derive(Deserialize)]
+        struct Count {
+            count: u64,
+        }
+
+        let response = self
+            .admin(
+                "requests/count",
+                json!({ "method": method.as_str(), "urlPath": path }),
+            )
+            .await?;
+        let count: Count = response.json().await.map_err(TestcontainersError::other)?;
+        Ok(count.count)
+    }
+
+    /// Removes all the stubs and forgets the received requests.
+    pub async fn reset(&self) -> Result<()> {
+        self.admin("reset", Value::Null).await.map(|_| ())
+    }
+
+    async fn admin(&self, path: &str, body: Value) -> Result<reqwest::Response> {
+        let url = self.url(&format!("/__admin/{path}"));
+        let request = self.http.post(url);
+        let request = if body.is_null() {
+            request
+        } else {
+            request.json(&body)
+        };
+
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(TestcontainersError::other)
+    }
+}
+
+/// Stub of the requests matching the method and the path, see [`MockHttpClient::stub_for`].
+#[must_use = "the stub is registered by `respond`"]
+#[derive(Debug)]
+pub struct StubBuilder<'a> {
+    client: &'a MockHttpClient,
+    method: Method,
+    path: String,
+    request_headers: BTreeMap<String, String>,
+    response_headers: BTreeMap<String, String>,
+}
+
+impl StubBuilder<'_> {
+    /// Matches only the requests having the header with the value.
+    pub fn with_request_header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.request_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Adds the header to the response.
+    pub fn with_response_header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.response_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Registers the stub responding with the status and the body.
+    pub async fn respond(self, status: u16, body: impl Into<String>) -> Result<()> {
+        let client = self.client;
+        let mapping = self.into_mapping(status, json!({ "body": body.into() }));
+        client.admin("mappings", mapping).await.map(|_| ())
+    }
+
+    /// Registers the stub responding with the status and the JSON body.
+    pub async fn respond_json(self, status: u16, body: &impl serde::Serialize) -> Result<()> {
+        let body = serde_json::to_value(body).map_err(TestcontainersError::other)?;
+        let client = self.client;
+        let mapping = self.into_mapping(status, json!({ "jsonBody": body }));
+        client.admin("mappings", mapping).await.map(|_| ())
+    }
+
+    fn into_mapping(self, status: u16, mut response: Value) -> Value {
+        let mut request = json!({
+            "method": self.method.as_str(),
+            "urlPath": self.path,
+        });
+        if !self.request_headers.is_empty() {
+            let headers: serde_json::Map<_, _> = self
+                .request_headers
+                .into_iter()
+                .map(|(name, value)| (name, json!({ "equalTo": value })))
+                .collect();
+            request["headers"] = headers.into();
+        }
+
+        response["status"] = status.into();
+        if !self.response_headers.is_empty() {
+            response["headers"] = json!(self.response_headers);
+        }
+
+        json!({ "request": request, "response": response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stub_is_converted_to_wiremock_mapping() {
+        let client = MockHttpClient {
+            base_url: Url::parse("http://localhost:8080").unwrap(),
+            http: reqwest::Client::new(),
+        };
+
+        let mapping = client
+            .stub_for(Method::POST, "/orders")
+            .with_request_header("Authorization", "Bearer token")
+            .with_response_header("Location", "/orders/1")
+            .into_mapping(201, json!({ "body": "" }));
+
+        assert_eq!(
+            mapping,
+            json!({
+                "request": {
+                    "method": "POST",
+                    "urlPath": "/orders",
+                    "headers": { "Authorization": { "equalTo": "Bearer token" } },
+                },
+                "response": {
+                    "status": 201,
+                    "body": "",
+                    "headers": { "Location": "/orders/1" },
+                },
+            })
+        );
+        assert_eq!(
+            client.url("/orders").as_str(),
+            "http://localhost:8080/orders"
+        );
+    }
+}
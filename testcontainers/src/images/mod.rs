@@ -1 +1,8 @@
+pub use manifest::{remote_manifest, ImagePlatform, RemoteManifest};
+
 pub mod generic;
+pub mod manager;
+mod manifest;
+#[cfg(feature = "mock_http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock_http")))]
+pub mod mock_http;
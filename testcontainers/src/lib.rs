@@ -81,17 +81,37 @@ pub use crate::core::Container;
 #[cfg(feature = "reusable-containers")]
 pub use crate::core::ReuseDirective;
 pub use crate::core::{
-    copy::{CopyDataSource, CopyToContainer, CopyToContainerError},
+    copy::{CopyDataSource, CopyFromContainerError, CopyToContainer, CopyToContainerError},
     error::TestcontainersError,
+    image::set_image_policy,
     ContainerAsync, ContainerRequest, Image, ImageExt,
 };
 
 #[cfg(feature = "watchdog")]
 #[cfg_attr(docsrs, doc(cfg(feature = "watchdog")))]
 pub(crate) mod watchdog;
+#[cfg(feature = "watchdog")]
+#[cfg_attr(docsrs, doc(cfg(feature = "watchdog")))]
+pub use watchdog::on_session_end;
+
+#[cfg(feature = "harness")]
+#[cfg_attr(docsrs, doc(cfg(feature = "harness")))]
+pub mod harness;
+
+#[cfg(feature = "environment")]
+#[cfg_attr(docsrs, doc(cfg(feature = "environment")))]
+pub mod environment;
+
+#[cfg(feature = "scenario")]
+#[cfg_attr(docsrs, doc(cfg(feature = "scenario")))]
+pub mod scenario;
+
+#[cfg(feature = "cli")]
+#[doc(hidden)]
+pub mod cli;
 
 /// All available Docker images.
-mod images;
-pub use images::generic::GenericImage;
+pub mod images;
+pub use images::generic::{GenericImage, GenericImageBuilder, GenericImageError};
 
 pub mod runners;
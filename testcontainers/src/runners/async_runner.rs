@@ -1,25 +1,36 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
 use bollard::{
-    container::{Config, CreateContainerOptions},
-    models::{HostConfig, PortBinding},
+    container::{Config, CreateContainerOptions, NetworkingConfig},
+    models::{HealthConfig, HostConfig, ImageInspect, PortBinding},
 };
-use bollard_stubs::models::{HostConfigCgroupnsModeEnum, ResourcesUlimits};
+use bollard_stubs::models::HostConfigCgroupnsModeEnum;
+use futures::future::BoxFuture;
 
 use crate::{
     core::{
+        async_drop,
         client::{Client, ClientError},
+        containers::{capture::PacketCapture, request::TimeoutEscalation},
         copy::CopyToContainer,
         error::{Result, WaitContainerError},
+        image::policy,
+        lifecycle::{lifecycle_event, lifecycle_span},
         mounts::{AccessMode, Mount, MountType},
-        network::Network,
-        CgroupnsMode, ContainerState,
+        network::{Network, NetworkEndpoint},
+        CgroupnsMode, ContainerHandle,
     },
-    ContainerAsync, ContainerRequest, Image,
+    ContainerAsync, ContainerRequest, GenericImage, Image, TestcontainersError,
 };
 
 const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+/// Fraction of the startup timeout after which the diagnostics of a slow container are collected.
+const TIMEOUT_ESCALATION_THRESHOLD: f64 = 0.8;
 #[cfg(feature = "reusable-containers")]
 static TESTCONTAINERS_SESSION_ID: std::sync::OnceLock<ulid::Ulid> = std::sync::OnceLock::new();
 
@@ -40,9 +51,225 @@ pub(crate) fn session_id() -> &'static ulid::Ulid {
     TESTCONTAINERS_SESSION_ID.get_or_init(ulid::Ulid::new)
 }
 
+/// An identifier of the current `cargo` invocation, shared by all the test binaries it runs
+/// (as well as the processes of a test runner), so that they share their containers too.
+///
+/// The invocation is identified by the parent process of the test binary, along with its start time
+/// on Linux, as process ids are reused. Elsewhere than on unix, the identifier is limited to the current
+/// session, as the parent process can't be identified.
+#[cfg(feature = "reusable-containers")]
+pub(crate) fn invocation_id() -> &'static str {
+    static INVOCATION_ID: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+    INVOCATION_ID.get_or_init(|| {
+        #[cfg(unix)]
+        {
+            let parent = std::os::unix::process::parent_id();
+            process_invocation_id(parent).unwrap_or_else(|| parent.to_string())
+        }
+        #[cfg(not(unix))]
+        {
+            session_id().to_string()
+        }
+    })
+}
+
+/// Returns the invocation identifier of the running process, `None` if there is no such process.
+#[cfg(all(feature = "reusable-containers", unix))]
+fn process_invocation_id(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        // the start time is the 22nd field, the 2nd one (the command) is in parentheses and may contain spaces
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let (_, fields) = stat.rsplit_once(')')?;
+        let started = fields.split_whitespace().nth(19)?;
+        Some(format!("{pid}-{started}"))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Some(pid.to_string())
+    }
+}
+
+/// An identifier of the current test binary: the name of its package (`CARGO_PKG_NAME`, as set by `cargo`)
+/// along with the hash `cargo` suffixes it with, so that it differs between builds of the binary.
+#[cfg(feature = "reusable-containers")]
+fn test_binary() -> String {
+    let binary = std::env::current_exe().ok().and_then(|exe| {
+        let stem = exe.file_stem()?.to_str()?.to_string();
+        Some(match stem.rsplit_once('-') {
+            Some((_, hash)) => hash.to_string(),
+            None => stem,
+        })
+    });
+    let package = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    format!("{package}-{}", binary.unwrap_or_default())
+}
+
+/// An identifier of the boot of this host, so that the processes of other hosts sharing the daemon
+/// (or of previous boots) are not mistaken for the ones of this host.
+#[cfg(all(feature = "reusable-containers", target_os = "linux"))]
+fn boot_id() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/random/boot_id")
+        .ok()
+        .map(|id| id.trim().to_string())
+}
+
+/// Removes the containers shared within previous invocations on this host, which aren't removed
+/// on drop and would otherwise be left behind.
+///
+/// Only the containers whose parent process has exited are removed, the ones of concurrent
+/// invocations (e.g. in another terminal) are left alone. Elsewhere than on Linux, where the
+/// parent processes can't be told apart from new processes with the same id, nothing is removed.
+#[cfg(feature = "reusable-containers")]
+async fn reap_previous_invocations(client: &Client, namespace: &str) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        let Some(boot_id) = boot_id() else {
+            return Ok(());
+        };
+        let id_label = format!("{namespace}.invocation-id");
+        let parent_label = format!("{namespace}.invocation-parent");
+        let containers = client
+            .list_labeled_containers(&[format!("{namespace}.invocation-host={boot_id}")])
+            .await?;
+        for container in containers {
+            let labels = container.labels.unwrap_or_default();
+            let Some(invocation) = labels.get(&id_label) else {
+                continue;
+            };
+            let alive = labels
+                .get(&parent_label)
+                .and_then(|parent| parent.parse().ok())
+                .and_then(process_invocation_id)
+                .is_some_and(|current| current == *invocation);
+            if let (false, Some(id)) = (alive, container.id) {
+                log::debug!("Removing container {id} shared within a previous invocation");
+                if let Err(err) = client.rm(&id).await {
+                    log::warn!("Failed to remove container {id} of a previous invocation: {err}");
+                }
+            }
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (client, namespace);
+    Ok(())
+}
+
+/// Returns the name of the container to create, if any.
+///
+/// Containers shared within the invocation are named after their configuration, so that
+/// Docker rejects duplicates created concurrently by other test binaries or processes.
+fn container_name<I: Image>(container_req: &ContainerRequest<I>) -> Option<String> {
+    #[cfg(feature = "reusable-containers")]
+    if container_req.container_name().is_none()
+        && container_req.reuse() == crate::ReuseDirective::CurrentInvocation
+    {
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        invocation_id().hash(&mut hasher);
+        serde_json::to_string(container_req)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        return Some(format!("testcontainers-{:016x}", hasher.finish()));
+    }
+    container_req.container_name().clone()
+}
+
+/// Warns about the commands left running in a reused container, as they accumulate across the reuses.
+#[cfg(feature = "reusable-containers")]
+async fn warn_about_running_execs<I: Image>(container: &ContainerAsync<I>) {
+    match container.execs().await {
+        Ok(execs) => {
+            for exec in execs.iter().filter(|exec| exec.is_running()) {
+                log::warn!(
+                    "Command {:?} ({}) is still running in reused container {}",
+                    exec.cmd(),
+                    exec.id(),
+                    container.id()
+                );
+            }
+        }
+        Err(err) => log::debug!(
+            "Failed to list the commands of reused container {}: {err}",
+            container.id()
+        ),
+    }
+}
+
+/// Inspects the image, pulling it if missing.
+#[cfg(feature = "reusable-containers")]
+async fn inspect_or_pull_image(client: &Client, descriptor: &str) -> Result<ImageInspect> {
+    match client.inspect_image(descriptor).await {
+        Ok(image) => Ok(image),
+        Err(_) => {
+            client.pull_image(descriptor).await?;
+            Ok(client.inspect_image(descriptor).await?)
+        }
+    }
+}
+
+/// Whether the entrypoint of the container failed to start because it's missing or not executable,
+/// which the daemon reports with the exit codes of a shell (127 and 126 respectively).
+async fn shell_not_found(client: &Client, container_id: &str) -> bool {
+    let exit_code = client
+        .inspect(container_id)
+        .await
+        .ok()
+        .and_then(|container| container.state)
+        .and_then(|state| state.exit_code);
+    matches!(exit_code, Some(126 | 127))
+}
+
+/// Whether the container runs a healthcheck: the one of the create body, if any, overrides
+/// the one of the image, and `NONE` disables the healthcheck altogether.
+fn runs_healthcheck(container: Option<&HealthConfig>, image: Option<&HealthConfig>) -> bool {
+    let test = |healthcheck: &HealthConfig| {
+        healthcheck
+            .test
+            .as_ref()
+            .and_then(|test| test.first())
+            .cloned()
+    };
+    container
+        .and_then(test)
+        .or_else(|| image.and_then(test))
+        .is_some_and(|kind| kind != "NONE")
+}
+
+/// Hashes the serialized configuration with FNV-1a, as unlike the std hashers
+/// it's stable across Rust versions, so the hash of kept containers stays valid.
+#[cfg(feature = "reusable-containers")]
+fn configuration_hash<I: Image>(container_req: &ContainerRequest<I>, image_id: &str) -> String {
+    let configuration = serde_json::to_string(container_req).unwrap_or_default();
+    let hash = configuration
+        .bytes()
+        .chain(image_id.bytes())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Checks the image of the request against the policies, before it's pulled or started.
+fn check_image_policy<I: Image>(
+    client: &Client,
+    container_req: &ContainerRequest<I>,
+) -> Result<()> {
+    policy::check_image_policy(
+        &container_req.descriptor(),
+        container_req.image_policy(),
+        client.config.image_allowlist(),
+    )
+}
+
 #[async_trait]
 /// Helper trait to start containers asynchronously.
 ///
+/// A [`ContainerRequest`] (or a [`GenericImage`]) may also be awaited directly, which starts the container
+/// without importing the trait.
+///
 /// ## Example
 ///
 /// ```rust,no_run
@@ -58,6 +285,9 @@ pub(crate) fn session_id() -> &'static ulid::Ulid {
 /// ```
 pub trait AsyncRunner<I: Image> {
     /// Starts the container and returns an instance of `ContainerAsync`.
+    ///
+    /// The future is cancellation-safe: if it's dropped before completion (e.g. by a timeout),
+    /// the created container is removed, the same way as a dropped `ContainerAsync`.
     async fn start(self) -> Result<ContainerAsync<I>>;
 
     /// Pulls the image from the registry.
@@ -72,9 +302,11 @@ where
     I: Image,
 {
     async fn start(self) -> Result<ContainerAsync<I>> {
-        let container_req = self.into();
+        let started = Instant::now();
+        let mut container_req = self.into();
 
-        let client = Client::lazy_client().await?;
+        let client = Client::lazy_client_for(container_req.docker_host()).await?;
+        check_image_policy(&client, &container_req)?;
         let mut create_options: Option<CreateContainerOptions<String>> = None;
 
         let extra_hosts: Vec<_> = container_req
@@ -82,39 +314,66 @@ where
             .map(|(key, value)| format!("{key}:{value}"))
             .collect();
 
-        let labels = HashMap::<String, String>::from_iter(
+        // the image is inspected at most once per start
+        let mut image: Option<ImageInspect> = None;
+        let mut labels = HashMap::<String, String>::from_iter(
             container_req
                 .labels()
                 .iter()
                 .map(|(key, value)| (key.into(), value.into()))
-                .chain([
-                    (
-                        "org.testcontainers.managed-by".into(),
-                        "testcontainers".into(),
-                    ),
-                    #[cfg(feature = "reusable-containers")]
-                    {
-                        if container_req.reuse() != crate::ReuseDirective::CurrentSession {
-                            Default::default()
-                        } else {
-                            (
-                                "org.testcontainers.session-id".to_string(),
-                                session_id().to_string(),
-                            )
-                        }
-                    },
-                ])
                 .filter(|(_, value): &(_, String)| !value.is_empty()),
         );
+        // automatic labels are applied last, so they can't be overridden by the user
+        if let Some(namespace) = client.config.labels_namespace() {
+            labels.insert(
+                format!("{namespace}.managed-by"),
+                "testcontainers".to_string(),
+            );
+            #[cfg(feature = "reusable-containers")]
+            match container_req.reuse() {
+                crate::ReuseDirective::CurrentSession => {
+                    labels.insert(format!("{namespace}.session-id"), session_id().to_string());
+                }
+                crate::ReuseDirective::CurrentInvocation => {
+                    labels.insert(
+                        format!("{namespace}.invocation-id"),
+                        invocation_id().to_string(),
+                    );
+                    #[cfg(unix)]
+                    labels.insert(
+                        format!("{namespace}.invocation-parent"),
+                        std::os::unix::process::parent_id().to_string(),
+                    );
+                    #[cfg(target_os = "linux")]
+                    if let Some(boot_id) = boot_id() {
+                        labels.insert(format!("{namespace}.invocation-host"), boot_id);
+                    }
+                    reap_previous_invocations(&client, namespace).await?;
+                }
+                _ => {}
+            }
+            #[cfg(feature = "reusable-containers")]
+            if container_req.reuse_hash() && container_req.reuse() != crate::ReuseDirective::Never {
+                let inspect = inspect_or_pull_image(&client, &container_req.descriptor()).await?;
+                let hash =
+                    configuration_hash(&container_req, inspect.id.as_deref().unwrap_or_default());
+                labels.insert(format!("{namespace}.reuse-hash"), hash);
+                image = Some(inspect);
+            }
+        }
+        let container_name = container_name(&container_req);
 
         #[cfg(feature = "reusable-containers")]
         {
-            use crate::ReuseDirective::{Always, CurrentSession};
+            use crate::ReuseDirective::{Always, CurrentInvocation, CurrentSession};
 
-            if matches!(container_req.reuse(), Always | CurrentSession) {
+            if matches!(
+                container_req.reuse(),
+                Always | CurrentSession | CurrentInvocation
+            ) {
                 if let Some(container_id) = client
                     .get_running_container_id(
-                        container_req.container_name().as_deref(),
+                        container_name.as_deref(),
                         container_req.network().as_deref(),
                         &labels,
                     )
@@ -126,12 +385,21 @@ where
                         None
                     };
 
-                    return Ok(ContainerAsync::construct(
+                    let container = ContainerAsync::construct(
                         container_id,
                         client,
                         container_req,
-                        network,
-                    ));
+                        network.into_iter().collect(),
+                    );
+                    warn_about_running_execs(&container).await;
+                    return Ok(container);
+                }
+            }
+
+            // only the creator is labeled, the containers are looked up by the labels shared within the invocation
+            if container_req.reuse() == CurrentInvocation {
+                if let Some(namespace) = client.config.labels_namespace() {
+                    labels.insert(format!("{namespace}.test-binary"), test_binary());
                 }
             }
         }
@@ -149,11 +417,26 @@ where
                 ..Default::default()
             }),
             working_dir: container_req.working_dir().map(|dir| dir.to_string()),
+            hostname: container_req
+                .hostname()
+                .map(|hostname| hostname.to_string()),
+            open_stdin: Some(container_req.open_stdin()),
+            attach_stdin: Some(container_req.open_stdin()),
             ..Default::default()
         };
 
+        // the host config hinted by the image is overridden by the request,
+        // except for the sysctls, which are left to the create body modifier
+        let hints = container_req.image().host_config_hints();
+        if let Some(sysctls) = hints.sysctls_map() {
+            config.host_config = config.host_config.map(|mut host_config| {
+                host_config.sysctls = Some(sysctls);
+                host_config
+            });
+        }
+
         // shared memory
-        if let Some(bytes) = container_req.shm_size() {
+        if let Some(bytes) = container_req.shm_size().or(hints.shm_size()) {
             config.host_config = config.host_config.map(|mut host_config| {
                 host_config.shm_size = Some(bytes as i64);
                 host_config
@@ -170,9 +453,43 @@ where
         } else {
             None
         };
+        let mut networks: Vec<_> = network.into_iter().collect();
+
+        // aliases in the primary network are set on creation, other networks are connected afterwards
+        let mut additional_networks = Vec::new();
+        for (network, aliases) in container_req.network_aliases() {
+            let endpoint = NetworkEndpoint::new().with_aliases(aliases.iter().cloned());
+            if container_req.network().as_deref() == Some(network.as_str()) {
+                config.networking_config = Some(NetworkingConfig {
+                    endpoints_config: HashMap::from([(network.clone(), endpoint.into())]),
+                });
+            } else {
+                networks.extend(Network::new(network, client.clone()).await?);
+                additional_networks.push((network, endpoint));
+            }
+        }
+
+        // the MAC address is a setting of the endpoint, the container-wide one is deprecated since API v1.44
+        if let Some(mac_address) = container_req.mac_address() {
+            if let Some(network) = container_req.network() {
+                let networking_config =
+                    config
+                        .networking_config
+                        .get_or_insert_with(|| NetworkingConfig {
+                            endpoints_config: HashMap::new(),
+                        });
+                networking_config
+                    .endpoints_config
+                    .entry(network.clone())
+                    .or_default()
+                    .mac_address = Some(mac_address.to_string());
+            } else {
+                config.mac_address = Some(mac_address.to_string());
+            }
+        }
 
         // name of the container
-        if let Some(name) = container_req.container_name() {
+        if let Some(name) = &container_name {
             create_options = Some(CreateContainerOptions {
                 name: name.to_owned(),
                 platform: None,
@@ -251,18 +568,10 @@ where
         }
 
         // resource ulimits
-        if let Some(ulimits) = &container_req.ulimits {
+        let ulimits = hints.merge_ulimits(container_req.ulimits.as_deref().unwrap_or_default());
+        if !ulimits.is_empty() {
             config.host_config = config.host_config.map(|mut host_config| {
-                host_config.ulimits = Some(
-                    ulimits
-                        .iter()
-                        .map(|ulimit| ResourcesUlimits {
-                            name: ulimit.name.clone(),
-                            soft: ulimit.soft,
-                            hard: ulimit.hard,
-                        })
-                        .collect(),
-                );
+                host_config.ulimits = Some(ulimits);
                 host_config
             });
         }
@@ -272,22 +581,96 @@ where
             config.cmd = Some(cmd);
         }
 
+        // shell command overrides both entrypoint and cmd
+        let shell = if let Some(shell_cmd) = container_req.shell_cmd() {
+            let shell = client.image_shell(&container_req.descriptor()).await?;
+            config.entrypoint = Some(shell.clone());
+            config.cmd = Some(vec![shell_cmd.to_string()]);
+            Some(shell)
+        } else {
+            None
+        };
+
+        if let Some(modifier) = container_req.create_body_modifier() {
+            modifier(&mut config);
+        }
+        let healthcheck = config.healthcheck.clone();
+
         // create the container with options
-        let create_result = client
-            .create_container(create_options.clone(), config.clone())
-            .await;
-        let container_id = match create_result {
-            Ok(id) => Ok(id),
+        let create_started = Instant::now();
+        let mut pull = None;
+        let create_result = lifecycle_span!(
+            client.create_container(create_options.clone(), config.clone()),
+            "create",
+            image = container_req.descriptor()
+        )
+        .await;
+        let (container_id, creation_warnings) = match create_result {
+            Ok(created) => Ok(created),
             Err(ClientError::CreateContainer(
                 bollard::errors::Error::DockerResponseServerError {
                     status_code: 404, ..
                 },
             )) => {
-                client.pull_image(&container_req.descriptor()).await?;
-                client.create_container(create_options, config).await
+                let descriptor = container_req.descriptor();
+                let pull_started = Instant::now();
+                lifecycle_span!(client.pull_image(&descriptor), "pull", image = descriptor).await?;
+                pull = Some(pull_started.elapsed());
+                lifecycle_event!(
+                    "image pulled",
+                    image = descriptor,
+                    elapsed = pull_started.elapsed()
+                );
+                lifecycle_span!(
+                    client.create_container(create_options, config),
+                    "create",
+                    image = container_req.descriptor()
+                )
+                .await
+            }
+            #[cfg(feature = "reusable-containers")]
+            Err(ClientError::CreateContainer(
+                bollard::errors::Error::DockerResponseServerError {
+                    status_code: 409, ..
+                },
+            )) if container_req.reuse() == crate::ReuseDirective::CurrentInvocation => {
+                // another test binary or process of the invocation has created the container in the meantime
+                let name = container_name.unwrap_or_default();
+                return await_shared_container(name, client, container_req, networks).await;
             }
             res => res,
         }?;
+        let create = create_started.elapsed() - pull.unwrap_or_default();
+        lifecycle_event!(
+            "created",
+            id = container_id,
+            image = container_req.descriptor(),
+            elapsed = create
+        );
+        // removes the container if the startup fails or is cancelled before it's handed over
+        let guard = CreatedContainerGuard::new(container_id.clone(), client.clone());
+
+        for (network, endpoint) in additional_networks {
+            client
+                .connect_network(network, &container_id, endpoint.into())
+                .await?;
+        }
+
+        // the image is present once the container is created
+        if client.config.implicit_healthcheck_wait() && container_req.ready_conditions().is_empty()
+        {
+            let image = match image.take() {
+                Some(image) => image,
+                None => client.inspect_image(&container_req.descriptor()).await?,
+            };
+            container_req.implicit_healthcheck = runs_healthcheck(
+                healthcheck.as_ref(),
+                image
+                    .config
+                    .as_ref()
+                    .and_then(|config| config.healthcheck.as_ref()),
+            );
+        }
 
         let copy_to_sources: Vec<&CopyToContainer> =
             container_req.copy_to_sources().map(Into::into).collect();
@@ -298,8 +681,11 @@ where
                 .await?;
         }
 
+        // the watchdog stops containers via the default client only
         #[cfg(feature = "watchdog")]
-        if client.config.command() == crate::core::env::Command::Remove {
+        if client.config.command() == crate::core::env::Command::Remove
+            && container_req.docker_host().is_none()
+        {
             crate::watchdog::register(container_id.clone());
         }
 
@@ -307,32 +693,274 @@ where
             .startup_timeout()
             .unwrap_or(DEFAULT_STARTUP_TIMEOUT);
 
-        tokio::time::timeout(startup_timeout, async {
-            client.start_container(&container_id).await?;
+        let escalation = container_req
+            .timeout_escalation()
+            .cloned()
+            .map(|escalation| {
+                (
+                    escalation,
+                    ContainerHandle::new(&container_id, client.clone()),
+                )
+            });
 
-            let container =
-                ContainerAsync::new(container_id, client.clone(), container_req, network).await?;
+        let startup = tokio::time::timeout(startup_timeout, async {
+            let start_started = Instant::now();
+            match (client.start_container(&container_id).await, &shell) {
+                (Err(ClientError::StartContainer(source)), Some(shell))
+                    if shell_not_found(&client, &container_id).await =>
+                {
+                    return Err(TestcontainersError::other(format!(
+                        "shell '{}' is not available in the image '{}': {source}",
+                        shell[0],
+                        container_req.descriptor()
+                    )));
+                }
+                (result, _) => result?,
+            }
+            let start = start_started.elapsed();
+            lifecycle_event!("started", id = container_id, elapsed = start);
+
+            let log_startup_report = container_req.log_startup_report();
+            let packet_capture = match container_req.packet_capture() {
+                Some(output) => Some(PacketCapture::start(&container_id, output.into()).await?),
+                None => None,
+            };
+            let mut container =
+                ContainerAsync::new(guard.disarm(), client.clone(), container_req, networks)
+                    .await?
+                    .with_creation_warnings(creation_warnings)
+                    .with_packet_capture(packet_capture);
 
-            let state = ContainerState::new(container.id(), container.ports().await?);
+            let state = container.container_state().await?;
             for cmd in container.image().exec_after_start(state)? {
-                container.exec(cmd).await?;
+                container.exec_after_start(cmd).await?;
             }
 
+            let report = container.startup_report_mut();
+            report.pull = pull;
+            report.create = create;
+            report.start = start;
+            report.total = started.elapsed();
+            lifecycle_event!("ready", id = container_id, elapsed = report.total);
+            if log_startup_report {
+                log::info!(
+                    "Container {container_id} is ready: {}",
+                    container.startup_report()
+                );
+            }
             Ok(container)
-        })
-        .await
-        .map_err(|_| WaitContainerError::StartupTimeout)?
+        });
+        let startup = lifecycle_span!(startup, "startup", id = container_id);
+
+        match escalation {
+            Some((escalation, handle)) => {
+                start_with_escalation(startup, startup_timeout, escalation, handle).await
+            }
+            None => startup
+                .await
+                .map_err(|_| WaitContainerError::StartupTimeout)?,
+        }
     }
 
     async fn pull_image(self) -> Result<ContainerRequest<I>> {
         let container_req = self.into();
-        let client = Client::lazy_client().await?;
+        let client = Client::lazy_client_for(container_req.docker_host()).await?;
+        check_image_policy(&client, &container_req)?;
         client.pull_image(&container_req.descriptor()).await?;
 
         Ok(container_req)
     }
 }
 
+/// Awaiting a request starts the container, the same as [`AsyncRunner::start`],
+/// so the runner trait doesn't have to be imported:
+///
+/// ```rust,no_run
+/// use testcontainers::{core::IntoContainerPort, GenericImage, ImageExt};
+///
+/// # async fn test() -> testcontainers::core::error::Result<()> {
+/// let container = GenericImage::new("redis", "7.2.4")
+///     .with_exposed_port(6379.tcp())
+///     .with_env_var("DEBUG", "1")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+impl<I: Image + 'static> IntoFuture for ContainerRequest<I> {
+    type Output = Result<ContainerAsync<I>>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        AsyncRunner::start(self)
+    }
+}
+
+/// Awaiting an image starts the container, the same as [`AsyncRunner::start`].
+impl IntoFuture for GenericImage {
+    type Output = Result<ContainerAsync<GenericImage>>;
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        AsyncRunner::start(self)
+    }
+}
+
+/// Starts the containers concurrently, returning them once all of them are ready (in the order of the requests).
+///
+/// The images are pulled in parallel as well. If any container fails to start, the others are removed,
+/// including the ones which have already started, and the first error is returned.
+///
+/// ```rust,no_run
+/// use testcontainers::{runners::start_all, GenericImage, ImageExt};
+///
+/// # async fn test() -> testcontainers::core::error::Result<()> {
+/// let [postgres, redis] = start_all([
+///     GenericImage::new("postgres", "16-alpine").with_env_var("POSTGRES_PASSWORD", "postgres"),
+///     GenericImage::new("redis", "7.2.4").into(),
+/// ])
+/// .await?
+/// .try_into()
+/// .unwrap();
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start_all<I, R>(
+    requests: impl IntoIterator<Item = R>,
+) -> Result<Vec<ContainerAsync<I>>>
+where
+    R: Into<ContainerRequest<I>> + Send,
+    I: Image,
+{
+    // the pending starts are cancelled on the first error, which removes their containers,
+    // while the started ones are removed on drop
+    futures::future::try_join_all(requests.into_iter().map(AsyncRunner::start)).await
+}
+
+/// Removes the container on drop, unless it's handed over to [`ContainerAsync`].
+///
+/// Makes `start` cancellation-safe: once the container is constructed, [`ContainerAsync`] takes care of it.
+struct CreatedContainerGuard {
+    id: Option<String>,
+    client: std::sync::Arc<Client>,
+}
+
+impl CreatedContainerGuard {
+    fn new(id: String, client: std::sync::Arc<Client>) -> Self {
+        Self {
+            id: Some(id),
+            client,
+        }
+    }
+
+    fn disarm(mut self) -> String {
+        self.id.take().expect("container id is taken only once")
+    }
+}
+
+impl Drop for CreatedContainerGuard {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        if self.client.config.command() != crate::core::env::Command::Remove {
+            return;
+        }
+
+        log::debug!("Startup of container {id} has been interrupted, removing it");
+        let client = self.client.clone();
+        async_drop::async_drop(async move {
+            if let Err(err) = client.rm(&id).await {
+                log::error!("Failed to remove container {id} after interrupted startup: {err}");
+            }
+        });
+    }
+}
+
+/// Awaits a container shared within the invocation, which is being started by another process.
+#[cfg(feature = "reusable-containers")]
+async fn await_shared_container<I: Image>(
+    name: String,
+    client: std::sync::Arc<Client>,
+    container_req: ContainerRequest<I>,
+    networks: Vec<std::sync::Arc<Network>>,
+) -> Result<ContainerAsync<I>> {
+    log::debug!("Waiting for container {name} started by another process");
+    let startup_timeout = container_req
+        .startup_timeout()
+        .unwrap_or(DEFAULT_STARTUP_TIMEOUT);
+
+    tokio::time::timeout(startup_timeout, async {
+        let container_id = loop {
+            let inspect = client.inspect(&name).await?;
+            let running = inspect
+                .state
+                .as_ref()
+                .and_then(|state| state.running)
+                .unwrap_or(false);
+            if let (true, Some(id)) = (running, inspect.id) {
+                break id;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        };
+        ContainerAsync::new(container_id, client, container_req, networks).await
+    })
+    .await
+    .map_err(|_| WaitContainerError::StartupTimeout)?
+}
+
+/// Awaits the startup of the container, collecting its diagnostics once the escalation threshold is reached.
+async fn start_with_escalation<T>(
+    startup: impl Future<Output = std::result::Result<Result<T>, tokio::time::error::Elapsed>>,
+    startup_timeout: Duration,
+    escalation: TimeoutEscalation,
+    handle: ContainerHandle,
+) -> Result<T> {
+    let threshold = startup_timeout.mul_f64(TIMEOUT_ESCALATION_THRESHOLD);
+    let diagnostics = async move {
+        tokio::time::sleep(threshold).await;
+        log::warn!(
+            "Container {} is not ready after {threshold:?}, collecting diagnostics",
+            handle.id()
+        );
+        escalation(handle).await
+    };
+    tokio::pin!(startup, diagnostics);
+
+    let mut collected = None;
+    let result = loop {
+        tokio::select! {
+            result = &mut startup => break result,
+            output = &mut diagnostics, if collected.is_none() => collected = Some(output),
+        }
+    };
+
+    match result {
+        Ok(result) => result,
+        Err(_) => {
+            // the timeout may fire while the diagnostics are still being collected,
+            // they're given as much time as they had before the timeout
+            let diagnostics = match collected {
+                Some(diagnostics) => Some(diagnostics),
+                None => tokio::time::timeout(startup_timeout - threshold, diagnostics)
+                    .await
+                    .ok(),
+            };
+            match diagnostics {
+                Some(diagnostics) => {
+                    Err(WaitContainerError::StartupTimeoutWithDiagnostics(diagnostics).into())
+                }
+                None => {
+                    log::warn!(
+                        "Diagnostics were not collected within {:?}",
+                        startup_timeout - threshold
+                    );
+                    Err(WaitContainerError::StartupTimeout.into())
+                }
+            }
+        }
+    }
+}
+
 impl From<&Mount> for bollard::models::Mount {
     fn from(mount: &Mount) -> Self {
         let mount_type = match mount.mount_type() {
@@ -371,6 +999,108 @@ mod tests {
         ImageExt,
     };
 
+    #[cfg(feature = "reusable-containers")]
+    #[test]
+    fn containers_shared_within_invocation_are_named_after_configuration() {
+        use crate::ReuseDirective::{CurrentInvocation, CurrentSession};
+
+        // `cargo test` runs the binary with the name of its package
+        assert!(test_binary().starts_with("testcontainers-"));
+
+        let image = GenericImage::new("redis", "7.2.4");
+        let shared = container_name(&image.clone().with_reuse(CurrentInvocation));
+
+        assert!(shared
+            .as_deref()
+            .is_some_and(|name| name.starts_with("testcontainers-")));
+        assert_eq!(
+            shared,
+            container_name(&image.clone().with_reuse(CurrentInvocation))
+        );
+        assert_ne!(
+            shared,
+            container_name(
+                &image
+                    .clone()
+                    .with_env_var("A", "1")
+                    .with_reuse(CurrentInvocation)
+            )
+        );
+        assert_eq!(
+            container_name(&image.clone().with_reuse(CurrentSession)),
+            None
+        );
+        assert_eq!(
+            container_name(
+                &image
+                    .with_container_name("named")
+                    .with_reuse(CurrentInvocation)
+            ),
+            Some("named".to_string())
+        );
+    }
+
+    #[cfg(all(feature = "reusable-containers", target_os = "linux"))]
+    #[test]
+    fn invocation_is_identified_by_parent_process() {
+        let parent = std::os::unix::process::parent_id();
+        assert_eq!(
+            Some(invocation_id()),
+            process_invocation_id(parent).as_deref()
+        );
+        assert!(invocation_id().starts_with(&format!("{parent}-")));
+
+        // the ids of exited processes are reused, the start time tells them apart
+        let current = process_invocation_id(std::process::id()).unwrap();
+        assert_ne!(current, invocation_id());
+        assert_eq!(process_invocation_id(u32::MAX), None);
+    }
+
+    #[cfg(feature = "reusable-containers")]
+    #[test]
+    fn configuration_hash_depends_on_configuration_and_image() {
+        use crate::ReuseDirective::Always;
+
+        let request = |value: &str| {
+            GenericImage::new("redis", "7.2.4")
+                .with_env_var("A", value)
+                .with_reuse(Always)
+        };
+        let hash = configuration_hash(&request("1"), "sha256:1");
+
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, configuration_hash(&request("1"), "sha256:1"));
+        assert_ne!(hash, configuration_hash(&request("1"), "sha256:2"));
+        assert_ne!(hash, configuration_hash(&request("2"), "sha256:1"));
+        assert_ne!(
+            hash,
+            configuration_hash(&request("1").with_cmd(["redis-server"]), "sha256:1")
+        );
+    }
+
+    #[test]
+    fn healthcheck_of_create_body_overrides_the_one_of_image() {
+        let healthcheck = |test: &[&str]| HealthConfig {
+            test: Some(test.iter().map(|arg| arg.to_string()).collect()),
+            ..Default::default()
+        };
+        let image = healthcheck(&["CMD", "true"]);
+
+        assert!(!runs_healthcheck(None, None));
+        assert!(runs_healthcheck(None, Some(&image)));
+        assert!(!runs_healthcheck(None, Some(&healthcheck(&["NONE"]))));
+        assert!(!runs_healthcheck(
+            Some(&healthcheck(&["NONE"])),
+            Some(&image)
+        ));
+        assert!(runs_healthcheck(
+            Some(&healthcheck(&["CMD-SHELL", "true"])),
+            None
+        ));
+        // an empty test inherits the healthcheck of the image
+        assert!(runs_healthcheck(Some(&healthcheck(&[])), Some(&image)));
+    }
+
     /// Test that all user-supplied labels are added to containers started by `AsyncRunner::start`
     #[tokio::test]
     async fn async_start_should_apply_expected_labels() -> anyhow::Result<()> {
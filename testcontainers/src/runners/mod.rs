@@ -2,7 +2,7 @@ pub(crate) mod async_runner;
 #[cfg(feature = "blocking")]
 pub(crate) mod sync_runner;
 
-pub use self::async_runner::AsyncRunner;
+pub use self::async_runner::{start_all, AsyncRunner};
 #[cfg(feature = "blocking")]
 #[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
 pub use self::sync_runner::SyncRunner;
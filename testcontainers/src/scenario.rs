@@ -0,0 +1,376 @@
+//! Chainable DSL structuring multi-step interactions with containers.
+//!
+//! A [`Scenario`] is a sequence of steps (starting containers, executing commands, waiting for conditions
+//! and custom assertions) executed in order. Once a step fails, the diagnostics of all the containers
+//! started so far (their state and the tail of their logs) are captured in the resulting [`ScenarioError`],
+//! so failures of long integration tests point to the offending step right away.
+//!
+//! ```rust,no_run
+//! use testcontainers::{
+//!     core::{ExecCommand, IntoContainerPort, WaitFor},
+//!     scenario::Scenario,
+//!     GenericImage, ImageExt,
+//! };
+//!
+//! # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! Scenario::new()
+//!     .start(
+//!         GenericImage::new("postgres", "16-alpine")
+//!             .with_exposed_port(5432.tcp())
+//!             .with_env_var("POSTGRES_PASSWORD", "postgres"),
+//!     )
+//!     .then_wait(WaitFor::message_on_stderr(
+//!         "database system is ready to accept connections",
+//!     ))
+//!     .then_exec(ExecCommand::new([
+//!         "psql", "-U", "postgres", "-c", "CREATE TABLE users (id int)",
+//!     ]))
+//!     .then(|ctx| async move {
+//!         let port = ctx.last()?.get_host_port_ipv4(5432).await?;
+//!         // connect to the database and assert on its content
+//!         Ok::<_, testcontainers::TestcontainersError>(())
+//!     })
+//!     .run()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{error::Error, fmt, future::Future};
+
+use futures::future::{self, BoxFuture, FutureExt};
+
+use crate::{
+    core::{
+        error::{ExecError, Result},
+        ContainerHandle, ExecCommand, WaitFor,
+    },
+    runners::AsyncRunner,
+    ContainerAsync, ContainerRequest, Image, TestcontainersError,
+};
+
+/// Number of the last log lines of each container included in the diagnostics.
+const DIAGNOSTICS_LOG_LINES: usize = 20;
+
+/// A sequence of steps interacting with containers, see the [module documentation](self).
+#[derive(Default)]
+pub struct Scenario {
+    steps: Vec<Step>,
+}
+
+struct Step {
+    description: String,
+    action: Action,
+}
+
+type StartFn = Box<dyn FnOnce() -> BoxFuture<'static, Result<Box<dyn ScenarioContainer>>> + Send>;
+type CustomFn = Box<dyn FnOnce(ScenarioContext) -> BoxFuture<'static, Result<()>> + Send>;
+
+enum Action {
+    Start(StartFn),
+    Exec(ExecCommand),
+    Wait(WaitFor),
+    Custom(CustomFn),
+}
+
+impl Scenario {
+    /// Creates an empty scenario.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the container, the following steps are applied to it until another container is started.
+    pub fn start<I: Image + 'static>(mut self, request: impl Into<ContainerRequest<I>>) -> Self {
+        let request = request.into();
+        let description = format!("start {}", request.descriptor());
+        let start: StartFn = Box::new(move || {
+            async move {
+                let container = request.start().await?;
+                Ok(Box::new(container) as Box<dyn ScenarioContainer>)
+            }
+            .boxed()
+        });
+        self.steps.push(Step {
+            description,
+            action: Action::Start(start),
+        });
+        self
+    }
+
+    /// Executes the command in the last started container, failing the step unless it exits with code `0`.
+    pub fn then_exec(mut self, cmd: ExecCommand) -> Self {
+        self.steps.push(Step {
+            description: format!("exec {:?}", cmd.cmd),
+            action: Action::Exec(cmd),
+        });
+        self
+    }
+
+    /// Waits for the condition (e.g. a log message) to be met by the last started container.
+    pub fn then_wait(mut self, condition: impl Into<WaitFor>) -> Self {
+        let condition = condition.into();
+        self.steps.push(Step {
+            description: format!("wait for {condition:?}"),
+            action: Action::Wait(condition),
+        });
+        self
+    }
+
+    /// Runs a custom step, e.g. an assertion using a client of the tested service.
+    pub fn then<F, Fut, E>(self, step: F) -> Self
+    where
+        F: FnOnce(ScenarioContext) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.then_named("custom step", step)
+    }
+
+    /// Runs a custom step described by the name in the diagnostics.
+    pub fn then_named<F, Fut, E>(mut self, name: impl Into<String>, step: F) -> Self
+    where
+        F: FnOnce(ScenarioContext) -> Fut + Send + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let custom: CustomFn = Box::new(move |ctx| {
+            step(ctx)
+                .map(|result| result.map_err(TestcontainersError::other))
+                .boxed()
+        });
+        self.steps.push(Step {
+            description: name.into(),
+            action: Action::Custom(custom),
+        });
+        self
+    }
+
+    /// Runs the steps in order, stopping at the first failure.
+    ///
+    /// The started containers are removed once the scenario is completed (or failed).
+    pub async fn run(self) -> std::result::Result<(), ScenarioError> {
+        let mut containers: Vec<Box<dyn ScenarioContainer>> = Vec::new();
+        for (index, step) in self.steps.into_iter().enumerate() {
+            let step_number = index + 1;
+            log::debug!("Running scenario step #{step_number}: {}", step.description);
+
+            let result = match step.action {
+                Action::Start(start) => start().await.map(|container| containers.push(container)),
+                Action::Exec(cmd) => match containers.last() {
+                    Some(container) => container.exec(cmd).await,
+                    None => Err(no_container_started()),
+                },
+                Action::Wait(condition) => match containers.last() {
+                    Some(container) => container.wait(condition).await,
+                    None => Err(no_container_started()),
+                },
+                Action::Custom(custom) => {
+                    let ctx = ScenarioContext {
+                        containers: containers.iter().map(|c| c.handle()).collect(),
+                    };
+                    custom(ctx).await
+                }
+            };
+
+            if let Err(source) = result {
+                let diagnostics =
+                    future::join_all(containers.iter().map(|container| container.diagnostics()))
+                        .await
+                        .join("\n");
+                return Err(ScenarioError {
+                    step: step_number,
+                    description: step.description,
+                    source,
+                    diagnostics,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<_> = self.steps.iter().map(|step| &step.description).collect();
+        f.debug_struct("Scenario").field("steps", &steps).finish()
+    }
+}
+
+fn no_container_started() -> TestcontainersError {
+    TestcontainersError::other("no container has been started by the scenario yet")
+}
+
+/// Containers started by a [`Scenario`] so far, passed to the custom steps.
+#[derive(Debug, Clone)]
+pub struct ScenarioContext {
+    containers: Vec<ContainerHandle>,
+}
+
+impl ScenarioContext {
+    /// Handles of the started containers, in the order of the start steps.
+    pub fn containers(&self) -> &[ContainerHandle] {
+        &self.containers
+    }
+
+    /// Handle of the container started by the `index`-th start step (counting from `0`).
+    pub fn container(&self, index: usize) -> Result<&ContainerHandle> {
+        self.containers.get(index).ok_or_else(|| {
+            TestcontainersError::other(format!("container #{index} is not started by the scenario"))
+        })
+    }
+
+    /// Handle of the last started container.
+    pub fn last(&self) -> Result<&ContainerHandle> {
+        self.containers.last().ok_or_else(no_container_started)
+    }
+}
+
+/// Failure of a [`Scenario`] step, along with the diagnostics of the started containers.
+#[derive(Debug, thiserror::Error)]
+#[error("scenario step #{step} ({description}) failed: {source}\n{diagnostics}")]
+pub struct ScenarioError {
+    step: usize,
+    description: String,
+    source: TestcontainersError,
+    diagnostics: String,
+}
+
+impl ScenarioError {
+    /// Number of the failed step, counting from `1`.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Description of the failed step, e.g. `start redis:7.2.4`.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// State and the last log lines of the containers started before the failure.
+    pub fn diagnostics(&self) -> &str {
+        &self.diagnostics
+    }
+
+    /// Returns the underlying error of the step.
+    pub fn into_source(self) -> TestcontainersError {
+        self.source
+    }
+}
+
+/// Type-erased container started by a scenario, owning it until the scenario is completed.
+trait ScenarioContainer: Send + Sync {
+    fn handle(&self) -> ContainerHandle;
+
+    fn exec(&self, cmd: ExecCommand) -> BoxFuture<'_, Result<()>>;
+
+    fn wait(&self, condition: WaitFor) -> BoxFuture<'_, Result<()>>;
+
+    fn diagnostics(&self) -> BoxFuture<'_, String>;
+}
+
+impl<I: Image> ScenarioContainer for ContainerAsync<I> {
+    fn handle(&self) -> ContainerHandle {
+        ContainerAsync::handle(self)
+    }
+
+    fn exec(&self, cmd: ExecCommand) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let args = cmd.cmd.clone();
+            let output = ContainerAsync::exec(self, cmd)
+                .await?
+                .into_output(args)
+                .await?;
+            if !output.is_success() {
+                log::debug!(
+                    "Command {:?} failed, stderr: {}",
+                    output.cmd(),
+                    String::from_utf8_lossy(output.stderr())
+                );
+                return Err(ExecError::ExitCodeMismatch {
+                    expected: 0,
+                    actual: output.exit_code(),
+                }
+                .into());
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn wait(&self, condition: WaitFor) -> BoxFuture<'_, Result<()>> {
+        self.block_until_ready(vec![condition]).boxed()
+    }
+
+    fn diagnostics(&self) -> BoxFuture<'_, String> {
+        async move {
+            let state = match self.state().await {
+                Ok(state) => match state.exit_code() {
+                    Some(code) if !state.is_running() => {
+                        format!("{} (exit code {code})", state.status())
+                    }
+                    _ => state.status().to_string(),
+                },
+                Err(err) => format!("unknown ({err})"),
+            };
+            let image = self.image();
+            let mut diagnostics = format!(
+                "container {} ({}:{}): {state}\n",
+                self.id(),
+                image.name(),
+                image.tag()
+            );
+            for (source, logs) in [
+                ("stdout", self.stdout_to_vec().await),
+                ("stderr", self.stderr_to_vec().await),
+            ] {
+                let logs = logs.unwrap_or_default();
+                let logs = String::from_utf8_lossy(&logs);
+                let lines: Vec<_> = logs.lines().collect();
+                let tail = &lines[lines.len().saturating_sub(DIAGNOSTICS_LOG_LINES)..];
+                diagnostics.push_str(&format!("  last {source} lines:\n"));
+                for line in tail {
+                    diagnostics.push_str(&format!("    {line}\n"));
+                }
+            }
+            diagnostics
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scenario_stops_at_the_first_failed_step() {
+        let err = Scenario::new()
+            .then(|ctx| async move {
+                assert!(ctx.containers().is_empty());
+                Ok::<_, TestcontainersError>(())
+            })
+            .then_named("failing step", |_| async {
+                Err(std::io::Error::other("boom"))
+            })
+            .then(|_| async { Err::<(), _>("must not be executed") })
+            .run()
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.step(), 2);
+        assert_eq!(err.description(), "failing step");
+        assert!(err.to_string().contains("boom"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn container_steps_require_started_container() {
+        let err = Scenario::new()
+            .then_exec(ExecCommand::new(["true"]))
+            .run()
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.step(), 1);
+        assert!(err.diagnostics().is_empty());
+    }
+}
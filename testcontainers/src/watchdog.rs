@@ -3,9 +3,10 @@
 //! By default, the watchdog is disabled. To enable it, enable the `watchdog` feature.
 //! Note that it works in background thread and may panic.
 
-use std::{collections::BTreeSet, sync::Mutex, thread};
+use std::{collections::BTreeSet, future::Future, sync::Mutex, thread};
 
 use conquer_once::Lazy;
+use futures::{future::BoxFuture, FutureExt};
 use signal_hook::{
     consts::{SIGINT, SIGQUIT, SIGTERM},
     iterator::Signals,
@@ -21,28 +22,30 @@ static WATCHDOG: Lazy<Mutex<Watchdog>> = Lazy::new(|| {
             .expect("failed to start watchdog runtime in background");
 
         runtime.block_on(async {
+            // the session end hooks are still executed without a client
             let signal_docker = Client::lazy_client()
                 .await
-                .expect("failed to create docker client");
+                .map_err(|err| log::error!("Watchdog failed to create docker client: {err}"))
+                .ok();
             let mut signals = Signals::new([SIGTERM, SIGINT, SIGQUIT])
                 .expect("failed to register signal handler");
 
             for signal in &mut signals {
-                for container_id in WATCHDOG
-                    .lock()
-                    .map(|s| s.containers.clone())
-                    .unwrap_or_default()
-                {
-                    signal_docker
-                        .stop(&container_id)
-                        .await
-                        .expect("failed to stop container");
-                    signal_docker
-                        .rm(&container_id)
-                        .await
-                        .expect("failed to remove container")
+                if let Some(signal_docker) = &signal_docker {
+                    let containers = WATCHDOG
+                        .lock()
+                        .map(|s| s.containers.clone())
+                        .unwrap_or_default();
+                    remove_containers(signal_docker, containers).await;
                 }
 
+                // taken out, so the hooks are executed only once
+                let hooks = WATCHDOG
+                    .lock()
+                    .map(|mut s| std::mem::take(&mut s.session_end_hooks))
+                    .unwrap_or_default();
+                run_session_end_hooks(hooks).await;
+
                 let _ = signal_hook::low_level::emulate_default_handler(signal);
             }
         });
@@ -51,9 +54,31 @@ static WATCHDOG: Lazy<Mutex<Watchdog>> = Lazy::new(|| {
     Mutex::new(Watchdog::default())
 });
 
+/// Stops and removes the containers, going on with the others if one of them fails.
+async fn remove_containers(client: &Client, containers: BTreeSet<String>) {
+    for container_id in containers {
+        if let Err(err) = client.stop(&container_id, None).await {
+            log::error!("Watchdog failed to stop container {container_id}: {err}");
+        }
+        if let Err(err) = client.rm(&container_id).await {
+            log::error!("Watchdog failed to remove container {container_id}: {err}");
+        }
+    }
+}
+
+/// Executes the hooks in order, each one as a separate task, so a panicking hook doesn't prevent the next ones.
+async fn run_session_end_hooks(hooks: Vec<BoxFuture<'static, ()>>) {
+    for hook in hooks {
+        if let Err(err) = tokio::spawn(hook).await {
+            log::error!("Session end hook failed: {err}");
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct Watchdog {
     containers: BTreeSet<String>,
+    session_end_hooks: Vec<BoxFuture<'static, ()>>,
 }
 
 /// Register a container for observation
@@ -64,6 +89,34 @@ pub(crate) fn register(container_id: String) {
         .containers
         .insert(container_id);
 }
+
+/// Registers a callback executed once during the final cleanup of the test session.
+///
+/// Allows to tie the cleanup of external resources created alongside containers
+/// (e.g. temporary registries or seeded buckets) to the lifecycle of the containers.
+/// The callbacks are executed by the watchdog in registration order, after the observed containers
+/// are removed, when the process receives SIGTERM, SIGINT, or SIGQUIT.
+/// A panicking callback doesn't prevent the next ones from being executed.
+///
+/// Note that Rust doesn't provide a hook on the regular exit of a test binary,
+/// so the callbacks aren't executed if the process exits normally.
+///
+/// ```rust,no_run
+/// testcontainers::on_session_end(async move {
+///     // e.g. delete a bucket created for the tests
+/// });
+/// ```
+pub fn on_session_end<F>(hook: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    WATCHDOG
+        .lock()
+        .expect("failed to access watchdog")
+        .session_end_hooks
+        .push(hook.boxed());
+}
+
 /// Unregisters a container for observation
 pub(crate) fn unregister(container_id: &str) {
     WATCHDOG
@@ -72,3 +125,29 @@ pub(crate) fn unregister(container_id: &str) {
         .containers
         .remove(container_id);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn session_end_hooks_are_executed_in_order_despite_panics() {
+        let executed = Arc::new(Mutex::new(Vec::new()));
+        let hook = |n: u8| {
+            let executed = executed.clone();
+            async move {
+                if n == 2 {
+                    panic!("hook {n} failed");
+                }
+                executed.lock().unwrap().push(n);
+            }
+            .boxed()
+        };
+
+        run_session_end_hooks(vec![hook(1), hook(2), hook(3)]).await;
+
+        assert_eq!(*executed.lock().unwrap(), vec![1, 3]);
+    }
+}
@@ -1,16 +1,26 @@
 use std::time::Duration;
 
 use bollard::Docker;
+use futures::{future::BoxFuture, FutureExt, StreamExt, TryStreamExt};
 use testcontainers::{
     core::{
+        client::{client_capabilities, CgroupVersion},
+        error::{ExecError, WaitContainerError},
         logs::{consumer::logging_consumer::LoggingConsumer, LogFrame},
-        wait::{ExitWaitStrategy, LogWaitStrategy},
-        CmdWaitFor, ExecCommand, WaitFor,
+        wait::{
+            CompositeWaitStrategy, CustomWaitStrategy, ExitWaitStrategy, LogWaitStrategy,
+            PortWaitStrategy,
+        },
+        AccessMode, BudgetViolation, CmdWaitFor, ContainerEventKind, ContainerHandle,
+        ContainerStatus, ExecCommand, ExecFailurePolicy, FilesystemChangeKind, HostConfigHints,
+        ImageDecision, IntoContainerPort, Mount, Network, NetworkEndpoint, ResourceBudget, Volume,
+        WaitFor,
     },
-    runners::AsyncRunner,
-    GenericImage, Image, ImageExt,
+    images::manager::{image_exists, list_images, remove_image, tag_image, ImageFilter},
+    runners::{start_all, AsyncRunner},
+    GenericImage, Image, ImageExt, TestcontainersError,
 };
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 
 #[derive(Debug, Default)]
 pub struct HelloWorld;
@@ -165,6 +175,28 @@ async fn async_wait_for_http() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "grpc_wait")]
+#[tokio::test]
+async fn async_wait_for_grpc_health() -> anyhow::Result<()> {
+    use testcontainers::core::{wait::GrpcHealthWaitStrategy, IntoContainerPort};
+
+    let _ = pretty_env_logger::try_init();
+
+    // etcd serves the gRPC health checking protocol on its client port
+    let image = GenericImage::new("quay.io/coreos/etcd", "v3.5.17")
+        .with_exposed_port(2379.tcp())
+        .with_wait_for(WaitFor::grpc(
+            GrpcHealthWaitStrategy::new().with_timeout(Duration::from_secs(30)),
+        ))
+        .with_cmd([
+            "etcd",
+            "--listen-client-urls=http://0.0.0.0:2379",
+            "--advertise-client-urls=http://127.0.0.1:2379",
+        ]);
+    let _container = image.start().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn async_run_exec_fails_due_to_unexpected_code() -> anyhow::Result<()> {
     let _ = pretty_env_logger::try_init();
@@ -251,3 +283,1640 @@ async fn async_copy_files_to_container() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn async_copy_dir_from_container() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    container
+        .exec(
+            ExecCommand::new([
+                "sh",
+                "-c",
+                "mkdir -p /report/nested && echo summary > /report/index.txt \
+                 && echo details > /report/nested/run.sh && chmod 755 /report/nested/run.sh",
+            ])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+
+    let temp_dir = temp_dir::TempDir::new()?;
+    let host_dir = temp_dir.child("report");
+    container.copy_dir_from("/report", &host_dir).await?;
+
+    assert_eq!(
+        std::fs::read_to_string(host_dir.join("index.txt"))?,
+        "summary\n"
+    );
+    let script = host_dir.join("nested/run.sh");
+    assert_eq!(std::fs::read_to_string(&script)?, "details\n");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(
+            std::fs::metadata(&script)?.permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
+    let err = container
+        .copy_dir_from("/report/index.txt", temp_dir.child("file"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("is not a directory"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_open_file_streams_container_file() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    container
+        .exec(
+            ExecCommand::new(["sh", "-c", "mkdir /dump && seq 1 200000 > /dump/data.txt"])
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+
+    let mut reader = tokio::io::BufReader::new(container.open_file("/dump/data.txt").await?);
+    let mut first_line = String::new();
+    reader.read_line(&mut first_line).await?;
+    assert_eq!(first_line, "1\n");
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).await?;
+    assert_eq!(rest.lines().count(), 199_999);
+    assert!(rest.ends_with("200000\n"));
+
+    let err = container.open_file("/dump").await.err().unwrap();
+    assert!(err.to_string().contains("is not a regular file"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_container_handle_can_be_shared_with_tasks() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_exposed_port(80.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("server is ready"))
+        .start()
+        .await?;
+    let expected_port = container.get_host_port_ipv4(80).await?;
+
+    let handle = container.handle();
+    assert_eq!(handle.id(), container.id());
+
+    let port = tokio::spawn(async move { handle.get_host_port_ipv4(80).await }).await??;
+    assert_eq!(port, expected_port);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_startup_timeout_escalation_collects_diagnostics() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let err = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("never printed"))
+        .with_cmd(["sleep", "infinity"])
+        .with_startup_timeout(Duration::from_secs(5))
+        .with_startup_timeout_escalation(|container| async move {
+            match container.exec(["ps"]).await {
+                Ok(output) => String::from_utf8_lossy(output.stdout()).into_owned(),
+                Err(err) => err.to_string(),
+            }
+        })
+        .start()
+        .await
+        .unwrap_err();
+
+    let err = err.to_string();
+    assert!(err.contains("diagnostics"), "{err}");
+    assert!(err.contains("sleep infinity"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_startup_timeout_escalation_does_not_wait_for_hanging_diagnostics(
+) -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let started = std::time::Instant::now();
+    let err = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("never printed"))
+        .with_cmd(["sleep", "infinity"])
+        .with_startup_timeout(Duration::from_secs(5))
+        .with_startup_timeout_escalation(|_| std::future::pending::<String>())
+        .start()
+        .await
+        .unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(15));
+    assert!(
+        matches!(
+            err,
+            TestcontainersError::WaitContainer(WaitContainerError::StartupTimeout)
+        ),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_connect_container_to_custom_network() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let network = Network::builder()
+        .with_subnet("172.30.42.0/24")
+        .with_gateway("172.30.42.1")
+        .with_label("purpose", "testcontainers-tests")
+        .create()
+        .await?;
+
+    let client = GenericImage::new("alpine", "latest")
+        .with_network(network.name())
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    let server = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    network
+        .connect(
+            &server,
+            NetworkEndpoint::new()
+                .with_alias("backend")
+                .with_ipv4_address("172.30.42.10"),
+        )
+        .await?;
+
+    let mut exec = client
+        .exec(
+            ExecCommand::new(["sh", "-c", "ping -c 1 backend | head -n 1"])
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+    let output = String::from_utf8(exec.stdout_to_vec().await?)?;
+    assert!(output.contains("172.30.42.10"), "{output}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_join_multiple_networks_with_aliases() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let server = GenericImage::new("alpine", "latest")
+        .with_network("tc-frontend-net")
+        .with_network_alias("tc-frontend-net", "web")
+        .with_network_alias("tc-backend-net", "api")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    let frontend_client = GenericImage::new("alpine", "latest")
+        .with_network("tc-frontend-net")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    let backend_client = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    backend_client
+        .connect_to_network("tc-backend-net", ["client"])
+        .await?;
+
+    for (client, host) in [
+        (&frontend_client, "web"),
+        (&backend_client, "api"),
+        (&server, "client"),
+    ] {
+        client
+            .exec(
+                ExecCommand::new(["ping", "-c", "1", host])
+                    .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_chown_mounts_after_start() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_mount(Mount::tmpfs_mount("/data"))
+        .with_mount(Mount::tmpfs_mount("/readonly").with_access_mode(AccessMode::ReadOnly))
+        .with_mount_chown(1000, 1001)
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let mut res = container
+        .exec(ExecCommand::new([
+            "stat",
+            "-c",
+            "%u:%g",
+            "/data",
+            "/readonly",
+        ]))
+        .await?;
+    let stdout = String::from_utf8(res.stdout_to_vec().await?)?;
+    assert_eq!(stdout, "1000:1001\n0:0\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_read_log_lines_with_timestamps() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::exit(ExitWaitStrategy::new()))
+        .with_shell_cmd("echo out-1; echo err-1 >&2; echo out-2")
+        .start()
+        .await?;
+
+    let lines: Vec<_> = container
+        .timestamped_log_stream(false)
+        .into_lines()
+        .try_collect()
+        .await?;
+    let mut contents: Vec<_> = lines
+        .iter()
+        .map(|line| format!("{}:{}", line.source(), line.content_lossy()))
+        .collect();
+    contents.sort();
+    assert_eq!(contents, ["stderr:err-1", "stdout:out-1", "stdout:out-2"]);
+    assert!(lines.iter().all(|line| line.timestamp().is_some()));
+    Ok(())
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn async_wait_for_log_matching_regex() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let _container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::log(
+            LogWaitStrategy::matching(regex::Regex::new(r"^listening on port \d+$")?)
+                .with_any_source()
+                .with_times(2),
+        ))
+        .with_shell_cmd(
+            "echo 'listening on port 1234' >&2; echo 'listening on port 5678'; sleep infinity",
+        )
+        .start()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_cancelled_start_removes_container() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let start = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("never printed"))
+        .with_label("test-name", "async_cancelled_start_removes_container")
+        .with_cmd(["sleep", "infinity"])
+        .start();
+    let res = tokio::time::timeout(Duration::from_secs(5), start).await;
+    assert!(res.is_err(), "start must not complete");
+
+    let docker = Docker::connect_with_local_defaults()?;
+    let containers = docker
+        .list_containers(Some(bollard::container::ListContainersOptions {
+            all: true,
+            filters: std::collections::HashMap::from([(
+                "label".to_string(),
+                vec!["test-name=async_cancelled_start_removes_container".to_string()],
+            )]),
+            ..Default::default()
+        }))
+        .await?;
+    assert!(containers.is_empty(), "leaked containers: {containers:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_strategy_times_out_before_startup_timeout() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let started = std::time::Instant::now();
+    let res = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::log(
+            LogWaitStrategy::stdout("never printed").with_timeout(Duration::from_secs(2)),
+        ))
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await;
+
+    let err = res.expect_err("the log line is never printed");
+    assert!(
+        err.to_string()
+            .contains("wait condition is not met within 2s"),
+        "unexpected error: {err}"
+    );
+    assert!(started.elapsed() < Duration::from_secs(30));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_inspect_remote_manifest() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let manifest = testcontainers::images::remote_manifest("alpine:3.20").await?;
+    assert!(manifest
+        .digest()
+        .is_some_and(|digest| digest.starts_with("sha256:")));
+    assert!(manifest.supports("linux/amd64"));
+    assert!(manifest.supports("linux/arm64"));
+    assert!(!manifest.supports("windows/amd64"));
+
+    let err = testcontainers::images::remote_manifest("testcontainers/does-not-exist:0.0.0")
+        .await
+        .expect_err("the image doesn't exist");
+    assert!(err.to_string().contains("in the registry"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_command_success() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::command(
+            ExecCommand::new(["test", "-f", "ready"]).with_working_dir("/tmp"),
+        ))
+        .with_shell_cmd("sleep 2 && touch /tmp/ready && sleep infinity")
+        .start()
+        .await?;
+
+    let res = container
+        .exec(ExecCommand::new(["ls", "/tmp/ready"]))
+        .await?;
+    assert_eq!(res.exit_code().await?, Some(0));
+    Ok(())
+}
+
+/// Counts the containers it has been awaited for, the file is created by the container command.
+#[derive(Debug, Default)]
+struct ReadyFileWait {
+    ready_containers: std::sync::atomic::AtomicUsize,
+}
+
+impl CustomWaitStrategy for ReadyFileWait {
+    fn wait_until_ready<'a>(
+        &'a self,
+        container: &'a ContainerHandle,
+    ) -> BoxFuture<'a, testcontainers::core::error::Result<()>> {
+        async move {
+            while !container
+                .exec(["test", "-f", "/tmp/ready"])
+                .await?
+                .is_success()
+            {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            self.ready_containers
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[tokio::test]
+async fn async_share_stateful_custom_wait_strategy() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let strategy = std::sync::Arc::new(ReadyFileWait::default());
+    let start = || {
+        GenericImage::new("alpine", "latest")
+            .with_wait_for(WaitFor::custom(strategy.clone()))
+            .with_shell_cmd("sleep 1 && touch /tmp/ready && sleep infinity")
+            .start()
+    };
+    let (_first, _second) = tokio::try_join!(start(), start())?;
+
+    assert_eq!(
+        strategy
+            .ready_containers
+            .load(std::sync::atomic::Ordering::SeqCst),
+        2
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_connect_to_container_port() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let container = GenericImage::new("simple_web_server", "latest")
+        .with_exposed_port(80.tcp())
+        .start()
+        .await?;
+
+    container
+        .wait_for_port_connectable(80, Duration::from_secs(10))
+        .await?;
+
+    let mut stream = container.tcp_stream(80).await?;
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    // the port isn't exposed
+    assert!(container.tcp_stream(8080).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_manage_local_images() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    // make sure the image is available locally
+    let _ = GenericImage::new("hello-world", "latest").start().await?;
+
+    let tag = format!("testcontainers-manager-test:{}", std::process::id());
+    tag_image("hello-world:latest", &tag).await?;
+    assert!(image_exists(&tag).await?);
+
+    let images = list_images(&ImageFilter::new().with_reference(&tag)).await?;
+    assert_eq!(images.len(), 1);
+    assert!(images[0].tags().contains(&tag));
+
+    remove_image(&tag).await?;
+    assert!(!image_exists(&tag).await?);
+    // the original image keeps its tag
+    assert!(image_exists("hello-world:latest").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_with_create_body_modifier() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .with_create_body_modifier(|body| {
+            body.stop_signal = Some("SIGINT".to_string());
+            body.hostname = Some("modified".to_string());
+        })
+        .start()
+        .await?;
+
+    let docker = Docker::connect_with_local_defaults()?;
+    let inspect = docker.inspect_container(container.id(), None).await?;
+    let config = inspect.config.expect("container config");
+    assert_eq!(config.stop_signal.as_deref(), Some("SIGINT"));
+    assert_eq!(config.hostname.as_deref(), Some("modified"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_commit_container_to_image() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    container
+        .exec(
+            ExecCommand::new(["sh", "-c", "echo seeded > /seed"])
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+
+    let tag = std::process::id().to_string();
+    let snapshot = container.commit("testcontainers-commit-test", &tag).await?;
+    let seeded = snapshot.start().await?;
+    let output = seeded
+        .exec(ExecCommand::new(["cat", "/seed"]))
+        .await?
+        .stdout_to_vec()
+        .await?;
+    assert_eq!(String::from_utf8_lossy(&output).trim(), "seeded");
+
+    seeded.rm().await?;
+    remove_image(&format!("testcontainers-commit-test:{tag}")).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_with_hostname_and_mac_address() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .with_hostname("license-server")
+        .with_mac_address("02:42:ac:11:00:42")
+        .start()
+        .await?;
+
+    let hostname = container
+        .exec(ExecCommand::new(["hostname"]))
+        .await?
+        .stdout_to_vec()
+        .await?;
+    assert_eq!(String::from_utf8_lossy(&hostname).trim(), "license-server");
+
+    let mac_address = container
+        .exec(ExecCommand::new(["cat", "/sys/class/net/eth0/address"]))
+        .await?
+        .stdout_to_vec()
+        .await?;
+    assert_eq!(
+        String::from_utf8_lossy(&mac_address).trim(),
+        "02:42:ac:11:00:42"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "mock_http")]
+#[tokio::test]
+async fn async_stub_http_api_with_mock_server() -> anyhow::Result<()> {
+    use reqwest::Method;
+    use testcontainers::images::mock_http::{MockHttpClient, MockHttpServer};
+
+    let _ = pretty_env_logger::try_init();
+
+    let server = MockHttpServer::default().start().await?;
+    let mock = MockHttpClient::new(&server).await?;
+    mock.stub_for(Method::GET, "/users/1")
+        .with_response_header("Content-Type", "application/json")
+        .respond(200, r#"{"id":1}"#)
+        .await?;
+
+    let response = reqwest::get(mock.url("/users/1?expand=true")).await?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await?, r#"{"id":1}"#);
+
+    let response = reqwest::get(mock.url("/users/2")).await?;
+    assert_eq!(response.status(), 404);
+    assert_eq!(mock.count_requests(Method::GET, "/users/1").await?, 1);
+
+    mock.reset().await?;
+    assert_eq!(mock.count_requests(Method::GET, "/users/1").await?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_share_data_through_volume() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let label = ("test-name", "async_share_data_through_volume");
+
+    let volume = Volume::builder()
+        .with_label(label.0, label.1)
+        .create()
+        .await?;
+    assert_eq!(Volume::list([label]).await?, [volume.name()]);
+
+    let writer = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+        .with_mount(volume.mount("/data"))
+        .with_cmd(["sh", "-c", "echo shared > /data/file"])
+        .start()
+        .await?;
+    writer.rm().await?;
+
+    let reader = GenericImage::new("alpine", "latest")
+        .with_mount(volume.mount("/data"))
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    let output = reader
+        .exec(ExecCommand::new(["cat", "/data/file"]))
+        .await?
+        .stdout_to_vec()
+        .await?;
+    assert_eq!(String::from_utf8_lossy(&output).trim(), "shared");
+    reader.rm().await?;
+
+    volume.remove().await?;
+    assert!(Volume::list([label]).await?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_await_request_directly() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .await?;
+    assert!(container.state().await?.is_running());
+
+    let container = HelloWorld
+        .with_label("test-name", "async_await_request_directly")
+        .await?;
+    assert_eq!(container.image().name(), "hello-world");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_start_all_containers_concurrently() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let label = "test-name=async_start_all_containers_concurrently";
+    let request = || {
+        GenericImage::new("alpine", "latest")
+            .with_label("test-name", "async_start_all_containers_concurrently")
+            .with_cmd(["sleep", "infinity"])
+    };
+
+    let containers = start_all([request(), request()]).await?;
+    assert_eq!(containers.len(), 2);
+    assert_ne!(containers[0].id(), containers[1].id());
+    drop(containers);
+
+    let res = start_all([
+        request(),
+        GenericImage::new("non-existent-image-testcontainers", "latest").into(),
+    ])
+    .await;
+    assert!(res.is_err(), "start must fail");
+
+    let docker = Docker::connect_with_local_defaults()?;
+    let containers = docker
+        .list_containers(Some(bollard::container::ListContainersOptions {
+            all: true,
+            filters: std::collections::HashMap::from([(
+                "label".to_string(),
+                vec![label.to_string()],
+            )]),
+            ..Default::default()
+        }))
+        .await?;
+    assert!(containers.is_empty(), "leaked containers: {containers:?}");
+    Ok(())
+}
+
+#[cfg(feature = "environment")]
+#[tokio::test]
+async fn async_start_environment_in_dependency_order() -> anyhow::Result<()> {
+    use testcontainers::environment::{Environment, Service};
+
+    let _ = pretty_env_logger::try_init();
+    let sleeping = || GenericImage::new("alpine", "latest").with_cmd(["sleep", "infinity"]);
+
+    let env = Environment::new()
+        .with_service(
+            Service::new("app", sleeping())
+                .depends_on("db")
+                .depends_on_with(
+                    "migrations",
+                    WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)),
+                ),
+        )
+        .with_service(
+            Service::new(
+                "migrations",
+                GenericImage::new("alpine", "latest").with_cmd(["ping", "-c", "1", "db"]),
+            )
+            .depends_on("db"),
+        )
+        .with_service(Service::new("db", sleeping()))
+        .start()
+        .await?;
+
+    // the services are resolvable by their names on the network of the environment
+    let output = env
+        .container("app")?
+        .exec(["ping", "-c", "1", "db"])
+        .await?;
+    assert!(output.is_success(), "{output:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_exec_after_start_follows_failure_policy() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct SetupImage(Vec<ExecCommand>);
+
+    impl Image for SetupImage {
+        fn name(&self) -> &str {
+            "alpine"
+        }
+
+        fn tag(&self) -> &str {
+            "latest"
+        }
+
+        fn ready_conditions(&self) -> Vec<WaitFor> {
+            vec![]
+        }
+
+        fn exec_after_start(
+            &self,
+            _: testcontainers::core::ContainerState,
+        ) -> testcontainers::core::error::Result<Vec<ExecCommand>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    let _ = pretty_env_logger::try_init();
+
+    // fails on the first attempt only
+    let flaky = ExecCommand::new([
+        "sh",
+        "-c",
+        "test -f /tmp/attempted && exit 0; touch /tmp/attempted; exit 1",
+    ])
+    .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+    .with_failure_policy(ExecFailurePolicy::Retry {
+        attempts: 2,
+        backoff: Duration::from_millis(100),
+    });
+    let ignored = ExecCommand::new(["false"])
+        .with_cmd_ready_condition(CmdWaitFor::exit_code(0))
+        .with_failure_policy(ExecFailurePolicy::Warn);
+    let container = SetupImage(vec![flaky, ignored])
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    container.rm().await?;
+
+    let failing = ExecCommand::new(["sh", "-c", "echo 'topic creation failed' >&2; exit 3"])
+        .with_cmd_ready_condition(CmdWaitFor::exit_code(0));
+    let err = SetupImage(vec![failing])
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("exit code: 3"), "{err}");
+    assert!(err.contains("topic creation failed"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_check_resource_budget() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // busy loop, quickly exceeding the CPU time budget
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sh", "-c", "while true; do :; done"])
+        .with_resource_budget(ResourceBudget::new().with_max_cpu_time(Duration::from_millis(500)))
+        .start()
+        .await?;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(30);
+    let err = loop {
+        match container.check_resource_budget() {
+            Err(err) => break err,
+            Ok(()) if tokio::time::Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(500)).await
+            }
+            Ok(()) => anyhow::bail!("the resource budget violation wasn't reported"),
+        }
+    };
+    assert!(matches!(
+        err,
+        TestcontainersError::ResourceBudgetExceeded {
+            violation: BudgetViolation::CpuTime { .. },
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_resource_budget_is_monitored_across_restarts() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // exits at once on the first run, then busy loops for a few seconds before exiting
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd([
+            "sh",
+            "-c",
+            "if [ ! -f /tmp/ran ]; then touch /tmp/ran; exit 0; fi; \
+             end=$(($(date +%s) + 5)); while [ $(date +%s) -lt $end ]; do :; done",
+        ])
+        .with_resource_budget(ResourceBudget::new().with_max_cpu_time(Duration::from_millis(500)))
+        .start()
+        .await?;
+    container
+        .wait(WaitFor::exit(ExitWaitStrategy::new()))
+        .await?;
+
+    container.start().await?;
+    let err = container
+        .wait(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            TestcontainersError::ResourceBudgetExceeded {
+                violation: BudgetViolation::CpuTime { .. },
+                ..
+            }
+        ),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_stop_gracefully_with_stop_timeout() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // exits cleanly on SIGTERM, while a killed container exits with 137
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd([
+            "sh",
+            "-c",
+            "trap 'echo terminated; exit 0' TERM; while true; do sleep 0.1; done",
+        ])
+        .with_stop_timeout(Duration::from_secs(5))
+        .start()
+        .await?;
+
+    container.stop().await?;
+
+    let state = container.state().await?;
+    assert_eq!(state.exit_code(), Some(0));
+    let mut stdout = String::new();
+    container.stdout(false).read_to_string(&mut stdout).await?;
+    assert!(stdout.contains("terminated"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_drop_stops_gracefully_with_sub_second_stop_timeout() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd([
+            "sh",
+            "-c",
+            "trap 'exit 0' TERM; while true; do sleep 0.1; done",
+        ])
+        .with_stop_timeout(Duration::from_millis(500))
+        .start()
+        .await?;
+    let mut events = container.events();
+
+    drop(container);
+
+    let exit_code = tokio::time::timeout(Duration::from_secs(30), async {
+        while let Some(event) = events.next().await {
+            if let ContainerEventKind::Die { exit_code } = event?.kind() {
+                return Ok(*exit_code);
+            }
+        }
+        anyhow::bail!("the events ended before the container died")
+    })
+    .await??;
+    assert_eq!(exit_code, Some(0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_list_filesystem_changes() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    container
+        .exec(ExecCommand::new([
+            "sh",
+            "-c",
+            "echo out > /tmp/out.txt && rm /etc/motd",
+        ]))
+        .await?;
+
+    let changes = container.changes().await?;
+    let change = |path: &str| {
+        changes
+            .iter()
+            .find(|change| change.path() == path)
+            .map(|change| change.kind())
+    };
+    assert_eq!(change("/tmp/out.txt"), Some(FilesystemChangeKind::Added));
+    assert_eq!(change("/etc/motd"), Some(FilesystemChangeKind::Deleted));
+    assert_eq!(change("/tmp"), Some(FilesystemChangeKind::Modified));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_resolve_cgroup_path() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let capabilities = client_capabilities().await?;
+    let result = container.cgroup_path().await;
+    // only cgroup v2 and rootful Docker are supported
+    if capabilities.rootless()
+        || capabilities.podman()
+        || capabilities.cgroup_version() != Some(CgroupVersion::V2)
+    {
+        assert!(result.is_err(), "{result:?}");
+        return Ok(());
+    }
+
+    let path = result?;
+    assert!(path.starts_with("/sys/fs/cgroup"));
+    assert!(path.to_string_lossy().contains(container.id()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_exec_expecting_success() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let output = container
+        .exec_expecting_success(ExecCommand::new(["echo", "hello"]))
+        .await?;
+    assert_eq!(output.stdout(), b"hello\n");
+
+    let err = container
+        .exec_expecting_success(ExecCommand::new(["sh", "-c", "echo oops >&2; exit 3"]))
+        .await
+        .unwrap_err();
+    let TestcontainersError::Exec(ExecError::Failed(output)) = &err else {
+        anyhow::bail!("unexpected error: {err}");
+    };
+    assert_eq!(output.exit_code(), 3);
+    assert_eq!(output.stderr(), b"oops\n");
+    assert!(err.to_string().contains("stderr: oops"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_report_startup_timings() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("ready"))
+        .with_wait_for(WaitFor::millis(100))
+        .with_cmd(["sh", "-c", "echo ready; sleep infinity"])
+        .start()
+        .await?;
+
+    let report = container.startup_report();
+    assert_eq!(report.wait_steps().len(), 2);
+    assert!(matches!(
+        report.wait_steps()[0].condition(),
+        WaitFor::Log(_)
+    ));
+    assert!(report.wait_steps()[1].duration() >= Duration::from_millis(100));
+    assert!(report.total() >= report.create() + report.start() + report.wait());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_applies_host_config_hints_of_image() -> anyhow::Result<()> {
+    #[derive(Debug)]
+    struct HintedImage;
+
+    impl Image for HintedImage {
+        fn name(&self) -> &str {
+            "alpine"
+        }
+
+        fn tag(&self) -> &str {
+            "latest"
+        }
+
+        fn ready_conditions(&self) -> Vec<WaitFor> {
+            vec![]
+        }
+
+        fn host_config_hints(&self) -> HostConfigHints {
+            HostConfigHints::new()
+                .with_ulimit("nofile", 4096, Some(4096))
+                .with_ulimit("nproc", 512, Some(512))
+                .with_sysctl("net.core.somaxconn", "1024")
+        }
+    }
+
+    let _ = pretty_env_logger::try_init();
+
+    let container = HintedImage
+        .with_ulimit("nofile", 2048, Some(2048))
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let output = container
+        .exec_expecting_success(ExecCommand::new([
+            "sh",
+            "-c",
+            "ulimit -n; ulimit -u; cat /proc/sys/net/core/somaxconn",
+        ]))
+        .await?;
+    // the requested `nofile` ulimit takes precedence over the hinted one
+    assert_eq!(output.stdout(), b"2048\n512\n1024\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_with_packet_capture() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let dir = temp_dir::TempDir::new()?;
+    let output = dir.path().join("captures/ping.pcap");
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .with_packet_capture(&output)
+        .start()
+        .await?;
+    container
+        .exec_expecting_success(ExecCommand::new(["ping", "-c", "1", "127.0.0.1"]))
+        .await?;
+    container.rm().await?;
+
+    // pcap files start with a magic number, in the byte order of the writer
+    let capture = std::fs::read(&output)?;
+    assert!(
+        capture.starts_with(&[0xd4, 0xc3, 0xb2, 0xa1])
+            || capture.starts_with(&[0xa1, 0xb2, 0xc3, 0xd4]),
+        "not a pcap file: {:?}",
+        &capture[..capture.len().min(4)]
+    );
+    assert!(capture.len() > 24, "no packets were captured");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_log_ignores_earlier_lines() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("started"))
+        .with_cmd(["sh", "-c", "echo started; echo processed; sleep infinity"])
+        .start()
+        .await?;
+
+    // the line logged at startup doesn't count
+    let err = container
+        .wait_for_log("processed", Duration::from_secs(2))
+        .await
+        .expect_err("the message was logged before the call");
+    assert!(
+        matches!(
+            err,
+            TestcontainersError::WaitContainer(WaitContainerError::Timeout(_))
+        ),
+        "{err}"
+    );
+
+    container
+        .exec(ExecCommand::new([
+            "sh",
+            "-c",
+            "sleep 1; echo processed > /proc/1/fd/1",
+        ]))
+        .await?;
+    container
+        .wait_for_log("processed", Duration::from_secs(10))
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_applies_conditions_after_start() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("started"))
+        .with_cmd([
+            "sh",
+            "-c",
+            "trap 'echo stopping; exit 3' TERM; echo started; sleep infinity & wait",
+        ])
+        .start()
+        .await?;
+
+    let stopping = LogWaitStrategy::stdout("stopping").from_now();
+    container.kill(Some("SIGTERM")).await?;
+    container.wait(WaitFor::log(stopping)).await?;
+    container
+        .wait(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(3)))
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_rejects_image_denied_by_policy() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let result = GenericImage::new("alpine", "latest")
+        .with_image_policy(|descriptor| {
+            if descriptor.ends_with(":latest") {
+                ImageDecision::Deny("floating tags are not allowed".to_string())
+            } else {
+                ImageDecision::Allow
+            }
+        })
+        .start()
+        .await;
+
+    let err = result.expect_err("the image is denied");
+    assert!(
+        matches!(
+            &err,
+            TestcontainersError::ImagePolicyViolation { descriptor, .. } if descriptor == "alpine:latest"
+        ),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_log_follows_restarts() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // fails on the first run, restarted by its restart policy
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::log(
+            LogWaitStrategy::stdout("ready").with_follow_restarts(true),
+        ))
+        .with_create_body_modifier(|body| {
+            let host_config = body.host_config.get_or_insert_with(Default::default);
+            host_config.restart_policy = Some(bollard::models::RestartPolicy {
+                name: Some(bollard::models::RestartPolicyNameEnum::ON_FAILURE),
+                maximum_retry_count: Some(1),
+            });
+        })
+        .with_cmd([
+            "sh",
+            "-c",
+            "echo booting; test -f /tmp/initialized || { touch /tmp/initialized; exit 1; }; echo ready; sleep infinity",
+        ])
+        .start()
+        .await?;
+
+    let stdout = String::from_utf8(container.stdout_to_vec().await?)?;
+    assert_eq!(stdout.matches("booting").count(), 2, "{stdout}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_log_wait_follows_immediate_restart() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd([
+            "sh",
+            "-c",
+            "echo booting; test -f /tmp/initialized || { touch /tmp/initialized; exit 0; }; echo ready; sleep infinity",
+        ])
+        .start()
+        .await?;
+    container
+        .wait(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+        .await?;
+
+    // restarted while the strategy inspects the stopped container
+    let (waited, started) = tokio::join!(
+        container.wait(WaitFor::log(
+            LogWaitStrategy::stdout("ready")
+                .with_follow_restarts(true)
+                .with_timeout(Duration::from_secs(20)),
+        )),
+        container.start(),
+    );
+    started?;
+    waited?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_log_quiet() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let started = std::time::Instant::now();
+    let container = GenericImage::new("alpine", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::log_quiet_for(Duration::from_secs(2)))
+        .with_cmd([
+            "-c",
+            "for i in 1 2 3 4 5 6; do echo \"imported batch $i\"; sleep 0.5; done; sleep infinity",
+        ])
+        .start()
+        .await?;
+
+    assert!(started.elapsed() >= Duration::from_secs(4));
+    let stdout = String::from_utf8(container.stdout_to_vec().await?)?;
+    assert!(stdout.contains("imported batch 6"), "{stdout}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_log_quiet_fails_on_crash() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let result = GenericImage::new("alpine", "latest")
+        .with_entrypoint("sh")
+        .with_wait_for(WaitFor::log_quiet_for(Duration::from_secs(10)))
+        .with_cmd(["-c", "echo 'import failed'; exit 3"])
+        .start()
+        .await;
+
+    assert!(
+        matches!(
+            result,
+            Err(TestcontainersError::WaitContainer(
+                WaitContainerError::UnexpectedExitCode {
+                    expected: 0,
+                    actual: Some(3)
+                }
+            ))
+        ),
+        "{result:?}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_with_shell_cmd() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+        .with_shell_cmd("echo foo && echo $HOME | tr a-z A-Z")
+        .start()
+        .await?;
+
+    let stdout = String::from_utf8(container.stdout_to_vec().await?)?;
+    assert_eq!(stdout, "foo\n/ROOT\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_with_shell_cmd_without_shell() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // the image is built from scratch, with a single binary
+    let err = GenericImage::new("hello-world", "latest")
+        .with_shell_cmd("echo foo")
+        .start()
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .starts_with("shell '/bin/sh' is not available in the image 'hello-world:latest'"),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_container_state_snapshot() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::exit(ExitWaitStrategy::new().with_exit_code(0)))
+        .with_cmd(["sh", "-c", "exit 0"])
+        .start()
+        .await?;
+
+    let state = container.state().await?;
+    assert_eq!(state.status(), ContainerStatus::Exited);
+    assert_eq!(state.exit_code(), Some(0));
+    assert!(state.started_at().unwrap() <= state.finished_at().unwrap());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_restart_and_kill_container() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::command(ExecCommand::new([
+            "test",
+            "-f",
+            "/tmp/ready",
+        ])))
+        .with_shell_cmd("rm -f /tmp/ready && sleep 1 && touch /tmp/ready && sleep infinity")
+        .start()
+        .await?;
+    let started_at = container.state().await?.started_at().unwrap();
+
+    container.restart().await?;
+    let state = container.state().await?;
+    assert_eq!(state.status(), ContainerStatus::Running);
+    assert!(state.started_at().unwrap() > started_at);
+    let res = container
+        .exec(ExecCommand::new(["test", "-f", "/tmp/ready"]))
+        .await?;
+    assert_eq!(res.exit_code().await?, Some(0));
+
+    container.kill(Some("SIGKILL")).await?;
+    let state = container.state().await?;
+    assert_eq!(state.status(), ContainerStatus::Exited);
+    assert_eq!(state.exit_code(), Some(137));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_restart_waits_for_logs_of_new_run() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_wait_for(WaitFor::message_on_stdout("ready"))
+        .with_shell_cmd(
+            "rm -f /tmp/ready && sleep 1 && touch /tmp/ready && echo ready && sleep infinity",
+        )
+        .start()
+        .await?;
+
+    // the output of the first run must not satisfy the condition again
+    container.restart().await?;
+    let res = container
+        .exec(ExecCommand::new(["test", "-f", "/tmp/ready"]))
+        .await?;
+    assert_eq!(res.exit_code().await?, Some(0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_container_stats() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let stats = container.stats().await?;
+    assert!(stats.memory_usage().is_some_and(|usage| usage > 0));
+    assert!(stats.memory_limit().is_some_and(|limit| limit > 0));
+    assert_eq!(stats.pids(), Some(1));
+
+    let samples: Vec<_> = container.stats_stream().take(2).try_collect().await?;
+    assert_eq!(samples.len(), 2);
+    assert!(samples[1].read_at() > samples[0].read_at());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_expose_creation_warnings() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+    assert!(container.creation_warnings().is_empty());
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .with_network("host")
+        .with_mapped_port(18080, 80.tcp())
+        .start()
+        .await?;
+    assert!(
+        container
+            .creation_warnings()
+            .iter()
+            .any(|warning| warning.contains("Published ports are discarded")),
+        "{:?}",
+        container.creation_warnings()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_subscribe_to_container_events() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let events = container.events();
+    container.kill(None).await?;
+
+    let died = tokio::time::timeout(
+        Duration::from_secs(10),
+        events
+            .try_filter(|event| {
+                std::future::ready(matches!(event.kind(), ContainerEventKind::Die { .. }))
+            })
+            .try_next(),
+    )
+    .await??
+    .expect("the events stream doesn't end");
+    assert_eq!(
+        died.kind(),
+        &ContainerEventKind::Die {
+            exit_code: Some(137)
+        }
+    );
+    assert!(died.time().is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_exec_all_stops_at_first_failure() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let transcript = container
+        .exec_all([
+            ExecCommand::new(["sh", "-c", "echo seeding"]),
+            ExecCommand::new(["sh", "-c", "echo broken >&2; exit 3"]),
+            ExecCommand::new(["sh", "-c", "echo unreachable"]),
+        ])
+        .await?;
+
+    assert!(!transcript.is_success());
+    assert_eq!(transcript.outputs().len(), 2);
+    assert_eq!(transcript.outputs()[0].stdout(), b"seeding\n");
+
+    let failure = transcript.failure().expect("second command fails");
+    assert_eq!(failure.exit_code(), 3);
+    assert_eq!(failure.stderr(), b"broken\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_run_exec_with_options() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let mut exec = container
+        .exec(
+            ExecCommand::new(["sh", "-c", "echo $(id -u) $PWD $GREETING; cat"])
+                .with_user("nobody")
+                .with_working_dir("/tmp")
+                .with_env_var("GREETING", "hello")
+                .with_stdin("from stdin")
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+
+    let stdout = String::from_utf8(exec.stdout_to_vec().await?)?;
+    assert_eq!(stdout, "65534 /tmp hello\nfrom stdin");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_exec_as_overrides_container_user() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    // like images dropping privileges
+    let container = GenericImage::new("alpine", "latest")
+        .with_create_body_modifier(|body| body.user = Some("nobody".to_string()))
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let mut exec = container
+        .exec(ExecCommand::new(["id", "-u"]).with_cmd_ready_condition(CmdWaitFor::exit_code(0)))
+        .await?;
+    assert_eq!(exec.stdout_to_vec().await?, b"65534\n");
+
+    let mut exec = container
+        .exec_as(
+            "root",
+            ExecCommand::new(["id", "-u"]).with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+        )
+        .await?;
+    assert_eq!(exec.stdout_to_vec().await?, b"0\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_list_container_execs() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    container
+        .exec(ExecCommand::new(["true"]).with_cmd_ready_condition(CmdWaitFor::exit_code(0)))
+        .await?;
+    container.exec(ExecCommand::new(["sleep", "300"])).await?;
+
+    let execs = container.execs().await?;
+    assert_eq!(execs.len(), 2);
+    let finished = execs.iter().find(|e| e.cmd() == ["true"]).unwrap();
+    assert!(!finished.is_running());
+    assert_eq!(finished.exit_code(), Some(0));
+    let running = execs.iter().find(|e| e.cmd() == ["sleep", "300"]).unwrap();
+    assert!(running.is_running());
+
+    container.stop().await?;
+    let execs = container.execs().await?;
+    assert!(execs.iter().all(|e| !e.is_running()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_attach_stdin() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["cat"])
+        .with_open_stdin(true)
+        .start()
+        .await?;
+
+    let mut stdin = container.attach_stdin().await?;
+    stdin.write_all(b"hello from stdin\n").await?;
+    stdin.flush().await?;
+
+    let mut line = String::new();
+    container.stdout(true).read_line(&mut line).await?;
+    assert_eq!(line, "hello from stdin\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_port() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let _container = GenericImage::new("simple_web_server", "latest")
+        .with_exposed_port(80.tcp())
+        .with_wait_for(WaitFor::port(80.tcp()))
+        .start()
+        .await?;
+
+    let _container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::Port(PortWaitStrategy::new(80).in_container()))
+        .start()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_wait_for_composite_conditions() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let _container = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(WaitFor::any([
+            WaitFor::message_on_stdout("this message never appears"),
+            WaitFor::all([
+                WaitFor::message_on_stdout("server is ready"),
+                WaitFor::message_on_stdout("server is ready"),
+            ]),
+        ]))
+        .start()
+        .await?;
+
+    let res = GenericImage::new("simple_web_server", "latest")
+        .with_wait_for(
+            CompositeWaitStrategy::all([WaitFor::message_on_stdout("this message never appears")])
+                .with_timeout(Duration::from_secs(1))
+                .into(),
+        )
+        .start()
+        .await;
+    assert!(res.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_container_image_metadata() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()
+        .await?;
+
+    let metadata = container.image_metadata().await?;
+    assert!(metadata.id().starts_with("sha256:"));
+    assert!(metadata.digest().is_some_and(|d| d.starts_with("sha256:")));
+    assert!(metadata.created().is_some());
+    assert!(metadata.size().is_some_and(|size| size > 0));
+    Ok(())
+}
@@ -206,6 +206,31 @@ fn sync_run_exec() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn sync_open_file_streams_container_file() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()?;
+    container.exec(
+        ExecCommand::new(["sh", "-c", "mkdir /dump && seq 1 200000 > /dump/data.txt"])
+            .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+    )?;
+
+    let mut content = String::new();
+    container
+        .open_file("/dump/data.txt")?
+        .read_to_string(&mut content)?;
+    assert_eq!(content.lines().count(), 200_000);
+    assert!(content.starts_with("1\n"));
+    assert!(content.ends_with("200000\n"));
+
+    let err = container.open_file("/dump").err().unwrap();
+    assert!(err.to_string().contains("is not a regular file"), "{err}");
+    Ok(())
+}
+
 #[test]
 fn sync_run_with_log_consumer() -> anyhow::Result<()> {
     let _ = pretty_env_logger::try_init();
@@ -271,3 +296,47 @@ fn sync_copy_files_to_container() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "harness")]
+#[test]
+fn harness_can_start_containers() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let harness = testcontainers::harness::TestHarness::new()?;
+    let container = harness.start(get_server_container(None).with_exposed_port(80.tcp()))?;
+
+    let port = container.get_host_port_ipv4(80.tcp())?;
+    let response = harness.block_on(async move {
+        tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .map(|_| ())
+    });
+    assert!(response.is_ok());
+    Ok(())
+}
+
+#[test]
+fn sync_operations_on_removed_container_return_errors() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let container = GenericImage::new("alpine", "latest")
+        .with_cmd(["sleep", "infinity"])
+        .start()?;
+
+    // removed behind the back of the container
+    let options = bollard::container::RemoveContainerOptions {
+        force: true,
+        ..Default::default()
+    };
+    tokio::runtime::Runtime::new()?.block_on(
+        bollard::Docker::connect_with_local_defaults()?
+            .remove_container(container.id(), Some(options)),
+    )?;
+
+    assert!(container.ports().is_err());
+    assert!(container.state().is_err());
+    assert!(container.stop().is_err());
+    assert!(container.stdout_to_vec().is_err());
+    // dropping the container only logs the failure of its removal
+    drop(container);
+    Ok(())
+}